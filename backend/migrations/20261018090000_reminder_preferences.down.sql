@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS public.reminder_log;
+
+DROP TABLE IF EXISTS public.reminder_preferences;
@@ -0,0 +1,29 @@
+-- TABEL 5: REMINDER PREFERENCES (Pengingat Pribadi via DM)
+CREATE TABLE IF NOT EXISTS public.reminder_preferences (
+    user_id VARCHAR(255) PRIMARY KEY,   -- Nomor WA User (juga chat id DM)
+    enabled BOOLEAN NOT NULL DEFAULT FALSE,
+    digest_times TIME[] NOT NULL DEFAULT ARRAY['07:00'::time],  -- Jam ringkasan harian (WIB)
+    lead_minutes INTEGER[] NOT NULL DEFAULT ARRAY[1440, 180],   -- H-1 dan 3 jam sebelum deadline
+    quiet_start TIME,                   -- Jam tenang mulai (WIB)
+    quiet_end TIME,                     -- Jam tenang selesai (WIB)
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+-- TABEL 6: REMINDER LOG (Mencegah pengingat terkirim 2x)
+CREATE TABLE IF NOT EXISTS public.reminder_log (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id VARCHAR(255) NOT NULL,
+    reminder_key TEXT NOT NULL,         -- "digest:<tanggal> <jam>" atau "lead:<assignment_id>:<menit>"
+    sent_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+
+    UNIQUE(user_id, reminder_key)
+);
+
+CREATE INDEX IF NOT EXISTS idx_reminder_preferences_enabled
+ON public.reminder_preferences (enabled) WHERE enabled;
+
+alter table public.reminder_preferences enable row level security;
+alter table public.reminder_log enable row level security;
+
+create policy "Enable access to all users" on public.reminder_preferences for all using (true) with check (true);
+create policy "Enable access to all users" on public.reminder_log for all using (true) with check (true);
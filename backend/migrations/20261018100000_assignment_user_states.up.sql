@@ -0,0 +1,19 @@
+-- TABEL 7: ASSIGNMENT USER STATES (Snooze / Hide per user)
+-- Sengaja dipisah dari user_completions supaya statistik selesai tidak terganggu
+CREATE TABLE IF NOT EXISTS public.assignment_user_states (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id VARCHAR(255) NOT NULL,  -- Nomor WA User
+    assignment_id UUID NOT NULL REFERENCES public.assignments(id) ON DELETE CASCADE,
+    hidden BOOLEAN NOT NULL DEFAULT FALSE,          -- Disembunyikan permanen (tidak berlaku untuk user ini)
+    snoozed_until TIMESTAMP WITH TIME ZONE,         -- Disembunyikan sementara sampai waktu ini
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+
+    UNIQUE(user_id, assignment_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_assignment_user_states_lookup 
+ON public.assignment_user_states (user_id, assignment_id);
+
+alter table public.assignment_user_states enable row level security;
+
+create policy "Enable access to all users" on public.assignment_user_states for all using (true) with check (true);
@@ -0,0 +1,10 @@
+-- TABEL 8: USER PARALLELS (Kelas paralel milik user, untuk #jadwal)
+CREATE TABLE IF NOT EXISTS public.user_parallels (
+    user_id VARCHAR(255) PRIMARY KEY,   -- Nomor WA User
+    parallels TEXT[] NOT NULL DEFAULT '{}',   -- Lowercase, mis. {k1,p2,r1}
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+alter table public.user_parallels enable row level security;
+
+create policy "Enable access to all users" on public.user_parallels for all using (true) with check (true);
@@ -0,0 +1,10 @@
+-- TABEL 9: CALENDAR TOKENS (Link langganan kalender .ics per user)
+CREATE TABLE IF NOT EXISTS public.calendar_tokens (
+    user_id VARCHAR(255) PRIMARY KEY,   -- Nomor WA User
+    token TEXT NOT NULL UNIQUE,         -- Acak, tidak bisa ditebak (bagian dari URL)
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+alter table public.calendar_tokens enable row level security;
+
+create policy "Enable access to all users" on public.calendar_tokens for all using (true) with check (true);
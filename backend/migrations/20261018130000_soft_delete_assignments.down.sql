@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS public.idx_assignments_deleted_at;
+ALTER TABLE public.assignments
+    DROP COLUMN IF EXISTS deleted_by,
+    DROP COLUMN IF EXISTS deleted_at;
@@ -0,0 +1,8 @@
+-- SOFT DELETE TUGAS (#delete → #trash / #restore, dihapus permanen setelah N hari)
+ALTER TABLE public.assignments
+    ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMP WITH TIME ZONE,  -- NULL = aktif
+    ADD COLUMN IF NOT EXISTS deleted_by VARCHAR(255);              -- Nomor WA yang menghapus
+
+CREATE INDEX IF NOT EXISTS idx_assignments_deleted_at
+    ON public.assignments (deleted_at)
+    WHERE deleted_at IS NOT NULL;
@@ -0,0 +1,16 @@
+-- TABEL 10: ASSIGNMENT NOTES (Catatan pribadi per user per tugas)
+-- Hanya ditampilkan di chat pribadi (#notes, kartu detail #<nomor>), tidak pernah di grup / broadcast
+CREATE TABLE IF NOT EXISTS public.assignment_notes (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id VARCHAR(255) NOT NULL,  -- Nomor WA User
+    assignment_id UUID NOT NULL REFERENCES public.assignments(id) ON DELETE CASCADE,
+    note TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_assignment_notes_lookup 
+ON public.assignment_notes (user_id, assignment_id);
+
+alter table public.assignment_notes enable row level security;
+
+create policy "Enable access to all users" on public.assignment_notes for all using (true) with check (true);
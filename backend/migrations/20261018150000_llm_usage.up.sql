@@ -0,0 +1,21 @@
+-- TABEL 11: LLM USAGE (Satu baris per panggilan ke provider LLM)
+-- Dipakai untuk #usage dan budget cap per provider (lihat llm_models.json)
+CREATE TABLE IF NOT EXISTS public.llm_usage (
+    id BIGSERIAL PRIMARY KEY,
+    provider VARCHAR(20) NOT NULL,             -- groq | gemini | local
+    model VARCHAR(255) NOT NULL,
+    task VARCHAR(20) NOT NULL,                 -- context | extraction | vision | matching | dedup | query
+    prompt_tokens INTEGER NOT NULL DEFAULT 0,
+    completion_tokens INTEGER NOT NULL DEFAULT 0,
+    latency_ms INTEGER NOT NULL,
+    outcome VARCHAR(20) NOT NULL,              -- ok | invalid | rate_limited | error
+    cost_usd DOUBLE PRECISION NOT NULL DEFAULT 0,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_llm_usage_provider_time 
+ON public.llm_usage (provider, created_at);
+
+alter table public.llm_usage enable row level security;
+
+create policy "Enable access to all users" on public.llm_usage for all using (true) with check (true);
@@ -0,0 +1,27 @@
+-- TABEL 12: ASSIGNMENT REVIEWS (Hasil ekstraksi yang kurang yakin, menunggu #approve / #reject)
+-- Baru masuk ke assignments setelah di-approve PJ Matkul di grup debug
+CREATE TABLE IF NOT EXISTS public.assignment_reviews (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    course_id UUID REFERENCES public.courses(id) ON DELETE SET NULL,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    deadline TIMESTAMP WITH TIME ZONE,
+    parallel_code TEXT,
+    sender_id TEXT,
+    message_id TEXT NOT NULL,
+    confidence JSONB NOT NULL,                 -- skor per field: course_name, title, deadline, parallel_code
+    score REAL NOT NULL,                       -- skor terendah dari confidence
+    status VARCHAR(20) NOT NULL DEFAULT 'pending', -- pending | approved | rejected
+    reviewed_by TEXT,
+    reviewed_at TIMESTAMP WITH TIME ZONE,
+    review_note TEXT,
+    assignment_id UUID REFERENCES public.assignments(id) ON DELETE SET NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_assignment_reviews_pending 
+ON public.assignment_reviews (created_at) WHERE status = 'pending';
+
+alter table public.assignment_reviews enable row level security;
+
+create policy "Enable access to all users" on public.assignment_reviews for all using (true) with check (true);
@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS public.idx_llm_usage_prompt_version;
+ALTER TABLE public.llm_usage
+    DROP COLUMN IF EXISTS prompt_version;
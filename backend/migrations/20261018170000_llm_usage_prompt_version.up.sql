@@ -0,0 +1,8 @@
+-- VERSI PROMPT DI LLM USAGE (template prompts/*.txt yang dipakai tiap panggilan)
+-- Supaya regresi ekstraksi bisa dilacak ke perubahan wording prompt
+ALTER TABLE public.llm_usage
+    ADD COLUMN IF NOT EXISTS prompt_version VARCHAR(100);  -- mis. classification@2026-10-18.1+9f1c2ab0, NULL = tanpa template
+
+CREATE INDEX IF NOT EXISTS idx_llm_usage_prompt_version
+    ON public.llm_usage (prompt_version, created_at)
+    WHERE prompt_version IS NOT NULL;
@@ -0,0 +1,12 @@
+-- TABEL 13: ASSIGNMENT VECTORS (Vektor term TF-IDF per tugas, untuk deteksi duplikat lokal)
+-- Bobot term saja (judul, deskripsi, nomor "#15", tipe "type:lab"); IDF dihitung saat dibandingkan
+CREATE TABLE IF NOT EXISTS public.assignment_vectors (
+    assignment_id UUID PRIMARY KEY REFERENCES public.assignments(id) ON DELETE CASCADE,
+    terms JSONB NOT NULL,                      -- {"lkp": 2.0, "#15": 3.0, "type:lab": 1.0, ...}
+    model VARCHAR(50) NOT NULL DEFAULT 'tfidf-v1', -- beda model = dihitung ulang
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+alter table public.assignment_vectors enable row level security;
+
+create policy "Enable access to all users" on public.assignment_vectors for all using (true) with check (true);
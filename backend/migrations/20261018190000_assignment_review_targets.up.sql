@@ -0,0 +1,4 @@
+-- TARGET REVIEW (review yang meng-update tugas lama, bukan membuat tugas baru)
+-- Duplikat / update yang kurang yakin ikut antri; #approve baru menulis ke tugas target
+ALTER TABLE public.assignment_reviews
+    ADD COLUMN IF NOT EXISTS target_id UUID REFERENCES public.assignments(id) ON DELETE CASCADE;  -- NULL = tugas baru
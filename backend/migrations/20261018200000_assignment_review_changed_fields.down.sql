@@ -0,0 +1,2 @@
+ALTER TABLE public.assignment_reviews
+    DROP COLUMN IF EXISTS changed_fields;
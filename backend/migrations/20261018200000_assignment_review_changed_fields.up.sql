@@ -0,0 +1,4 @@
+-- FIELD YANG DIUBAH REVIEW UPDATE
+-- #approve hanya menulis field ini ke tugas target, perubahan lain selama review menunggu tetap aman
+ALTER TABLE public.assignment_reviews
+    ADD COLUMN IF NOT EXISTS changed_fields TEXT[] NOT NULL DEFAULT '{}';  -- title, description, deadline, parallel_code; kosong = tugas baru
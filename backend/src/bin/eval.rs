@@ -0,0 +1,556 @@
+// backend/src/bin/eval.rs
+//
+// Golden-set evaluation of the extraction pipeline. Every case of the corpus
+// runs through `extract_with_ai` (which calls `build_context`) with a pinned
+// "now", then through the duplicate / update matching against the corpus'
+// existing assignments. Prints per-field precision/recall and a diff report.
+//
+// LLM traffic goes through a cassette: replay (default) needs no network or
+// API keys; `--record` calls the real APIs once and saves the responses.
+// The database is never used — sender history and quoted lookups are empty.
+//
+//   cargo run --bin eval -- [--corpus eval/golden.json] [--cassette eval/cassette.json]
+//                           [--record] [--report eval/report.md]
+
+use chrono::{DateTime, FixedOffset, NaiveDateTime, Utc};
+use serde::Deserialize;
+use sqlx::postgres::{PgPool, PgPoolOptions};
+use std::collections::HashMap;
+use std::time::Duration;
+use uuid::Uuid;
+
+use whatsapp_backend::database::crud::{format_courses_for_prompt, parse_deadline};
+use whatsapp_backend::models::{AIClassification, Assignment, Course};
+use whatsapp_backend::parser::ai_extractor::{
+    check_duplicate_assignment, extract_with_ai, load_prompt_templates, match_update_to_assignment, set_fixed_now,
+    Cassette,
+};
+
+#[derive(Debug, Deserialize)]
+struct Corpus {
+    courses: Vec<CorpusCourse>,
+    #[serde(default)]
+    assignments: Vec<CorpusAssignment>,
+    cases: Vec<Case>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CorpusCourse {
+    name: String,
+    #[serde(default)]
+    aliases: Vec<String>,
+}
+
+/// An assignment that already exists when the cases run
+#[derive(Debug, Deserialize)]
+struct CorpusAssignment {
+    id: Uuid,
+    course: String,
+    title: String,
+    #[serde(default)]
+    description: String,
+    deadline: Option<String>,
+    parallel_code: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Case {
+    id: String,
+    text: String,
+    /// WIB, e.g. "2026-10-18T10:00:00"
+    now: NaiveDateTime,
+    #[serde(default = "default_sender")]
+    sender: String,
+    quoted: Option<String>,
+    expected: AIClassification,
+    /// Existing assignment this message duplicates or updates (None = new)
+    #[serde(default)]
+    existing_id: Option<Uuid>,
+}
+
+fn default_sender() -> String {
+    "eval@c.us".to_string()
+}
+
+const FIELDS: [&str; 4] = ["course", "deadline", "parallel", "type"];
+
+/// One comparable assignment out of a classification
+#[derive(Debug, Clone, PartialEq)]
+struct Item {
+    course: Option<String>,
+    deadline: Option<String>,
+    parallel: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct Counts {
+    tp: u32,
+    fp: u32,
+    fn_: u32,
+}
+
+/// Score of one field in one case, with the values for the diff report
+struct FieldResult {
+    field: &'static str,
+    counts: Counts,
+    expected: Vec<Option<String>>,
+    got: Vec<Option<String>>,
+}
+
+impl Counts {
+    fn precision(&self) -> f64 {
+        ratio(self.tp, self.tp + self.fp)
+    }
+
+    fn recall(&self) -> f64 {
+        ratio(self.tp, self.tp + self.fn_)
+    }
+}
+
+fn ratio(a: u32, b: u32) -> f64 {
+    if b == 0 {
+        1.0
+    } else {
+        a as f64 / b as f64
+    }
+}
+
+struct Args {
+    corpus: String,
+    cassette: String,
+    record: bool,
+    report: Option<String>,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args {
+        corpus: "eval/golden.json".to_string(),
+        cassette: "eval/cassette.json".to_string(),
+        record: false,
+        report: None,
+    };
+
+    let mut it = std::env::args().skip(1);
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--corpus" => args.corpus = it.next().ok_or("--corpus needs a path")?,
+            "--cassette" => args.cassette = it.next().ok_or("--cassette needs a path")?,
+            "--report" => args.report = Some(it.next().ok_or("--report needs a path")?),
+            "--record" => args.record = true,
+            other => return Err(format!("Unknown argument: {}", other)),
+        }
+    }
+    Ok(args)
+}
+
+#[tokio::main]
+async fn main() {
+    dotenv::dotenv().ok();
+
+    if let Err(e) = run().await {
+        eprintln!("❌ {}", e);
+        std::process::exit(1);
+    }
+}
+
+async fn run() -> Result<(), String> {
+    let args = parse_args()?;
+
+    let content = std::fs::read_to_string(&args.corpus).map_err(|e| format!("Failed to read {}: {}", args.corpus, e))?;
+    let corpus: Corpus = serde_json::from_str(&content).map_err(|e| format!("Invalid corpus {}: {}", args.corpus, e))?;
+
+    let cassette = if args.record {
+        Cassette::record(&args.cassette)
+    } else {
+        Cassette::replay(&args.cassette).map_err(|e| format!("{} (run once with --record)", e))?
+    }
+    .install()?;
+
+    // Unreachable on purpose: context builder lookups fail fast and degrade to "no history"
+    let pool = PgPoolOptions::new()
+        .acquire_timeout(Duration::from_millis(200))
+        .connect_lazy("postgres://eval@127.0.0.1:1/eval")
+        .map_err(|e| e.to_string())?;
+
+    // Stable ids → identical prompts between record and replay
+    let courses: Vec<Course> = corpus
+        .courses
+        .iter()
+        .enumerate()
+        .map(|(i, c)| Course {
+            id: Uuid::from_u128(i as u128 + 1),
+            name: c.name.clone(),
+            aliases: Some(c.aliases.clone()),
+            created_at: DateTime::<Utc>::UNIX_EPOCH,
+        })
+        .collect();
+    let course_map: HashMap<Uuid, String> = courses.iter().map(|c| (c.id, c.name.clone())).collect();
+    let available_courses = format_courses_for_prompt(&courses);
+    let existing = corpus_assignments(&corpus.assignments, &courses)?;
+
+    let mut totals: HashMap<&str, Counts> = FIELDS.iter().map(|f| (*f, Counts::default())).collect();
+    let mut existing_hits = (0u32, 0u32);
+    let mut failures = 0u32;
+    let mut diffs = Vec::new();
+
+    for case in &corpus.cases {
+        println!("\n🧪 Case {}", case.id);
+        set_fixed_now(Some(case.now));
+
+        let predicted = match extract_with_ai(
+            &case.text,
+            &available_courses,
+            &existing,
+            &course_map,
+            None,
+            None,
+            &case.sender,
+            &pool,
+            case.quoted.as_deref(),
+        )
+        .await
+        {
+            Ok(classification) => classification,
+            Err(e) => {
+                failures += 1;
+                diffs.push(format!("- `{}`: extraction failed — {}", case.id, e));
+                AIClassification::Unrecognized
+            }
+        };
+
+        let mut case_diffs = Vec::new();
+        for result in compare(&case.expected, &predicted, &courses) {
+            let total = totals.get_mut(result.field).unwrap();
+            *total = add(*total, result.counts);
+            if result.counts.fp + result.counts.fn_ > 0 {
+                case_diffs.push(format!(
+                    "{}: expected `{}`, got `{}`",
+                    result.field,
+                    show(&result.expected),
+                    show(&result.got)
+                ));
+            }
+        }
+
+        if let Some(found) = find_existing(&predicted, &existing, &course_map, &courses, &pool).await {
+            existing_hits.1 += 1;
+            if found == case.existing_id {
+                existing_hits.0 += 1;
+            } else {
+                case_diffs.push(format!("existing: expected `{:?}`, got `{:?}`", case.existing_id, found));
+            }
+        }
+
+        if !case_diffs.is_empty() {
+            diffs.push(format!("- `{}`: {}", case.id, case_diffs.join("; ")));
+        }
+    }
+    set_fixed_now(None);
+
+    if args.record {
+        cassette.save()?;
+        println!("\n💾 Recorded responses saved to {}", args.cassette);
+    }
+
+    let mut report = format_report(corpus.cases.len(), &totals, existing_hits, failures, &diffs);
+    // A stale cassette silently turns into extraction failures: say which calls had no recording
+    let unmatched = cassette.unmatched();
+    if !unmatched.is_empty() {
+        report.push_str(&format!(
+            "\n## Unrecorded requests\n\nRe-record with --record.\n\n{}\n",
+            unmatched.iter().map(|u| format!("- {}", u)).collect::<Vec<_>>().join("\n")
+        ));
+    }
+    // Which wording produced these numbers
+    report.push_str(&format!("\n## Prompts\n\n{}\n", load_prompt_templates().join("\n")));
+    println!("\n{}", report);
+
+    if let Some(path) = args.report {
+        std::fs::write(&path, &report).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+        println!("📝 Report written to {}", path);
+    }
+    Ok(())
+}
+
+fn corpus_assignments(assignments: &[CorpusAssignment], courses: &[Course]) -> Result<Vec<Assignment>, String> {
+    assignments
+        .iter()
+        .map(|a| {
+            let course = canonical_course(&a.course, courses)
+                .ok_or_else(|| format!("Assignment {} has unknown course '{}'", a.id, a.course))?;
+            Ok(Assignment {
+                id: a.id,
+                created_at: a.created_at,
+                course_id: courses.iter().find(|c| c.name == course).map(|c| c.id),
+                title: a.title.clone(),
+                description: a.description.clone(),
+                deadline: a.deadline.as_deref().map(parse_deadline).transpose()?,
+                parallel_code: a.parallel_code.clone(),
+                sender_id: None,
+                message_ids: Vec::new(),
+            })
+        })
+        .collect()
+}
+
+/// Run the duplicate check (new assignment) or update matching (update) like the webhook does.
+/// `None` = nothing to check for this prediction.
+async fn find_existing(
+    predicted: &AIClassification,
+    existing: &[Assignment],
+    course_map: &HashMap<Uuid, String>,
+    courses: &[Course],
+    pool: &PgPool,
+) -> Option<Option<Uuid>> {
+    match predicted {
+        AIClassification::AssignmentInfo { course_name, title, description, parallel_code, .. } => {
+            let course = canonical_course(course_name.as_deref()?, courses)?;
+            let course_id = courses.iter().find(|c| c.name == course)?.id;
+            let candidates: Vec<Assignment> =
+                existing.iter().filter(|a| a.course_id == Some(course_id)).cloned().collect();
+            if candidates.is_empty() {
+                return Some(None);
+            }
+
+            let result = check_duplicate_assignment(
+                title,
+                description.as_deref().unwrap_or("No description"),
+                &course,
+                parallel_code.as_deref(),
+                &candidates,
+                course_map,
+                pool,
+            )
+            .await;
+            Some(result.ok().flatten())
+        }
+        AIClassification::AssignmentUpdate { reference_keywords, changes, parallel_code, .. } => {
+            let result = match_update_to_assignment(
+                changes,
+                reference_keywords,
+                existing,
+                course_map,
+                parallel_code.as_deref(),
+            )
+            .await;
+            Some(result.ok().flatten())
+        }
+        _ => None,
+    }
+}
+
+fn type_label(classification: &AIClassification) -> Option<String> {
+    match classification {
+        AIClassification::AssignmentInfo { .. } => Some("assignment_info".to_string()),
+        AIClassification::MultipleAssignments { .. } => Some("multiple_assignments".to_string()),
+        AIClassification::AssignmentUpdate { .. } => Some("assignment_update".to_string()),
+        AIClassification::Unrecognized => None,
+    }
+}
+
+fn items(classification: &AIClassification, courses: &[Course]) -> Vec<Item> {
+    let course = |name: Option<&str>| name.map(|n| canonical_course(n, courses).unwrap_or_else(|| n.trim().to_lowercase()));
+
+    match classification {
+        AIClassification::AssignmentInfo { course_name, deadline, parallel_code, .. } => vec![Item {
+            course: course(course_name.as_deref()),
+            deadline: normalize_deadline(deadline.as_deref()),
+            parallel: normalize_parallel(parallel_code.as_deref()),
+        }],
+        AIClassification::MultipleAssignments { assignments, .. } => assignments
+            .iter()
+            .map(|a| Item {
+                course: course(Some(&a.course_name)),
+                deadline: normalize_deadline(a.deadline.as_deref()),
+                parallel: normalize_parallel(a.parallel_code.as_deref()),
+            })
+            .collect(),
+        AIClassification::AssignmentUpdate { new_deadline, parallel_code, .. } => vec![Item {
+            course: None,
+            deadline: normalize_deadline(new_deadline.as_deref()),
+            parallel: normalize_parallel(parallel_code.as_deref()),
+        }],
+        AIClassification::Unrecognized => Vec::new(),
+    }
+}
+
+/// Per-field counts of one case. Assignments are aligned by position.
+fn compare(
+    expected: &AIClassification,
+    predicted: &AIClassification,
+    courses: &[Course],
+) -> Vec<FieldResult> {
+    let expected_items = items(expected, courses);
+    let predicted_items = items(predicted, courses);
+    let len = expected_items.len().max(predicted_items.len());
+
+    let column = |list: &[Item], pick: fn(&Item) -> Option<String>| -> Vec<Option<String>> {
+        (0..len).map(|i| list.get(i).and_then(pick)).collect()
+    };
+
+    let mut result = Vec::new();
+    for (field, pick) in [
+        ("course", (|i: &Item| i.course.clone()) as fn(&Item) -> Option<String>),
+        ("deadline", |i: &Item| i.deadline.clone()),
+        ("parallel", |i: &Item| i.parallel.clone()),
+    ] {
+        let exp = column(&expected_items, pick);
+        let got = column(&predicted_items, pick);
+        let counts = exp.iter().zip(&got).fold(Counts::default(), |acc, (e, g)| add(acc, score(e, g)));
+        result.push(FieldResult { field, counts, expected: exp, got });
+    }
+
+    let (exp, got) = (type_label(expected), type_label(predicted));
+    result.push(FieldResult { field: "type", counts: score(&exp, &got), expected: vec![exp], got: vec![got] });
+    result
+}
+
+fn add(a: Counts, b: Counts) -> Counts {
+    Counts { tp: a.tp + b.tp, fp: a.fp + b.fp, fn_: a.fn_ + b.fn_ }
+}
+
+/// A wrong value is both a false positive and a false negative
+fn score(expected: &Option<String>, predicted: &Option<String>) -> Counts {
+    match (expected, predicted) {
+        (Some(e), Some(p)) if e == p => Counts { tp: 1, ..Counts::default() },
+        (Some(_), Some(_)) => Counts { tp: 0, fp: 1, fn_: 1 },
+        (None, Some(_)) => Counts { fp: 1, ..Counts::default() },
+        (Some(_), None) => Counts { fn_: 1, ..Counts::default() },
+        (None, None) => Counts::default(),
+    }
+}
+
+/// Course name or alias → canonical course name
+fn canonical_course(name: &str, courses: &[Course]) -> Option<String> {
+    let lower = name.trim().to_lowercase();
+    courses
+        .iter()
+        .find(|c| {
+            c.name.to_lowercase() == lower
+                || c.aliases.iter().flatten().any(|a| a.to_lowercase() == lower)
+        })
+        .map(|c| c.name.clone())
+}
+
+/// "2026-10-25" and "2026-10-25 23:59" are the same deadline
+fn normalize_deadline(deadline: Option<&str>) -> Option<String> {
+    let raw = deadline?.trim();
+    match parse_deadline(raw) {
+        Ok(dt) => Some(dt.with_timezone(&wib()).format("%Y-%m-%d %H:%M").to_string()),
+        Err(_) => Some(raw.to_string()),
+    }
+}
+
+fn wib() -> FixedOffset {
+    FixedOffset::east_opt(7 * 3600).unwrap()
+}
+
+fn normalize_parallel(code: Option<&str>) -> Option<String> {
+    code.map(|c| c.trim().to_lowercase()).filter(|c| !c.is_empty())
+}
+
+fn show(values: &[Option<String>]) -> String {
+    values
+        .iter()
+        .map(|v| v.as_deref().unwrap_or("∅"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn format_report(
+    cases: usize,
+    totals: &HashMap<&str, Counts>,
+    existing_hits: (u32, u32),
+    failures: u32,
+    diffs: &[String],
+) -> String {
+    let mut report = format!("# Extraction eval — {} cases\n\n", cases);
+    report.push_str("| field | precision | recall | tp | fp | fn |\n|---|---|---|---|---|---|\n");
+    for field in FIELDS {
+        let c = totals[field];
+        report.push_str(&format!(
+            "| {} | {:.2} | {:.2} | {} | {} | {} |\n",
+            field,
+            c.precision(),
+            c.recall(),
+            c.tp,
+            c.fp,
+            c.fn_
+        ));
+    }
+
+    report.push_str(&format!(
+        "\nDuplicate / update matching: {}/{} correct\nExtraction failures: {}\n",
+        existing_hits.0, existing_hits.1, failures
+    ));
+
+    report.push_str("\n## Diffs\n\n");
+    if diffs.is_empty() {
+        report.push_str("_none_\n");
+    } else {
+        for diff in diffs {
+            report.push_str(diff);
+            report.push('\n');
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use whatsapp_backend::models::FieldConfidence;
+
+    fn courses() -> Vec<Course> {
+        vec![Course {
+            id: Uuid::from_u128(1),
+            name: "Pemrograman".to_string(),
+            aliases: Some(vec!["pemrog".to_string()]),
+            created_at: DateTime::<Utc>::UNIX_EPOCH,
+        }]
+    }
+
+    fn info(course: &str, deadline: Option<&str>, parallel: Option<&str>) -> AIClassification {
+        AIClassification::AssignmentInfo {
+            course_name: Some(course.to_string()),
+            title: "LKP 5".to_string(),
+            deadline: deadline.map(str::to_string),
+            description: None,
+            parallel_code: parallel.map(str::to_string),
+            confidence: FieldConfidence::default(),
+            original_message: None,
+            low_confidence: false,
+        }
+    }
+
+    fn counts_of(expected: &AIClassification, predicted: &AIClassification) -> HashMap<&'static str, Counts> {
+        compare(expected, predicted, &courses()).into_iter().map(|r| (r.field, r.counts)).collect()
+    }
+
+    #[test]
+    fn test_compare_normalizes_values() {
+        let counts = counts_of(
+            &info("Pemrograman", Some("2026-10-25"), Some("K1")),
+            &info("pemrog", Some("2026-10-25 23:59"), Some("k1")),
+        );
+
+        for field in FIELDS {
+            assert_eq!(counts[field], Counts { tp: 1, fp: 0, fn_: 0 }, "{}", field);
+        }
+    }
+
+    #[test]
+    fn test_compare_wrong_and_missing_fields() {
+        let counts = counts_of(&info("Pemrograman", Some("2026-10-25"), None), &info("Basis Data", None, Some("k2")));
+
+        assert_eq!(counts["course"], Counts { tp: 0, fp: 1, fn_: 1 });
+        assert_eq!(counts["deadline"], Counts { tp: 0, fp: 0, fn_: 1 });
+        assert_eq!(counts["parallel"], Counts { tp: 0, fp: 1, fn_: 0 });
+
+        let missed = counts_of(&info("Pemrograman", None, None), &AIClassification::Unrecognized);
+        assert_eq!(missed["type"], Counts { tp: 0, fp: 0, fn_: 1 });
+        assert_eq!(missed["course"].recall(), 0.0);
+        assert_eq!(missed["course"].precision(), 1.0);
+    }
+}
@@ -0,0 +1,147 @@
+// backend/src/calendar.rs
+//
+// iCalendar (RFC 5545) export of a user's assignments, used by `#export ics`
+// and the `/calendar/<token>.ics` subscription feed.
+
+use crate::models::AssignmentWithCourse;
+use chrono::{DateTime, Utc};
+
+const PRODID: &str = "-//MABOT//Academic Bot//ID";
+
+/// Build a VCALENDAR with one VEVENT per assignment that has a deadline.
+/// Every lead time (minutes before deadline) becomes a VALARM.
+pub fn build_calendar(assignments: &[AssignmentWithCourse], lead_minutes: &[i32], now: DateTime<Utc>) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:{}", PRODID),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        "X-WR-CALNAME:MABOT — Deadline Tugas".to_string(),
+        "X-WR-TIMEZONE:Asia/Jakarta".to_string(),
+        "REFRESH-INTERVAL;VALUE=DURATION:PT1H".to_string(),
+        "X-PUBLISHED-TTL:PT1H".to_string(),
+    ];
+
+    for assignment in assignments {
+        let Some(deadline) = assignment.deadline else {
+            continue;
+        };
+
+        let mut summary = format!("[{}] {}", assignment.course_name, assignment.title);
+        if let Some(parallel) = assignment.parallel_code.as_deref().filter(|p| *p != "all") {
+            summary.push_str(&format!(" ({})", parallel.to_uppercase()));
+        }
+
+        lines.push("BEGIN:VEVENT".to_string());
+        // Stable UID so calendar apps update the event instead of duplicating it
+        lines.push(format!("UID:{}@marbot", assignment.id));
+        lines.push(format!("DTSTAMP:{}", format_utc(now)));
+        lines.push(format!("DTSTART:{}", format_utc(deadline)));
+        lines.push(format!("DTEND:{}", format_utc(deadline)));
+        lines.push(format!("SUMMARY:{}", escape_text(&summary)));
+        if let Some(description) = assignment.description.as_deref().map(str::trim).filter(|d| !d.is_empty()) {
+            lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+        }
+        lines.push("TRANSP:TRANSPARENT".to_string());
+
+        for lead in lead_minutes.iter().filter(|m| **m > 0) {
+            lines.push("BEGIN:VALARM".to_string());
+            lines.push("ACTION:DISPLAY".to_string());
+            lines.push(format!("DESCRIPTION:{}", escape_text(&summary)));
+            lines.push(format!("TRIGGER:-PT{}M", lead));
+            lines.push("END:VALARM".to_string());
+        }
+
+        lines.push("END:VEVENT".to_string());
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+
+    lines
+        .iter()
+        .map(|line| fold_line(line))
+        .collect::<Vec<_>>()
+        .join("\r\n")
+        + "\r\n"
+}
+
+fn format_utc(dt: DateTime<Utc>) -> String {
+    dt.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Escape TEXT values (RFC 5545 §3.3.11)
+fn escape_text(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Fold lines longer than 75 octets (RFC 5545 §3.1), never splitting a UTF-8 char
+fn fold_line(line: &str) -> String {
+    let mut out = String::with_capacity(line.len() + 8);
+    let mut octets = 0;
+
+    for ch in line.chars() {
+        let len = ch.len_utf8();
+        if octets + len > 75 {
+            out.push_str("\r\n ");
+            octets = 1;
+        }
+        out.push(ch);
+        octets += len;
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn assignment(deadline: Option<DateTime<Utc>>) -> AssignmentWithCourse {
+        AssignmentWithCourse {
+            id: uuid::Uuid::nil(),
+            course_name: "Struktur Data".to_string(),
+            parallel_code: Some("k1".to_string()),
+            title: "LKP 3".to_string(),
+            description: Some("Kumpul di LMS, format: PDF; maks 2 hal".to_string()),
+            deadline,
+            message_ids: vec![],
+            sender_id: None,
+            is_completed: false,
+        }
+    }
+
+    #[test]
+    fn test_build_calendar() {
+        let now = Utc.with_ymd_and_hms(2026, 10, 18, 0, 0, 0).unwrap();
+        let deadline = Utc.with_ymd_and_hms(2026, 10, 20, 16, 59, 0).unwrap();
+        let ics = build_calendar(&[assignment(Some(deadline)), assignment(None)], &[1440, 180], now);
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 1);
+        assert!(ics.contains("UID:00000000-0000-0000-0000-000000000000@marbot"));
+        assert!(ics.contains("DTSTART:20261020T165900Z"));
+        assert!(ics.contains("SUMMARY:[Struktur Data] LKP 3 (K1)"));
+        assert!(ics.contains("DESCRIPTION:Kumpul di LMS\\, format: PDF\\; maks 2 hal"));
+        assert!(ics.contains("TRIGGER:-PT1440M"));
+        assert!(ics.contains("TRIGGER:-PT180M"));
+    }
+
+    #[test]
+    fn test_fold_line() {
+        let long = format!("DESCRIPTION:{}", "é".repeat(60));
+        let folded = fold_line(&long);
+
+        for part in folded.split("\r\n") {
+            assert!(part.len() <= 75);
+        }
+        assert_eq!(folded.replace("\r\n ", ""), long);
+        assert_eq!(fold_line("SHORT"), "SHORT");
+    }
+}
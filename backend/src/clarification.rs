@@ -364,7 +364,7 @@ fn parse_date(text: &str, month_map: &HashMap<&str, u32>, current_year: i32) ->
             // Check previous word for day
             if i > 0 {
                 if let Ok(day) = words[i - 1].parse::<u32>() {
-                    if day >= 1 && day <= 31 {
+                    if (1..=31).contains(&day) {
                         return NaiveDate::from_ymd_opt(current_year, month, day)
                             .ok_or_else(|| "Invalid date".to_string());
                     }
@@ -374,7 +374,7 @@ fn parse_date(text: &str, month_map: &HashMap<&str, u32>, current_year: i32) ->
             // Check next word for day
             if i + 1 < words.len() {
                 if let Ok(day) = words[i + 1].parse::<u32>() {
-                    if day >= 1 && day <= 31 {
+                    if (1..=31).contains(&day) {
                         return NaiveDate::from_ymd_opt(current_year, month, day)
                             .ok_or_else(|| "Invalid date".to_string());
                     }
@@ -384,10 +384,7 @@ fn parse_date(text: &str, month_map: &HashMap<&str, u32>, current_year: i32) ->
     }
     
     // Try numeric formats
-    let normalized = text.replace('-', " ")
-                         .replace('/', " ")
-                         .replace('.', " ")
-                         .replace(',', " ");
+    let normalized = text.replace(['-', '/', '.', ','], " ");
     
     let numbers: Vec<u32> = normalized.split_whitespace()
                                       .filter_map(|s| s.parse::<u32>().ok())
@@ -398,7 +395,7 @@ fn parse_date(text: &str, month_map: &HashMap<&str, u32>, current_year: i32) ->
         let day = numbers[0];
         let month = numbers[1];
         
-        if day >= 1 && day <= 31 && month >= 1 && month <= 12 {
+        if (1..=31).contains(&day) && (1..=12).contains(&month) {
             return NaiveDate::from_ymd_opt(current_year, month, day)
                 .ok_or_else(|| "Invalid date".to_string());
         }
@@ -408,11 +405,11 @@ fn parse_date(text: &str, month_map: &HashMap<&str, u32>, current_year: i32) ->
     if numbers.len() == 1 {
         let num = numbers[0];
         
-        if num >= 101 && num <= 3112 {
+        if (101..=3112).contains(&num) {
             let day = num / 100;
             let month = num % 100;
             
-            if day >= 1 && day <= 31 && month >= 1 && month <= 12 {
+            if (1..=31).contains(&day) && (1..=12).contains(&month) {
                 return NaiveDate::from_ymd_opt(current_year, month, day)
                     .ok_or_else(|| "Invalid date".to_string());
             }
@@ -422,7 +419,7 @@ fn parse_date(text: &str, month_map: &HashMap<&str, u32>, current_year: i32) ->
     Err("Could not parse date".to_string())
 }
 
-fn detect_parallel_code(text: &str) -> Option<String> {
+pub fn detect_parallel_code(text: &str) -> Option<String> {
     let lower = text.to_lowercase();
     
     // Check for "all" variations
@@ -445,12 +442,10 @@ fn detect_parallel_code(text: &str) -> Option<String> {
     // Look for patterns like "kelas 1", "parallel 2"
     for (i, word) in words.iter().enumerate() {
         let lower_word = word.to_lowercase();
-        if lower_word == "kelas" || lower_word == "parallel" || lower_word == "paralel" {
-            if i + 1 < words.len() {
-                if let Ok(num) = words[i + 1].parse::<u8>() {
-                    if (1..=4).contains(&num) {
-                        return Some(format!("k{}", num));
-                    }
+        if (lower_word == "kelas" || lower_word == "parallel" || lower_word == "paralel") && i + 1 < words.len() {
+            if let Ok(num) = words[i + 1].parse::<u8>() {
+                if (1..=4).contains(&num) {
+                    return Some(format!("k{}", num));
                 }
             }
         }
@@ -473,7 +468,7 @@ fn normalize_parallel_code(code: &str) -> String {
     code
 }
 
-fn is_valid_parallel_code(code: &str) -> bool {
+pub fn is_valid_parallel_code(code: &str) -> bool {
     if code.to_lowercase() == "all" {
         return true;
     }
@@ -525,6 +520,13 @@ pub fn generate_cancellation_message(assignment_id: Uuid) -> String {
     )
 }
 
+/// Prepended to the clarification when the assignment was read without AI
+pub fn generate_low_confidence_notice() -> String {
+    "🤖 *AI SEDANG TIDAK TERSEDIA*\n\
+    Tugas ini dibaca otomatis tanpa AI, jadi mungkin kurang tepat.\n\
+    Mohon cek & lengkapi datanya ya.".to_string()
+}
+
 /// Generate message when clarification parsing fails
 pub fn generate_parse_failed_message() -> String {
     "⚠️ *FORMAT TIDAK DIKENALI*\n\
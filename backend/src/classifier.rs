@@ -1,4 +1,6 @@
-use crate::models::{MessageType, BotCommand};
+use crate::models::{ArgValue, BotCommand, CommandArgs, MessageType};
+use crate::parser::registry;
+use chrono::NaiveTime;
 
 // Check if message is a bot command
 #[allow(non_snake_case)]
@@ -40,51 +42,113 @@ fn parse_command(text: &str) -> Option<BotCommand> {
     
     let command = parts[0].to_lowercase();
     
-    match command.as_str() {
-        "test" | "tes" | "ping" => Some(BotCommand::Ping),
-        "tugas" => {
-            // Handle both "#tugas" alone and "#tugas 123"
-            if parts.len() > 1 {
-                if let Ok(id) = parts[1].parse() {
-                    return Some(BotCommand::Expand(id));
-                }
-            }
-            Some(BotCommand::Tugas)
-        }
-        "todo" => Some(BotCommand::Todo),  
-        "today" => Some(BotCommand::Today),
-        "week" => Some(BotCommand::Week),
-        "help" => Some(BotCommand::Help),
-        "undo" => Some(BotCommand::Undo),
-        "done" => {
-            if parts.len() > 1 {
-                let id = parts[1].parse().ok()?;
-                Some(BotCommand::Done(id))
-            } else {
-                None
-            }
-        }
-        "delete" | "hapus" => {
-            if parts.len() > 1 {
-                let id = parts[1].parse().ok()?;
-                Some(BotCommand::Delete(id))
-            } else {
-                None
-            }
+    // Handle numeric-only commands like "# 123" or "#123"
+    if command.chars().all(|c| c.is_numeric()) {
+        let id = command.parse().ok()?;
+        return Some(BotCommand::Run {
+            name: "expand",
+            args: CommandArgs(vec![Some(ArgValue::Index(id))]),
+        });
+    }
+    
+    // Names, aliases and argument schemas all come from the registry
+    let command = registry::lookup(&command)?;
+    
+    match registry::parse_args(command.args(), &parts[1..]) {
+        Some(args) => Some(BotCommand::Run { name: command.name(), args }),
+        None => Some(BotCommand::InvalidArgs(command.name())),
+    }
+}
+
+/// Parse "07:00", "7.30" or "19" into a time of day
+pub fn parse_clock_time(text: &str) -> Option<NaiveTime> {
+    let text = text.trim();
+    let (h, m) = text
+        .split_once(':')
+        .or_else(|| text.split_once('.'))
+        .unwrap_or((text, "0"));
+    let hour = h.trim().parse::<u32>().ok()?;
+    let minute = m.trim().parse::<u32>().ok()?;
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+/// Parse durations like "H-1", "1d", "2hari", "3h", "3j", "3jam", "30m" into minutes
+pub fn parse_duration_minutes(text: &str) -> Option<i32> {
+    let lower = text.trim().to_lowercase();
+
+    if let Some(days) = lower.strip_prefix("h-") {
+        return days.parse::<i32>().ok().filter(|d| *d > 0).map(|d| d * 1440);
+    }
+
+    let split_at = lower.find(|c: char| !c.is_ascii_digit())?;
+    let (number, unit) = lower.split_at(split_at);
+    let value = number.parse::<i32>().ok().filter(|v| *v > 0)?;
+
+    let per_unit = match unit {
+        "d" | "hari" => 1440,
+        "h" | "j" | "jam" => 60,
+        "m" | "mnt" | "menit" => 1,
+        "w" | "minggu" => 7 * 1440,
+        _ => return None,
+    };
+
+    value.checked_mul(per_unit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration_minutes() {
+        assert_eq!(parse_duration_minutes("H-1"), Some(1440));
+        assert_eq!(parse_duration_minutes("2d"), Some(2880));
+        assert_eq!(parse_duration_minutes("3h"), Some(180));
+        assert_eq!(parse_duration_minutes("3jam"), Some(180));
+        assert_eq!(parse_duration_minutes("30m"), Some(30));
+        assert_eq!(parse_duration_minutes("1minggu"), Some(10080));
+        assert_eq!(parse_duration_minutes("besok"), None);
+        assert_eq!(parse_duration_minutes("0h"), None);
+    }
+
+    /// Shortcut: parsed command name + args, `None` for unknown / invalid arguments
+    fn parsed(text: &str) -> Option<(&'static str, CommandArgs)> {
+        match parse_command(text)? {
+            BotCommand::Run { name, args } => Some((name, args)),
+            _ => None,
         }
-        "expand" => {
-            if parts.len() > 1 {
-                let id = parts[1].parse().ok()?;
-                Some(BotCommand::Expand(id))
-            } else {
-                None
+    }
+
+    #[test]
+    fn test_parse_snooze_and_hide() {
+        let (name, args) = parsed("#snooze 2 3h").unwrap();
+        assert_eq!((name, args.index(0), args.minutes(1)), ("snooze", Some(2), Some(180)));
+        let (_, args) = parsed("#tunda 2 H-1").unwrap();
+        assert_eq!(args.minutes(1), Some(1440));
+        assert!(parsed("#snooze 2").is_none());
+        assert!(parsed("#snooze 2 60d").is_none());
+        assert!(matches!(parse_command("#snooze 2"), Some(BotCommand::InvalidArgs("snooze"))));
+
+        let (name, args) = parsed("#hide 4").unwrap();
+        assert_eq!((name, args.index(0)), ("hide", Some(4)));
+        assert_eq!(parsed("#hidden").map(|(name, _)| name), Some("hidden"));
+        let (name, args) = parsed("#unhide 1").unwrap();
+        assert_eq!((name, args.index(0)), ("unhide", Some(1)));
+    }
+
+    #[test]
+    fn test_every_registry_word_is_parsed() {
+        for cmd in registry::COMMANDS {
+            for word in std::iter::once(cmd.name()).chain(cmd.aliases().iter().copied()) {
+                let text = format!("#{} 1 1h", word);
+                assert_eq!(parsed(&text).map(|(name, _)| name), Some(cmd.name()), "{}", text);
             }
         }
-        // Handle numeric-only commands like "# 123" or "#123"
-        _ if command.chars().all(|c| c.is_numeric()) => {
-            let id = command.parse().ok()?;
-            Some(BotCommand::Expand(id))
-        }
-        _ => None,
+
+        let (name, args) = parsed("#12").unwrap();
+        assert_eq!((name, args.index(0)), ("expand", Some(12)));
+        let (name, args) = parsed("#stats Basis Data").unwrap();
+        assert_eq!((name, args.text(0)), ("stats", Some("Basis Data")));
+        assert!(parse_command("#tood").is_none());
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,260 @@
+// backend/src/confidence.rs
+//
+// Per-field confidence for an extracted assignment: what the model says it
+// is sure of, checked against what is actually written in the message (and
+// the quoted message). Anything under the review threshold is held in
+// `assignment_reviews` until a course rep runs #approve in the debug group.
+
+use crate::models::{ConfidenceReport, Course, FieldConfidence};
+
+/// Rule-based extraction (no LLM) guesses the title: never merged into an existing assignment
+/// unreviewed (a new one goes through the forced title clarification instead, see main.rs)
+const RULE_BASED_CAP: f32 = 0.4;
+
+const DATE_WORDS: &[&str] = &[
+    "hari", "besok", "lusa", "nanti", "malam", "pagi", "siang", "sore", "jam", "pukul", "pkl",
+    "minggu", "senin", "selasa", "rabu", "kamis", "jumat", "sabtu", "deadline", "dl", "tenggat",
+    "januari", "februari", "maret", "april", "mei", "juni", "juli", "agustus", "september",
+    "oktober", "november", "desember", "jan", "feb", "mar", "apr", "jun", "jul", "agu", "agt",
+    "sep", "okt", "nov", "des",
+];
+
+/// What the extraction settled on, after course lookup and deadline parsing
+pub struct Extracted<'a> {
+    pub course: Option<&'a Course>,
+    pub title: &'a str,
+    pub has_deadline: bool,
+    pub parallel_code: Option<&'a str>,
+}
+
+/// Minimum overall score to publish without review (env REVIEW_CONFIDENCE_THRESHOLD)
+pub fn review_threshold() -> f32 {
+    parse_threshold(std::env::var("REVIEW_CONFIDENCE_THRESHOLD").ok().as_deref())
+}
+
+/// 0-1, anything else (or unset) → 0.6
+fn parse_threshold(raw: Option<&str>) -> f32 {
+    raw.and_then(|v| v.trim().parse().ok())
+        .filter(|t: &f32| (0.0..=1.0).contains(t))
+        .unwrap_or(0.6)
+}
+
+/// Combine the model's own scores with rule-based evidence from `message`.
+/// A field the model didn't score gets the rule score alone.
+pub fn score(message: &str, extracted: &Extracted, reported: FieldConfidence, rule_based: bool) -> ConfidenceReport {
+    let lower = message.to_lowercase();
+    let words = words_of(&lower);
+
+    let combine = |model: Option<f32>, rule: f32| match model {
+        Some(model) => (model.clamp(0.0, 1.0) + rule) / 2.0,
+        None => rule,
+    };
+
+    let mut report = ConfidenceReport {
+        course_name: combine(reported.course_name, course_signal(&words, extracted.course)),
+        title: combine(reported.title, title_signal(&words, extracted.title)),
+        // Nothing extracted means nothing guessed: missing fields go through clarification
+        deadline: if extracted.has_deadline { combine(reported.deadline, deadline_signal(&words)) } else { 1.0 },
+        parallel_code: match extracted.parallel_code {
+            Some(code) => combine(reported.parallel_code, parallel_signal(&words, code)),
+            None => 1.0,
+        },
+    };
+
+    if rule_based {
+        report.title = report.title.min(RULE_BASED_CAP);
+    }
+    report
+}
+
+/// An update matched to an existing assignment: the matcher already settled the course,
+/// and a field the update leaves alone isn't a guess. Only the changed fields are scored
+/// (rule signals alone, updates carry no model confidence).
+pub fn score_update(message: &str, new_title: Option<&str>, has_deadline: bool, parallel_code: Option<&str>) -> ConfidenceReport {
+    let extracted = Extracted { course: None, title: new_title.unwrap_or_default(), has_deadline, parallel_code };
+    let mut report = score(message, &extracted, FieldConfidence::default(), false);
+
+    report.course_name = 1.0;
+    if new_title.is_none() {
+        report.title = 1.0;
+    }
+    report
+}
+
+impl ConfidenceReport {
+    /// The weakest field decides
+    pub fn overall(&self) -> f32 {
+        self.fields().iter().map(|(_, s)| *s).fold(1.0, f32::min)
+    }
+
+    /// Fields scoring under `threshold`, weakest first
+    pub fn below(&self, threshold: f32) -> Vec<&'static str> {
+        let mut weak: Vec<(&'static str, f32)> = self.fields().into_iter().filter(|(_, s)| *s < threshold).collect();
+        weak.sort_by(|a, b| a.1.total_cmp(&b.1));
+        weak.into_iter().map(|(name, _)| name).collect()
+    }
+
+    fn fields(&self) -> [(&'static str, f32); 4] {
+        [
+            ("course_name", self.course_name),
+            ("title", self.title),
+            ("deadline", self.deadline),
+            ("parallel_code", self.parallel_code),
+        ]
+    }
+}
+
+fn words_of(lower: &str) -> Vec<&str> {
+    lower
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+fn contains_phrase(words: &[&str], phrase: &str) -> bool {
+    let phrase = phrase.to_lowercase();
+    let needle = words_of(&phrase);
+    !needle.is_empty() && words.windows(needle.len()).any(|w| w == needle.as_slice())
+}
+
+/// Course name, either half of "KODE - Nama", or an alias written in the message
+fn course_signal(words: &[&str], course: Option<&Course>) -> f32 {
+    let Some(course) = course else {
+        return 0.0;
+    };
+
+    let mentioned = course
+        .name
+        .split(" - ")
+        .chain(std::iter::once(course.name.as_str()))
+        .chain(course.aliases.iter().flatten().map(String::as_str))
+        .any(|name| contains_phrase(words, name));
+
+    // Not written down: inferred from the schedule or the quoted message
+    if mentioned { 1.0 } else { 0.5 }
+}
+
+/// Share of the title's words that appear in the message
+fn title_signal(words: &[&str], title: &str) -> f32 {
+    let lower = title.to_lowercase();
+    let title_words = words_of(&lower);
+    if title_words.is_empty() {
+        return 0.0;
+    }
+
+    let found = title_words.iter().filter(|w| words.contains(w)).count();
+    0.3 + 0.7 * found as f32 / title_words.len() as f32
+}
+
+/// Any date-ish word or number at all; a deadline out of nowhere is a guess
+fn deadline_signal(words: &[&str]) -> f32 {
+    let dated = words.iter().any(|w| {
+        w.chars().any(|c| c.is_ascii_digit()) || DATE_WORDS.contains(w)
+    });
+
+    if dated { 1.0 } else { 0.4 }
+}
+
+fn parallel_signal(words: &[&str], code: &str) -> f32 {
+    let code = code.to_lowercase();
+    let mentioned = if code == "all" {
+        words.iter().any(|w| matches!(*w, "all" | "semua" | "seluruh"))
+    } else {
+        words.contains(&code.as_str())
+    };
+
+    if mentioned { 1.0 } else { 0.5 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, Utc};
+    use uuid::Uuid;
+
+    fn pemrog() -> Course {
+        Course {
+            id: Uuid::from_u128(1),
+            name: "KOM120C - Pemrograman".to_string(),
+            aliases: Some(vec!["pemrog".to_string()]),
+            created_at: DateTime::<Utc>::UNIX_EPOCH,
+        }
+    }
+
+    fn extracted<'a>(course: Option<&'a Course>, title: &'a str, parallel_code: Option<&'a str>) -> Extracted<'a> {
+        Extracted { course, title, has_deadline: true, parallel_code }
+    }
+
+    #[test]
+    fn test_explicit_message_scores_high() {
+        let course = pemrog();
+        let report = score(
+            "LKP 6 pemrog K1 stack & queue, deadline Rabu 23:59",
+            &extracted(Some(&course), "LKP 6", Some("k1")),
+            FieldConfidence::default(),
+            false,
+        );
+
+        assert_eq!(report.overall(), 1.0);
+        assert!(report.below(0.6).is_empty());
+    }
+
+    #[test]
+    fn test_guessed_fields_are_flagged() {
+        let course = pemrog();
+        let reported = FieldConfidence { course_name: Some(0.3), title: Some(0.9), deadline: None, parallel_code: None };
+        let report = score(
+            "jangan lupa kumpulin laporan ya",
+            &extracted(Some(&course), "Laporan Praktikum 3", Some("k2")),
+            reported,
+            false,
+        );
+
+        // course: (0.3 + 0.5) / 2, deadline: no date words, parallel: k2 never written
+        assert!((report.course_name - 0.4).abs() < 1e-6);
+        assert_eq!(report.deadline, 0.4);
+        assert_eq!(report.parallel_code, 0.5);
+        assert_eq!(report.below(0.6), vec!["course_name", "deadline", "parallel_code"]);
+        assert!((report.overall() - 0.4).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_missing_values_and_rule_based_cap() {
+        let course = pemrog();
+        let none = Extracted { course: None, title: "Kuis", has_deadline: false, parallel_code: None };
+        let report = score("kuis besok", &none, FieldConfidence::default(), false);
+        assert_eq!(report.course_name, 0.0);
+        assert_eq!((report.deadline, report.parallel_code), (1.0, 1.0));
+
+        let report = score("LKP 6 pemrog semua kelas", &extracted(Some(&course), "LKP 6", Some("all")), FieldConfidence::default(), true);
+        assert_eq!(report.title, RULE_BASED_CAP);
+        assert_eq!(report.parallel_code, 1.0);
+    }
+
+    #[test]
+    fn test_update_scores_only_changed_fields() {
+        // Course not repeated, target picked by the matcher: nothing to doubt
+        let report = score_update("deadline LKP 5 diundur jadi Kamis", None, true, None);
+        assert_eq!(report.overall(), 1.0);
+
+        // A new parallel code that is never written down is still a guess
+        let report = score_update("deadline LKP 5 diundur jadi Kamis", None, true, Some("k3"));
+        assert_eq!(report.below(0.6), vec!["parallel_code"]);
+
+        let report = score_update("judulnya ganti ya", Some("Laporan Akhir"), false, None);
+        assert_eq!(report.below(0.6), vec!["title"]);
+    }
+
+    #[test]
+    fn test_parse_threshold() {
+        assert_eq!(parse_threshold(None), 0.6);
+        assert_eq!(parse_threshold(Some(" 0.75 ")), 0.75);
+        assert_eq!(parse_threshold(Some("0")), 0.0);
+        assert_eq!(parse_threshold(Some("1")), 1.0);
+        // Out of range or garbage → default
+        assert_eq!(parse_threshold(Some("1.5")), 0.6);
+        assert_eq!(parse_threshold(Some("-0.1")), 0.6);
+        assert_eq!(parse_threshold(Some("NaN")), 0.6);
+        assert_eq!(parse_threshold(Some("tinggi")), 0.6);
+    }
+}
@@ -0,0 +1,171 @@
+// backend/src/conversation.rs
+//
+// Per-(chat, user) conversation state: a command can ask a follow-up question
+// ("hapus tugas ini? ya/tidak", "pilih nomor 1-3") and the next plain reply
+// from the same user in the same chat is routed back to it — no quoting needed.
+
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// How long the bot waits for a reply before forgetting the question
+pub const REPLY_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+pub static CONVERSATIONS: Lazy<ConversationStore> = Lazy::new(|| ConversationStore::new(REPLY_TIMEOUT));
+
+/// What the bot is waiting for
+#[derive(Debug, Clone, PartialEq)]
+pub enum PendingAction {
+    /// `#delete` menunggu "ya" / "tidak"
+    ConfirmDelete { assignment_id: Uuid, title: String, course_name: String },
+    /// Several matches; the user answers with a number
+    Choose { purpose: ChoicePurpose, options: Vec<ChoiceOption> },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChoicePurpose {
+    Restore,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChoiceOption {
+    pub id: Uuid,
+    pub label: String,
+}
+
+/// A reply to a yes/no question
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Answer {
+    Yes,
+    No,
+}
+
+pub struct ConversationStore {
+    ttl: Duration,
+    pending: Mutex<HashMap<(String, String), (PendingAction, Instant)>>,
+}
+
+impl ConversationStore {
+    pub fn new(ttl: Duration) -> Self {
+        Self { ttl, pending: Mutex::new(HashMap::new()) }
+    }
+
+    /// Wait for the next reply of `user_id` in `chat_id` (replaces any earlier question)
+    pub fn expect(&self, chat_id: &str, user_id: &str, action: PendingAction) {
+        let mut pending = self.pending.lock().unwrap();
+        let now = Instant::now();
+        pending.retain(|_, (_, expires_at)| *expires_at > now);
+        pending.insert((chat_id.to_string(), user_id.to_string()), (action, now + self.ttl));
+    }
+
+    /// Remove and return the pending question, if it hasn't expired
+    pub fn take(&self, chat_id: &str, user_id: &str) -> Option<PendingAction> {
+        let mut pending = self.pending.lock().unwrap();
+        let (action, expires_at) = pending.remove(&(chat_id.to_string(), user_id.to_string()))?;
+        (expires_at > Instant::now()).then_some(action)
+    }
+
+    pub fn clear(&self, chat_id: &str, user_id: &str) {
+        self.pending.lock().unwrap().remove(&(chat_id.to_string(), user_id.to_string()));
+    }
+}
+
+const YES: &[&str] = &["ya", "iya", "y", "yes", "yup", "ok", "oke", "okay", "lanjut", "hapus", "betul"];
+const NO: &[&str] = &["tidak", "tdk", "ga", "gak", "nggak", "enggak", "no", "n", "batal", "cancel", "jangan"];
+/// What may follow the answer word ("iya dong", "gak jadi", "jangan hapus")
+const FILLERS: &[&str] = &["dong", "deh", "aja", "saja", "jadi", "ya", "kak", "sih", "hapus", "lah"];
+
+/// "ya", "iya dong", "ok" → Yes; "tidak", "gak jadi", "batal" → No.
+/// Only a short whole-message answer counts: "Ok guys, LKP 7 …" is an announcement, not a yes
+pub fn parse_answer(text: &str) -> Option<Answer> {
+    let lower = text.trim().to_lowercase();
+    let words: Vec<&str> = lower
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect();
+
+    let (first, rest) = words.split_first()?;
+    if rest.len() > 1 || rest.iter().any(|w| !FILLERS.contains(w)) {
+        return None;
+    }
+
+    if YES.contains(first) {
+        Some(Answer::Yes)
+    } else if NO.contains(first) {
+        Some(Answer::No)
+    } else {
+        None
+    }
+}
+
+/// "2" / "no 2" / "nomor 2" → index 2 (1-based, within `count`)
+pub fn parse_choice(text: &str, count: usize) -> Option<usize> {
+    let lower = text.trim().to_lowercase();
+    let number = lower
+        .trim_start_matches("nomor")
+        .trim_start_matches("no")
+        .trim_start_matches('.')
+        .trim()
+        .trim_start_matches('#');
+
+    number.parse().ok().filter(|n| (1..=count).contains(n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn confirm() -> PendingAction {
+        PendingAction::ConfirmDelete {
+            assignment_id: Uuid::nil(),
+            title: "LKP 5".to_string(),
+            course_name: "Pemrograman".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_store_take_once_per_chat_and_user() {
+        let store = ConversationStore::new(Duration::from_secs(60));
+        store.expect("group@g.us", "628111@c.us", confirm());
+
+        assert_eq!(store.take("group@g.us", "628222@c.us"), None);
+        assert_eq!(store.take("other@g.us", "628111@c.us"), None);
+        assert_eq!(store.take("group@g.us", "628111@c.us"), Some(confirm()));
+        assert_eq!(store.take("group@g.us", "628111@c.us"), None);
+
+        store.expect("group@g.us", "628111@c.us", confirm());
+        store.clear("group@g.us", "628111@c.us");
+        assert_eq!(store.take("group@g.us", "628111@c.us"), None);
+    }
+
+    #[test]
+    fn test_store_expiry() {
+        let store = ConversationStore::new(Duration::ZERO);
+        store.expect("chat", "user", confirm());
+        assert_eq!(store.take("chat", "user"), None);
+    }
+
+    #[test]
+    fn test_parse_answer_and_choice() {
+        assert_eq!(parse_answer("Iya dong"), Some(Answer::Yes));
+        assert_eq!(parse_answer("ok!"), Some(Answer::Yes));
+        assert_eq!(parse_answer("gak jadi"), Some(Answer::No));
+        assert_eq!(parse_answer("jangan hapus"), Some(Answer::No));
+        assert_eq!(parse_answer("besok ada kuis"), None);
+        assert_eq!(parse_answer(""), None);
+
+        // Announcements that merely start with "ok" / "tidak" are not answers
+        assert_eq!(parse_answer("Ok guys, LKP 7 dikumpulkan Jumat"), None);
+        assert_eq!(parse_answer("Ya teman-teman, kuis diundur"), None);
+        assert_eq!(parse_answer("Tidak ada kelas besok, tugas tetap dikumpulkan"), None);
+        assert_eq!(parse_answer("ya ya ya"), None);
+
+        assert_eq!(parse_choice("2", 3), Some(2));
+        assert_eq!(parse_choice("no. 3", 3), Some(3));
+        assert_eq!(parse_choice("nomor 1", 3), Some(1));
+        assert_eq!(parse_choice("4", 3), None);
+        assert_eq!(parse_choice("0", 3), None);
+    }
+}
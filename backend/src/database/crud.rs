@@ -3,7 +3,9 @@ use uuid::Uuid;
 use chrono::{DateTime, Utc, FixedOffset, TimeZone, NaiveDateTime};
 use std::collections::HashMap;
 
-use crate::models::{Assignment, NewAssignment, Course, AssignmentDisplay, AssignmentWithCourse};
+use crate::stats::{AssignmentCompletionCount, CompletionRecord};
+use crate::models::{Assignment, NewAssignment, Course, AssignmentDisplay, AssignmentWithCourse, ReminderPreference, HiddenAssignment, TrashedAssignment, AssignmentNote, NewLlmUsage, LlmUsageTotals, LlmUsageSummary, AssignmentReview, NewAssignmentReview};
+use crate::similarity::{self, Corpus, TermVector};
 
 // ========================================
 // CREATE OPERATIONS
@@ -49,13 +51,14 @@ pub async fn create_assignment(
     let clean_parallel = new_assignment.parallel_code.as_ref().map(|p| p.to_lowercase());
 
     // B. Insert Tugas
-    sqlx::query!(
+    let assignment_id = sqlx::query_scalar!(
         r#"
         INSERT INTO assignments (
             course_id, parallel_code, title, description, 
             deadline, sender_id, message_ids
         )
         VALUES ($1, $2, $3, $4, $5, $6, ARRAY[$7])
+        RETURNING id
         "#,
         new_assignment.course_id,
         clean_parallel,
@@ -65,10 +68,17 @@ pub async fn create_assignment(
         new_assignment.sender_id,
         new_assignment.message_id
     )
-    .execute(&mut *tx)  // ✅ Use transaction
+    .fetch_one(&mut *tx)  // ✅ Use transaction
     .await?;
 
     tx.commit().await?;
+
+    // C. Vektor untuk deteksi duplikat (gagal = dihitung ulang saat dibutuhkan)
+    let terms = similarity::term_vector(&new_assignment.title, &new_assignment.description);
+    if let Err(e) = upsert_assignment_vector(pool, assignment_id, &terms).await {
+        eprintln!("⚠️  Failed to store assignment vector: {}", e);
+    }
+
     Ok(format!("Sukses! Tugas '{}' berhasil disimpan ke matkul '{}'\n", new_assignment.title, real_course_name))
 }
 
@@ -117,6 +127,102 @@ pub async fn unmark_assignment_complete(
     Ok(result.rows_affected() > 0)
 }
 
+// ========================================
+// SNOOZE / HIDE OPERATIONS
+// ========================================
+
+/// Sembunyikan tugas untuk user ini (tidak dihitung sebagai selesai)
+pub async fn hide_assignment_for_user(
+    pool: &PgPool,
+    assignment_id: Uuid,
+    user_id: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO assignment_user_states (user_id, assignment_id, hidden)
+        VALUES ($1, $2, TRUE)
+        ON CONFLICT (user_id, assignment_id) DO UPDATE
+        SET hidden = TRUE, updated_at = NOW()
+        "#
+    )
+    .bind(user_id)
+    .bind(assignment_id)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Sembunyikan tugas sementara sampai `until`
+pub async fn snooze_assignment_for_user(
+    pool: &PgPool,
+    assignment_id: Uuid,
+    user_id: &str,
+    until: DateTime<Utc>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO assignment_user_states (user_id, assignment_id, snoozed_until)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (user_id, assignment_id) DO UPDATE
+        SET snoozed_until = EXCLUDED.snoozed_until, updated_at = NOW()
+        "#
+    )
+    .bind(user_id)
+    .bind(assignment_id)
+    .bind(until)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Tampilkan lagi tugas yang di-hide / di-snooze
+pub async fn restore_assignment_for_user(
+    pool: &PgPool,
+    assignment_id: Uuid,
+    user_id: &str,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "DELETE FROM assignment_user_states WHERE assignment_id = $1 AND user_id = $2"
+    )
+    .bind(assignment_id)
+    .bind(user_id)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Active assignments the user has hidden or is currently snoozing (order is stable for `#unhide <n>`)
+pub async fn get_hidden_assignments_for_user(
+    pool: &PgPool,
+    user_id: &str,
+) -> Result<Vec<HiddenAssignment>, sqlx::Error> {
+    sqlx::query_as::<_, HiddenAssignment>(
+        r#"
+        SELECT 
+            a.id,
+            c.name as course_name,
+            a.title,
+            a.deadline,
+            s.hidden,
+            s.snoozed_until
+        FROM assignment_user_states s
+        JOIN assignments a ON s.assignment_id = a.id
+        JOIN courses c ON a.course_id = c.id
+        WHERE s.user_id = $1
+        AND a.deleted_at IS NULL
+        AND (s.hidden OR s.snoozed_until > NOW())
+        AND (a.deadline IS NULL OR a.deadline >= NOW())
+        ORDER BY a.deadline ASC NULLS LAST, a.title ASC
+        "#
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+}
+
 // ========================================
 // READ OPERATIONS
 // ========================================
@@ -143,6 +249,7 @@ pub async fn get_last_completed_assignment(
         JOIN courses c ON a.course_id = c.id
         JOIN user_completions uc ON uc.assignment_id = a.id
         WHERE uc.user_id = $1
+        AND a.deleted_at IS NULL
         ORDER BY uc.completed_at DESC
         LIMIT 1
         "#,
@@ -168,6 +275,7 @@ pub async fn get_assignments(pool: &PgPool) -> Result<Vec<AssignmentDisplay>, sq
             a.deadline
         FROM assignments a
         JOIN courses c ON a.course_id = c.id
+        WHERE a.deleted_at IS NULL
         ORDER BY a.deadline ASC
         "#
     )
@@ -201,6 +309,7 @@ pub async fn get_assignment_by_title_and_course(
         r#"
         SELECT * FROM assignments
         WHERE title = $1 AND course_id = $2
+        AND deleted_at IS NULL
         "#
     )
     .bind(title)
@@ -219,7 +328,8 @@ pub async fn get_active_assignments(pool: &PgPool) -> Result<Vec<Assignment>> {
     let assignments = sqlx::query_as::<_, Assignment>(
         r#"
         SELECT a.* FROM assignments a
-        WHERE a.deadline > $1 OR a.deadline IS NULL
+        WHERE (a.deadline > $1 OR a.deadline IS NULL)
+        AND a.deleted_at IS NULL
         ORDER BY a.created_at DESC
         LIMIT 20
         "#
@@ -251,7 +361,8 @@ pub async fn get_active_assignments_sorted(pool: &PgPool) -> Result<Vec<Assignme
             false as "is_completed!" -- Default false untuk scheduler
         FROM assignments a
         JOIN courses c ON a.course_id = c.id
-        WHERE a.deadline IS NULL OR a.deadline >= $1
+        WHERE (a.deadline IS NULL OR a.deadline >= $1)
+        AND a.deleted_at IS NULL
         ORDER BY 
             CASE WHEN a.deadline IS NULL THEN 0 ELSE 1 END,  -- NULL deadlines first
             a.deadline ASC NULLS FIRST,  -- Then sort by deadline
@@ -297,7 +408,15 @@ pub async fn get_active_assignments_for_user(
             ) as "is_completed!" 
         FROM assignments a
         JOIN courses c ON a.course_id = c.id
-        WHERE a.deadline IS NULL OR a.deadline >= $1
+        WHERE (a.deadline IS NULL OR a.deadline >= $1)
+        AND a.deleted_at IS NULL
+        -- Skip tugas yang di-hide / masih di-snooze oleh user ini
+        AND NOT EXISTS(
+            SELECT 1 FROM assignment_user_states s
+            WHERE s.assignment_id = a.id
+            AND s.user_id = $2
+            AND (s.hidden OR s.snoozed_until > $1)
+        )
         ORDER BY 
             CASE WHEN a.deadline IS NULL THEN 0 ELSE 1 END,  -- NULL deadlines at top
             a.deadline ASC NULLS FIRST,
@@ -321,7 +440,7 @@ pub async fn get_active_assignments_for_user(
             i + 1, a.title, deadline_str, a.is_completed);
     }
 
-    println!("");
+    println!();
     
     Ok(assignments)
 }
@@ -340,6 +459,7 @@ pub async fn get_recent_assignments_for_update(
             r#"
             SELECT * FROM assignments
             WHERE course_id = $1 
+            AND deleted_at IS NULL
             AND deadline >= NOW() - INTERVAL '7 days'  -- Include assignments from last week
             ORDER BY created_at DESC  -- Most recent first
             LIMIT 10
@@ -354,6 +474,7 @@ pub async fn get_recent_assignments_for_update(
             r#"
             SELECT * FROM assignments
             WHERE deadline >= NOW() - INTERVAL '7 days'
+            AND deleted_at IS NULL
             ORDER BY created_at DESC
             LIMIT 10
             "#
@@ -408,14 +529,25 @@ pub async fn get_course_by_name_or_alias(
     Ok(course)
 }
 
-/// Get all courses formatted with their aliases for AI prompt
-pub async fn get_all_courses_formatted(pool: &PgPool) -> Result<String> {
+/// All courses with their aliases
+pub async fn get_all_courses(pool: &PgPool) -> Result<Vec<Course>> {
     let courses = sqlx::query_as::<_, Course>(
         "SELECT * FROM courses ORDER BY name"
     )
     .fetch_all(pool)
     .await?;
-    
+
+    Ok(courses)
+}
+
+/// Get all courses formatted with their aliases for AI prompt
+pub async fn get_all_courses_formatted(pool: &PgPool) -> Result<String> {
+    let courses = get_all_courses(pool).await?;
+    Ok(format_courses_for_prompt(&courses))
+}
+
+/// "- Name (aliases: a, b)" per line, as the extraction prompt expects
+pub fn format_courses_for_prompt(courses: &[Course]) -> String {
     let formatted = courses
         .iter()
         .map(|c| {
@@ -432,7 +564,7 @@ pub async fn get_all_courses_formatted(pool: &PgPool) -> Result<String> {
         .collect::<Vec<_>>()
         .join("\n- ");
     
-    Ok(format!("- {}", formatted))
+    format!("- {}", formatted)
 }
 
 /// Check if assignment already exists by message_id
@@ -456,6 +588,7 @@ pub async fn get_assignment_with_course_by_id(
         FROM assignments a
         JOIN courses c ON a.course_id = c.id
         WHERE a.id = $1
+        AND a.deleted_at IS NULL
         "#,
         assignment_id
     )
@@ -488,7 +621,7 @@ pub async fn find_assignment_by_keywords(
             .collect();
         
         let mut query = String::from(
-            "SELECT * FROM assignments WHERE course_id = $1 AND ("
+            "SELECT * FROM assignments WHERE course_id = $1 AND deleted_at IS NULL AND ("
         );
         
         let mut conditions = Vec::new();
@@ -538,7 +671,7 @@ pub async fn find_assignment_by_keywords(
     }
     
     let query = format!(
-        "SELECT * FROM assignments WHERE {} ORDER BY created_at DESC LIMIT 5",
+        "SELECT * FROM assignments WHERE deleted_at IS NULL AND ({}) ORDER BY created_at DESC LIMIT 5",
         conditions.join(" OR ")  // Changed from AND to OR for broader matching
     );
     
@@ -557,11 +690,275 @@ pub async fn find_assignment_by_keywords(
     Ok(assignments)
 }
 
+// ========================================
+// PAST ASSIGNMENTS (OVERDUE / RIWAYAT)
+// ========================================
+
+/// Tugas yang sudah lewat deadline tapi belum diselesaikan user (untuk #overdue)
+pub async fn get_overdue_assignments_for_user(
+    pool: &PgPool,
+    user_id: &str,
+) -> Result<Vec<AssignmentWithCourse>, sqlx::Error> {
+    sqlx::query_as::<_, AssignmentWithCourse>(
+        r#"
+        SELECT 
+            a.id,
+            c.name as course_name,
+            a.parallel_code,
+            a.title,
+            a.description,
+            a.deadline,
+            a.message_ids,
+            a.sender_id,
+            FALSE as is_completed
+        FROM assignments a
+        JOIN courses c ON a.course_id = c.id
+        WHERE a.deadline < NOW()
+        AND a.deleted_at IS NULL
+        AND NOT EXISTS(
+            SELECT 1 FROM user_completions uc
+            WHERE uc.assignment_id = a.id
+            AND uc.user_id = $1
+        )
+        AND NOT EXISTS(
+            SELECT 1 FROM assignment_user_states s
+            WHERE s.assignment_id = a.id
+            AND s.user_id = $1
+            AND (s.hidden OR s.snoozed_until > NOW())
+        )
+        ORDER BY a.deadline DESC
+        LIMIT 30
+        "#
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Tugas yang deadline-nya sudah lewat, opsional dibatasi rentang waktu / mata kuliah (untuk #riwayat)
+pub async fn get_past_assignments_for_user(
+    pool: &PgPool,
+    user_id: &str,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+    course_id: Option<Uuid>,
+) -> Result<Vec<AssignmentWithCourse>, sqlx::Error> {
+    sqlx::query_as::<_, AssignmentWithCourse>(
+        r#"
+        SELECT 
+            a.id,
+            c.name as course_name,
+            a.parallel_code,
+            a.title,
+            a.description,
+            a.deadline,
+            a.message_ids,
+            a.sender_id,
+            EXISTS(
+                SELECT 1 FROM user_completions uc 
+                WHERE uc.assignment_id = a.id 
+                AND uc.user_id = $1
+            ) as is_completed
+        FROM assignments a
+        JOIN courses c ON a.course_id = c.id
+        WHERE a.deadline < NOW()
+        AND a.deleted_at IS NULL
+        AND ($2::timestamptz IS NULL OR a.deadline >= $2)
+        AND ($3::timestamptz IS NULL OR a.deadline < $3)
+        AND ($4::uuid IS NULL OR a.course_id = $4)
+        AND NOT EXISTS(
+            SELECT 1 FROM assignment_user_states s
+            WHERE s.assignment_id = a.id
+            AND s.user_id = $1
+            AND s.hidden
+        )
+        ORDER BY a.deadline DESC
+        LIMIT 30
+        "#
+    )
+    .bind(user_id)
+    .bind(from)
+    .bind(to)
+    .bind(course_id)
+    .fetch_all(pool)
+    .await
+}
+
+// ========================================
+// STATS
+// ========================================
+
+/// Assignments that count toward a user's stats: past deadline or already completed, minus hidden ones
+pub async fn get_completion_records_for_user(
+    pool: &PgPool,
+    user_id: &str,
+) -> Result<Vec<CompletionRecord>, sqlx::Error> {
+    sqlx::query_as::<_, CompletionRecord>(
+        r#"
+        SELECT 
+            c.name as course_name,
+            a.deadline,
+            uc.completed_at
+        FROM assignments a
+        JOIN courses c ON a.course_id = c.id
+        LEFT JOIN user_completions uc 
+            ON uc.assignment_id = a.id AND uc.user_id = $1
+        WHERE (uc.id IS NOT NULL OR a.deadline < NOW())
+        AND a.deleted_at IS NULL
+        AND NOT EXISTS(
+            SELECT 1 FROM assignment_user_states s
+            WHERE s.assignment_id = a.id
+            AND s.user_id = $1
+            AND s.hidden
+        )
+        ORDER BY a.deadline ASC NULLS LAST
+        "#
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Anonymized completion counts for every assignment of a course
+pub async fn get_course_completion_counts(
+    pool: &PgPool,
+    course_id: Uuid,
+) -> Result<Vec<AssignmentCompletionCount>, sqlx::Error> {
+    sqlx::query_as::<_, AssignmentCompletionCount>(
+        r#"
+        SELECT 
+            a.title,
+            a.parallel_code,
+            a.deadline,
+            COUNT(uc.id) as completed,
+            COUNT(uc.id) FILTER (
+                WHERE a.deadline IS NOT NULL AND uc.completed_at <= a.deadline
+            ) as on_time
+        FROM assignments a
+        LEFT JOIN user_completions uc ON uc.assignment_id = a.id
+        WHERE a.course_id = $1
+        AND a.deleted_at IS NULL
+        GROUP BY a.id
+        ORDER BY a.deadline DESC NULLS FIRST, a.title ASC
+        LIMIT 30
+        "#
+    )
+    .bind(course_id)
+    .fetch_all(pool)
+    .await
+}
+
+// ========================================
+// PRIVATE NOTES (#note / #notes)
+// ========================================
+
+/// Tambah catatan pribadi user untuk sebuah tugas
+pub async fn add_assignment_note(
+    pool: &PgPool,
+    user_id: &str,
+    assignment_id: Uuid,
+    note: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO assignment_notes (user_id, assignment_id, note) VALUES ($1, $2, $3)"
+    )
+    .bind(user_id)
+    .bind(assignment_id)
+    .bind(note)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Hapus semua catatan user untuk sebuah tugas
+pub async fn delete_assignment_notes(
+    pool: &PgPool,
+    user_id: &str,
+    assignment_id: Uuid,
+) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(
+        "DELETE FROM assignment_notes WHERE user_id = $1 AND assignment_id = $2"
+    )
+    .bind(user_id)
+    .bind(assignment_id)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Catatan user, opsional untuk satu tugas saja. Urut per tugas (deadline), lalu waktu dibuat
+pub async fn get_assignment_notes(
+    pool: &PgPool,
+    user_id: &str,
+    assignment_id: Option<Uuid>,
+) -> Result<Vec<AssignmentNote>, sqlx::Error> {
+    sqlx::query_as::<_, AssignmentNote>(
+        r#"
+        SELECT 
+            n.assignment_id,
+            c.name as course_name,
+            a.title,
+            a.deadline,
+            n.note,
+            n.created_at
+        FROM assignment_notes n
+        JOIN assignments a ON n.assignment_id = a.id
+        JOIN courses c ON a.course_id = c.id
+        WHERE n.user_id = $1
+        AND ($2::uuid IS NULL OR n.assignment_id = $2)
+        AND a.deleted_at IS NULL
+        ORDER BY a.deadline ASC NULLS FIRST, a.id, n.created_at ASC
+        "#
+    )
+    .bind(user_id)
+    .bind(assignment_id)
+    .fetch_all(pool)
+    .await
+}
+
+// ========================================
+// USER PARALLELS (#jadwal)
+// ========================================
+
+/// Kelas paralel yang disimpan user (lowercase). Kosong = belum diatur
+pub async fn get_user_parallels(pool: &PgPool, user_id: &str) -> Result<Vec<String>, sqlx::Error> {
+    let parallels: Option<Vec<String>> = sqlx::query_scalar(
+        "SELECT parallels FROM user_parallels WHERE user_id = $1"
+    )
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(parallels.unwrap_or_default())
+}
+
+pub async fn set_user_parallels(pool: &PgPool, user_id: &str, parallels: &[String]) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO user_parallels (user_id, parallels, updated_at)
+        VALUES ($1, $2, NOW())
+        ON CONFLICT (user_id) DO UPDATE SET
+            parallels = EXCLUDED.parallels,
+            updated_at = NOW()
+        "#
+    )
+    .bind(user_id)
+    .bind(parallels)
+    .execute(pool)
+    .await?;
+
+    println!("✅ Parallels for {} set to {:?}", user_id, parallels);
+    Ok(())
+}
+
 // ========================================
 // UPDATE OPERATIONS
 // ========================================
 
-/// Update specific fields of an assignment (simplified version)
+/// Update specific fields of an assignment (simplified version).
+/// A trashed assignment counts as missing: `sqlx::Error::RowNotFound`
 #[allow(non_snake_case)]
 pub async fn update_assignment_fields(
     pool: &PgPool,
@@ -582,7 +979,7 @@ pub async fn update_assignment_fields(
     
     // Fetch current assignment
     let current = sqlx::query_as::<_, Assignment>(
-        "SELECT * FROM assignments WHERE id = $1"
+        "SELECT * FROM assignments WHERE id = $1 AND deleted_at IS NULL FOR UPDATE"
     )
     .bind(id)
     .fetch_one(&mut *tx)
@@ -609,7 +1006,7 @@ pub async fn update_assignment_fields(
                             WHEN $6::text IS NOT NULL THEN array_append(message_ids, $6)
                             ELSE message_ids 
                           END
-        WHERE id = $1
+        WHERE id = $1 AND deleted_at IS NULL
         RETURNING *
         "#
     )
@@ -623,6 +1020,11 @@ pub async fn update_assignment_fields(
     .await?;
     
     tx.commit().await?;
+
+    let terms = similarity::term_vector(&assignment.title, &assignment.description);
+    if let Err(e) = upsert_assignment_vector(pool, assignment.id, &terms).await {
+        eprintln!("⚠️  Failed to store assignment vector: {}", e);
+    }
     
     println!("✅ Successfully updated assignment: {}\n", assignment.title);
     
@@ -630,19 +1032,221 @@ pub async fn update_assignment_fields(
 }
 
 
+// ========================================
+// CALENDAR FEED TOKENS
+// ========================================
+
+/// Token acak untuk URL kalender (2 × UUID v4 ≈ 244 bit)
+fn new_calendar_token() -> String {
+    format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+}
+
+/// Ambil token kalender user, buat baru kalau belum ada
+pub async fn get_or_create_calendar_token(pool: &PgPool, user_id: &str) -> Result<String, sqlx::Error> {
+    sqlx::query_scalar(
+        r#"
+        INSERT INTO calendar_tokens (user_id, token)
+        VALUES ($1, $2)
+        ON CONFLICT (user_id) DO UPDATE SET user_id = EXCLUDED.user_id
+        RETURNING token
+        "#
+    )
+    .bind(user_id)
+    .bind(new_calendar_token())
+    .fetch_one(pool)
+    .await
+}
+
+/// Ganti token (link lama langsung tidak berlaku)
+pub async fn rotate_calendar_token(pool: &PgPool, user_id: &str) -> Result<String, sqlx::Error> {
+    sqlx::query_scalar(
+        r#"
+        INSERT INTO calendar_tokens (user_id, token)
+        VALUES ($1, $2)
+        ON CONFLICT (user_id) DO UPDATE SET token = EXCLUDED.token, created_at = NOW()
+        RETURNING token
+        "#
+    )
+    .bind(user_id)
+    .bind(new_calendar_token())
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn get_user_by_calendar_token(pool: &PgPool, token: &str) -> Result<Option<String>, sqlx::Error> {
+    sqlx::query_scalar("SELECT user_id FROM calendar_tokens WHERE token = $1")
+        .bind(token)
+        .fetch_optional(pool)
+        .await
+}
+
+// ========================================
+// REMINDER PREFERENCES
+// ========================================
+
+/// Ambil preferensi pengingat user (None kalau belum pernah diatur)
+pub async fn get_reminder_preference(
+    pool: &PgPool,
+    user_id: &str,
+) -> Result<Option<ReminderPreference>, sqlx::Error> {
+    sqlx::query_as::<_, ReminderPreference>(
+        "SELECT * FROM reminder_preferences WHERE user_id = $1"
+    )
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Simpan (insert/update) preferensi pengingat user
+pub async fn upsert_reminder_preference(
+    pool: &PgPool,
+    pref: &ReminderPreference,
+) -> Result<ReminderPreference, sqlx::Error> {
+    sqlx::query_as::<_, ReminderPreference>(
+        r#"
+        INSERT INTO reminder_preferences (
+            user_id, enabled, digest_times, lead_minutes, quiet_start, quiet_end, updated_at
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, NOW())
+        ON CONFLICT (user_id) DO UPDATE
+        SET enabled = EXCLUDED.enabled,
+            digest_times = EXCLUDED.digest_times,
+            lead_minutes = EXCLUDED.lead_minutes,
+            quiet_start = EXCLUDED.quiet_start,
+            quiet_end = EXCLUDED.quiet_end,
+            updated_at = NOW()
+        RETURNING *
+        "#
+    )
+    .bind(&pref.user_id)
+    .bind(pref.enabled)
+    .bind(&pref.digest_times)
+    .bind(&pref.lead_minutes)
+    .bind(pref.quiet_start)
+    .bind(pref.quiet_end)
+    .fetch_one(pool)
+    .await
+}
+
+/// Semua user yang mengaktifkan pengingat pribadi
+pub async fn get_enabled_reminder_preferences(
+    pool: &PgPool,
+) -> Result<Vec<ReminderPreference>, sqlx::Error> {
+    sqlx::query_as::<_, ReminderPreference>(
+        "SELECT * FROM reminder_preferences WHERE enabled"
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Incomplete assignments with a deadline for one user (no debug logging, used every minute by the scheduler)
+pub async fn get_pending_assignments_for_reminder(
+    pool: &PgPool,
+    user_id: &str,
+) -> Result<Vec<AssignmentWithCourse>, sqlx::Error> {
+    let now = Utc::now();
+
+    sqlx::query_as!(
+        AssignmentWithCourse,
+        r#"
+        SELECT 
+            a.id,
+            c.name as course_name,
+            a.parallel_code,
+            a.title,
+            a.description,
+            a.deadline,
+            a.message_ids,
+            a.sender_id,
+            false as "is_completed!"
+        FROM assignments a
+        JOIN courses c ON a.course_id = c.id
+        WHERE a.deadline >= $1
+        AND a.deleted_at IS NULL
+        AND NOT EXISTS(
+            SELECT 1 FROM user_completions uc 
+            WHERE uc.assignment_id = a.id 
+            AND uc.user_id = $2
+        )
+        AND NOT EXISTS(
+            SELECT 1 FROM assignment_user_states s
+            WHERE s.assignment_id = a.id
+            AND s.user_id = $2
+            AND (s.hidden OR s.snoozed_until > $1)
+        )
+        ORDER BY a.deadline ASC, c.name ASC
+        "#,
+        now,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Catat pengingat terkirim. Returns false kalau key ini sudah pernah dikirim.
+pub async fn mark_reminder_sent(
+    pool: &PgPool,
+    user_id: &str,
+    reminder_key: &str,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        r#"
+        INSERT INTO reminder_log (user_id, reminder_key)
+        VALUES ($1, $2)
+        ON CONFLICT (user_id, reminder_key) DO NOTHING
+        "#
+    )
+    .bind(user_id)
+    .bind(reminder_key)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Batalkan tanda terkirim (pengiriman gagal) supaya dicoba lagi di menit berikutnya
+pub async fn unmark_reminder_sent(pool: &PgPool, user_id: &str, reminder_key: &str) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM reminder_log WHERE user_id = $1 AND reminder_key = $2")
+        .bind(user_id)
+        .bind(reminder_key)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Hapus log pengingat lama supaya tabel tidak membengkak
+pub async fn purge_reminder_log(pool: &PgPool, older_than_days: i32) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(
+        "DELETE FROM reminder_log WHERE sent_at < NOW() - make_interval(days => $1)"
+    )
+    .bind(older_than_days)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+
 // ========================================
 // DELETE OPERATIONS
 // ========================================
 
-/// Hapus tugas berdasarkan ID
+/// Pindahkan tugas ke trash (soft delete). Completion mahasiswa tetap tersimpan
 pub async fn delete_assignment(
     pool: &PgPool,
     id: Uuid,
+    deleted_by: &str,
 ) -> Result<bool, sqlx::Error> {
-    let result = sqlx::query!(
-        "DELETE FROM assignments WHERE id = $1",
-        id
+    let result = sqlx::query(
+        r#"
+        UPDATE assignments
+        SET deleted_at = NOW(), deleted_by = $2
+        WHERE id = $1 AND deleted_at IS NULL
+        "#
     )
+    .bind(id)
+    .bind(deleted_by)
     .execute(pool)
     .await?;
 
@@ -650,6 +1254,125 @@ pub async fn delete_assignment(
     Ok(result.rows_affected() > 0)
 }
 
+/// Tugas di trash, terbaru dulu (untuk #trash / #restore)
+pub async fn get_deleted_assignments(pool: &PgPool) -> Result<Vec<TrashedAssignment>, sqlx::Error> {
+    sqlx::query_as::<_, TrashedAssignment>(
+        r#"
+        SELECT 
+            a.id,
+            c.name as course_name,
+            a.title,
+            a.deadline,
+            a.deleted_at,
+            a.deleted_by
+        FROM assignments a
+        JOIN courses c ON a.course_id = c.id
+        WHERE a.deleted_at IS NOT NULL
+        ORDER BY a.deleted_at DESC
+        LIMIT 30
+        "#
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Kembalikan tugas dari trash
+pub async fn restore_assignment(pool: &PgPool, id: Uuid) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "UPDATE assignments SET deleted_at = NULL, deleted_by = NULL WHERE id = $1 AND deleted_at IS NOT NULL"
+    )
+    .bind(id)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Hapus permanen tugas yang sudah di trash lebih dari N hari
+pub async fn purge_deleted_assignments(pool: &PgPool, older_than_days: i32) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(
+        "DELETE FROM assignments WHERE deleted_at < NOW() - make_interval(days => $1)"
+    )
+    .bind(older_than_days)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+
+// ========================================
+// LLM USAGE
+// ========================================
+
+/// Catat satu panggilan ke provider LLM
+pub async fn insert_llm_usage(pool: &PgPool, usage: &NewLlmUsage) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO llm_usage (provider, model, task, prompt_version, prompt_tokens, completion_tokens, latency_ms, outcome, cost_usd)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        "#
+    )
+    .bind(&usage.provider)
+    .bind(&usage.model)
+    .bind(&usage.task)
+    .bind(&usage.prompt_version)
+    .bind(usage.prompt_tokens)
+    .bind(usage.completion_tokens)
+    .bind(usage.latency_ms)
+    .bind(&usage.outcome)
+    .bind(usage.cost_usd)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Total pemakaian satu provider sejak `since` (untuk budget cap)
+pub async fn get_llm_usage_totals(
+    pool: &PgPool,
+    provider: &str,
+    since: DateTime<Utc>,
+) -> Result<LlmUsageTotals, sqlx::Error> {
+    sqlx::query_as::<_, LlmUsageTotals>(
+        r#"
+        SELECT 
+            COUNT(*) as requests,
+            COALESCE(SUM(prompt_tokens + completion_tokens), 0)::BIGINT as tokens,
+            COALESCE(SUM(cost_usd), 0)::FLOAT8 as cost_usd
+        FROM llm_usage
+        WHERE provider = $1 AND created_at >= $2
+        "#
+    )
+    .bind(provider)
+    .bind(since)
+    .fetch_one(pool)
+    .await
+}
+
+/// Ringkasan pemakaian per provider + model sejak `since` (untuk #usage)
+pub async fn get_llm_usage_summary(pool: &PgPool, since: DateTime<Utc>) -> Result<Vec<LlmUsageSummary>, sqlx::Error> {
+    sqlx::query_as::<_, LlmUsageSummary>(
+        r#"
+        SELECT 
+            provider,
+            model,
+            COUNT(*) as requests,
+            COUNT(*) FILTER (WHERE outcome <> 'ok') as failures,
+            COALESCE(SUM(prompt_tokens), 0)::BIGINT as prompt_tokens,
+            COALESCE(SUM(completion_tokens), 0)::BIGINT as completion_tokens,
+            COALESCE(SUM(cost_usd), 0)::FLOAT8 as cost_usd,
+            COALESCE(AVG(latency_ms), 0)::FLOAT8 as avg_latency_ms
+        FROM llm_usage
+        WHERE created_at >= $1
+        GROUP BY provider, model
+        ORDER BY provider, requests DESC
+        "#
+    )
+    .bind(since)
+    .fetch_all(pool)
+    .await
+}
 
 
 /// Parse deadline string with TIMESTAMP support (YYYY-MM-DD HH:MM)
@@ -679,4 +1402,178 @@ pub fn parse_deadline(deadline_str: &str) -> Result<DateTime<Utc>, String> {
     }
     
     Err(format!("Failed to parse deadline '{}'. Expected format: 'YYYY-MM-DD HH:MM' or 'YYYY-MM-DD'", deadline_str))
-}
\ No newline at end of file
+}
+
+// ========================================
+// ASSIGNMENT REVIEWS
+// ========================================
+
+const REVIEW_COLUMNS: &str = r#"
+    r.id, r.course_id, c.name AS course_name, r.title, r.description, r.deadline,
+    r.parallel_code, r.sender_id, r.message_id, r.confidence, r.score, r.status, r.target_id, r.changed_fields, r.created_at
+"#;
+
+/// Simpan hasil ekstraksi yang kurang yakin ke antrian review
+pub async fn create_assignment_review(pool: &PgPool, review: &NewAssignmentReview) -> Result<AssignmentReview, sqlx::Error> {
+    let assignment = &review.assignment;
+    let id: Uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO assignment_reviews (course_id, title, description, deadline, parallel_code, sender_id, message_id, confidence, score, target_id, changed_fields)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+        RETURNING id
+        "#
+    )
+    .bind(assignment.course_id)
+    .bind(&assignment.title)
+    .bind(&assignment.description)
+    .bind(assignment.deadline)
+    .bind(assignment.parallel_code.as_ref().map(|p| p.to_lowercase()))
+    .bind(&assignment.sender_id)
+    .bind(&assignment.message_id)
+    .bind(sqlx::types::Json(review.confidence))
+    .bind(review.confidence.overall())
+    .bind(review.target_id)
+    .bind(&review.changed_fields)
+    .fetch_one(pool)
+    .await?;
+
+    sqlx::query_as::<_, AssignmentReview>(&format!(
+        "SELECT {} FROM assignment_reviews r LEFT JOIN courses c ON c.id = r.course_id WHERE r.id = $1",
+        REVIEW_COLUMNS
+    ))
+    .bind(id)
+    .fetch_one(pool)
+    .await
+}
+
+/// Antrian review yang belum diputuskan, terlama dulu
+pub async fn get_pending_reviews(pool: &PgPool) -> Result<Vec<AssignmentReview>, sqlx::Error> {
+    sqlx::query_as::<_, AssignmentReview>(&format!(
+        "SELECT {} FROM assignment_reviews r LEFT JOIN courses c ON c.id = r.course_id \
+         WHERE r.status = 'pending' ORDER BY r.created_at",
+        REVIEW_COLUMNS
+    ))
+    .fetch_all(pool)
+    .await
+}
+
+/// Tandai review approved / rejected. False kalau sudah diputuskan orang lain duluan
+pub async fn close_review(
+    pool: &PgPool,
+    review_id: Uuid,
+    status: &str,
+    reviewed_by: &str,
+    note: Option<&str>,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        r#"
+        UPDATE assignment_reviews
+        SET status = $2, reviewed_by = $3, reviewed_at = NOW(), review_note = $4
+        WHERE id = $1 AND status = 'pending'
+        "#
+    )
+    .bind(review_id)
+    .bind(status)
+    .bind(reviewed_by)
+    .bind(note)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() == 1)
+}
+
+/// Hubungkan review yang di-approve dengan tugas yang dibuat darinya
+pub async fn set_review_assignment(pool: &PgPool, review_id: Uuid, assignment_id: Uuid) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE assignment_reviews SET assignment_id = $2 WHERE id = $1")
+        .bind(review_id)
+        .bind(assignment_id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Kembalikan review ke antrian (approve gagal disimpan)
+pub async fn reopen_review(pool: &PgPool, review_id: Uuid) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        UPDATE assignment_reviews
+        SET status = 'pending', reviewed_by = NULL, reviewed_at = NULL, review_note = NULL
+        WHERE id = $1
+        "#
+    )
+    .bind(review_id)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+// ========================================
+// ASSIGNMENT VECTORS
+// ========================================
+
+/// Simpan / perbarui vektor term tugas (dipanggil setelah tugas dibuat atau diubah)
+pub async fn upsert_assignment_vector(pool: &PgPool, assignment_id: Uuid, terms: &TermVector) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO assignment_vectors (assignment_id, terms, model, updated_at)
+        VALUES ($1, $2, $3, NOW())
+        ON CONFLICT (assignment_id)
+        DO UPDATE SET terms = EXCLUDED.terms, model = EXCLUDED.model, updated_at = NOW()
+        "#
+    )
+    .bind(assignment_id)
+    .bind(sqlx::types::Json(terms))
+    .bind(similarity::MODEL)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Vektor tersimpan untuk tugas-tugas ini (yang modelnya masih sama). Yang belum punya tidak ikut
+pub async fn get_assignment_vectors(pool: &PgPool, assignment_ids: &[Uuid]) -> Result<HashMap<Uuid, TermVector>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, (Uuid, sqlx::types::Json<TermVector>)>(
+        "SELECT assignment_id, terms FROM assignment_vectors WHERE assignment_id = ANY($1) AND model = $2"
+    )
+    .bind(assignment_ids)
+    .bind(similarity::MODEL)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(|(id, terms)| (id, terms.0)).collect())
+}
+
+/// Statistik IDF: jumlah tugas aktif yang punya vektor, dan berapa di antaranya memuat tiap term
+pub async fn get_term_document_frequencies(pool: &PgPool, terms: &[String]) -> Result<Corpus, sqlx::Error> {
+    let documents: i64 = sqlx::query_scalar(
+        r#"
+        SELECT COUNT(*) FROM assignment_vectors v
+        JOIN assignments a ON a.id = v.assignment_id
+        WHERE a.deleted_at IS NULL AND v.model = $1
+        "#
+    )
+    .bind(similarity::MODEL)
+    .fetch_one(pool)
+    .await?;
+
+    let frequencies = sqlx::query_as::<_, (String, i64)>(
+        r#"
+        SELECT t.term, COUNT(*) FROM assignment_vectors v
+        JOIN assignments a ON a.id = v.assignment_id
+        CROSS JOIN LATERAL jsonb_object_keys(v.terms) AS t(term)
+        WHERE a.deleted_at IS NULL AND v.model = $1 AND t.term = ANY($2)
+        GROUP BY t.term
+        "#
+    )
+    .bind(similarity::MODEL)
+    .bind(terms)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(Corpus::new(
+        documents as usize,
+        frequencies.into_iter().map(|(term, count)| (term, count as usize)).collect(),
+    ))
+}
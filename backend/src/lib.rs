@@ -0,0 +1,18 @@
+// backend/src/lib.rs
+//
+// The bot as a library: the server (main.rs) and tools such as the
+// extraction eval (bin/eval.rs) share these modules.
+
+pub mod models;
+pub mod scheduler;
+pub mod classifier;
+pub mod parser;
+pub mod whitelist;
+pub mod database;
+pub mod clarification;
+pub mod stats;
+pub mod calendar;
+pub mod conversation;
+pub mod confidence;
+pub mod similarity;
+pub mod ocr;
@@ -1,6 +1,8 @@
 use axum::{
-    extract::State,
-    routing::post,
+    extract::{Path, State},
+    http::header,
+    response::IntoResponse,
+    routing::{get, post},
     Json,
     Router,
 };
@@ -17,21 +19,16 @@ use std::collections::HashMap;
 use chrono::{Datelike};
 use chrono::Duration as ChronoDuration;
 
-pub mod models;
-pub mod scheduler;
-pub mod classifier;
-pub mod parser;
-pub mod whitelist;
-pub mod database;
-pub mod clarification;
+use whatsapp_backend::{calendar, clarification, classifier, confidence, database, models, ocr, parser, scheduler, whitelist};
 
-use crate::database::crud;
-use crate::parser::commands::CommandResponse;
+use whatsapp_backend::database::crud;
+use whatsapp_backend::parser::commands::CommandResponse;
 
-use models::{MessageType, AIClassification, WebhookPayload, SendTextRequest, NewAssignment};
+use models::{MessageType, AIClassification, WebhookPayload, SendTextRequest, Assignment, NewAssignment, NewAssignmentReview, ConfidenceReport, FieldConfidence};
 use classifier::classify_message;
-use parser::commands::handle_command;
-use parser::ai_extractor::{extract_with_ai, check_duplicate_assignment}; 
+use parser::commands::{handle_command, handle_reply};
+use parser::commands::query::handle_query;
+use parser::ai_extractor::{extract_with_ai, check_duplicate_assignment, init_usage_tracking, load_prompt_templates, Cassette}; 
 use whitelist::Whitelist;
 
 type MessageCache = Arc<Mutex<HashSet<String>>>;
@@ -86,6 +83,25 @@ async fn main() {
     println!("    ├─ 🧠 Gemini AI    : {}", gemini_status);
     println!("    ├─ 🔌 WAHA API     : {}", waha_status);
 
+    // Optional LLM record/replay (LLM_CASSETTE), for capturing test fixtures
+    match Cassette::from_env().and_then(|c| c.map(Cassette::install).transpose()) {
+        Ok(Some(cassette)) => println!("    ├─ 📼 LLM Cassette : {:?}", cassette.mode()),
+        Ok(None) => {}
+        Err(e) => {
+            eprintln!("    ├─ 📼 LLM Cassette : \x1b[31m❌ {}\x1b[0m", e);
+            std::process::exit(1);
+        }
+    }
+
+    // Prompt templates (PROMPTS_DIR); invalid files fall back to the built-in copy
+    println!("    ├─ 📝 Prompts      : {}", load_prompt_templates().join(", "));
+
+    // Local OCR for announcement images (optional; without it images only reach the vision models)
+    match ocr::status() {
+        Ok(version) => println!("    ├─ 🔤 OCR          : \x1b[32m✅ {}\x1b[0m", version),
+        Err(e) => println!("    ├─ 🔤 OCR          : \x1b[33m⚠️  {}\x1b[0m", e),
+    }
+
     // 3. Koneksi Database
     print!("    ├─ 🗄️  Database     : 🔌 Connecting...");
     std::io::stdout().flush().unwrap();
@@ -105,6 +121,9 @@ async fn main() {
         }
     };
 
+    // Token / cost accounting + budget caps for every LLM call
+    init_usage_tracking(pool.clone());
+
     let whitelist = Arc::new(Whitelist::new());
     let cache = Arc::new(Mutex::new(HashSet::new()));
     
@@ -131,6 +150,7 @@ async fn main() {
     
     let app = Router::new()
         .route("/webhook", post(webhook))
+        .route("/calendar/:file", get(calendar_feed))
         .with_state(state);
 
     let port = 3000;
@@ -217,10 +237,13 @@ async fn webhook(
     // STEP 1: CLASSIFY MESSAGE DULUAN (Supaya bisa cek is_command)
     let message_type = classify_message(&payload.payload.body);
     let is_command = matches!(message_type, MessageType::Command(_));
+    // Pertanyaan bebas di chat pribadi ("tugas apa aja besok?")
+    let is_private_query = matches!(&message_type, MessageType::NeedsAI(text) if !text.trim().is_empty())
+        && state.whitelist.is_private_chat(chat_id);
 
 
-    // ANTI-SPAM (HANYA UNTUK COMMAND)
-    if is_command {
+    // ANTI-SPAM (COMMAND & PERTANYAAN DI CHAT PRIBADI)
+    if is_command || is_private_query {
         const MAX_MESSAGES: u32 = 5;      // Batas 5 command
         const WINDOW_SECONDS: u64 = 30;   // Dalam 30 detik
 
@@ -401,6 +424,23 @@ async fn webhook(
     }
     // ============= END CLARIFICATION =============
 
+    // PENDING FOLLOW-UP: balasan "ya/tidak" / nomor pilihan untuk command sebelumnya
+    if let MessageType::NeedsAI(ref text) = message_type {
+        if let Some(response) = handle_reply(text, sender_phone, sender_name, chat_id, &state.pool).await {
+            send_command_response(chat_id, response).await;
+            return StatusCode::OK;
+        }
+    }
+
+    // PRIVATE QUESTIONS: intent via AI, jawaban dari database
+    if is_private_query {
+        if let MessageType::NeedsAI(ref text) = message_type {
+            let response = handle_query(text, sender_phone, sender_name, chat_id, &state.pool).await;
+            send_command_response(chat_id, response).await;
+        }
+        return StatusCode::OK;
+    }
+
     // STEP 2: CHECK WHITELIST
     let (should_process, reason) =
         state.whitelist.should_process(chat_id, is_command);
@@ -415,37 +455,21 @@ async fn webhook(
         MessageType::Command(cmd) => {
             println!("⚙️  Processing command: {:?}", cmd);
             let response = handle_command(cmd, sender_phone, sender_name, chat_id, &state.pool).await;
-            
-            match response {
-                CommandResponse::Text(text) => {
-                    if let Err(e) = send_reply(chat_id, &text).await {
-                        eprintln!("❌ Failed to send reply: {}", e);
-                    }
-                }
-                CommandResponse::ForwardMessage { message_id, warning } => {
-                    if let Err(e) = forward_message(chat_id, &message_id).await {
-                        eprintln!("❌ Failed to forward message: {}", e);
-                    } else {
-                        if let Err(e) = send_reply(chat_id, &warning).await {
-                            eprintln!("❌ Failed to send warning: {}", e);
-                        }
-                    }
-                }
-            }
+            send_command_response(chat_id, response).await;
         }
 
         MessageType::NeedsAI(text) => {
             println!("🤖 Processing with AI...");
             
             // Image handling (GUNAKAN VERSI AMAN DARI KODE ORIGINAL ANDA)
-            let image_base64 = if payload.payload.has_media.unwrap_or(false) {
+            let image = if payload.payload.has_media.unwrap_or(false) {
                 if let Some(ref media) = payload.payload.media {
                     if let Some(ref media_url) = media.url {
                          if media.mimetype.as_ref().map(|m| m.starts_with("image/")).unwrap_or(false) {
                             let api_key = std::env::var("WAHA_API_KEY").unwrap_or_else(|_| "devkey123".to_string());
                             // Pakai fetch_image_from_url yang AMAN
                             match fetch_image_from_url(media_url, &api_key).await {
-                                Ok(base64) => Some(base64),
+                                Ok(bytes) => Some(bytes),
                                 Err(e) => {
                                     eprintln!("❌ Failed to download image: {}", e);
                                     None
@@ -456,6 +480,19 @@ async fn webhook(
                 } else { None }
             } else { None };
             
+            // Local OCR (Tesseract): text-only models and the rule-based tier can read the image too
+            let image_text = match &image {
+                Some(bytes) => ocr::extract_text(bytes).await,
+                None => None,
+            };
+            let image_base64 = image.and_then(|bytes| match encode_image_for_vision(&bytes) {
+                Ok(base64) => Some(base64),
+                Err(e) => {
+                    eprintln!("❌ Failed to prepare image: {}", e);
+                    None
+                }
+            });
+            
             // Context fetching
             let courses_list = crud::get_all_courses_formatted(&state.pool).await.unwrap_or_default();
             let active_assignments = crud::get_active_assignments(&state.pool).await.unwrap_or_default();
@@ -473,6 +510,7 @@ async fn webhook(
                 &active_assignments, 
                 &course_map, 
                 image_base64.as_deref(),
+                image_text.as_deref(),
                 sender_phone,   
                 &state.pool,
                 quoted_message_text.as_deref(),  
@@ -483,7 +521,14 @@ async fn webhook(
                     println!("🧠 AI Latency: {:.2?}", ai_duration);
 
                     println!("✅ AI Classification: {:?}\n", classification);
-                    handle_ai_classification(state.pool.clone(), classification, &payload.payload.id, sender_phone, debug_group_id).await;
+
+                    // What the confidence check may treat as written down
+                    let written = ocr::with_image_text(&text, image_text.as_deref());
+                    let evidence = match &quoted_message_text {
+                        Some(quoted) => format!("{}\n{}", written, quoted),
+                        None => written,
+                    };
+                    handle_ai_classification(state.pool.clone(), classification, &evidence, &payload.payload.id, sender_phone, debug_group_id).await;
                 }
                 Err(e) => {
                     eprintln!("❌ AI extraction failed: {}", e);
@@ -500,6 +545,29 @@ async fn webhook(
     StatusCode::OK
 }
 
+/// Deliver a command/query response (text, forwarded original, or file)
+async fn send_command_response(chat_id: &str, response: CommandResponse) {
+    match response {
+        CommandResponse::Text(text) => {
+            if let Err(e) = send_reply(chat_id, &text).await {
+                eprintln!("❌ Failed to send reply: {}", e);
+            }
+        }
+        CommandResponse::ForwardMessage { message_id, warning } => {
+            if let Err(e) = forward_message(chat_id, &message_id).await {
+                eprintln!("❌ Failed to forward message: {}", e);
+            } else if let Err(e) = send_reply(chat_id, &warning).await {
+                eprintln!("❌ Failed to send warning: {}", e);
+            }
+        }
+        CommandResponse::File { filename, mimetype, data, caption } => {
+            if let Err(e) = send_file(chat_id, &filename, &mimetype, &data, &caption).await {
+                eprintln!("❌ Failed to send file: {}", e);
+            }
+        }
+    }
+}
+
 async fn forward_message(chat_id: &str, message_id: &str) -> Result<(), String> {
     let waha_url = std::env::var("WAHA_URL").unwrap_or_else(|_| "http://localhost:3001".to_string());
     let api_key = std::env::var("WAHA_API_KEY").map_err(|e| e.to_string())?;
@@ -521,21 +589,98 @@ async fn forward_message(chat_id: &str, message_id: &str) -> Result<(), String>
         .map_err(|e| e.to_string())?;
     
     if !response.status().is_success() {
-        return Err(format!("Failed to forward message"));
+        return Err("Failed to forward message".to_string());
     }
     Ok(())
 }
 
+async fn send_file(chat_id: &str, filename: &str, mimetype: &str, data: &[u8], caption: &str) -> Result<(), String> {
+    use base64::{Engine as _, engine::general_purpose};
+
+    let waha_url = std::env::var("WAHA_URL").unwrap_or_else(|_| "http://localhost:3001".to_string());
+    let api_key = std::env::var("WAHA_API_KEY").map_err(|e| e.to_string())?;
+
+    let payload = serde_json::json!({
+        "session": "default",
+        "chatId": chat_id,
+        "file": {
+            "mimetype": mimetype,
+            "filename": filename,
+            "data": general_purpose::STANDARD.encode(data)
+        },
+        "caption": caption
+    });
+
+    let client = reqwest::Client::new();
+    let response = client
+        .post(format!("{}/api/sendFile", waha_url))
+        .header("X-Api-Key", api_key)
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if !response.status().is_success() {
+        return Err(format!("Failed to send file: {}", response.status()));
+    }
+    Ok(())
+}
+
+/// GET /calendar/<token>.ics — per-user subscription feed (token from `#export link`)
+async fn calendar_feed(
+    State(state): State<AppState>,
+    Path(file): Path<String>,
+) -> axum::response::Response {
+    let Some(token) = file.strip_suffix(".ics") else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    let user_id = match crud::get_user_by_calendar_token(&state.pool, token).await {
+        Ok(Some(user_id)) => user_id,
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(e) => {
+            eprintln!("❌ Calendar token lookup failed: {}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let assignments = match crud::get_active_assignments_for_user(&state.pool, &user_id).await {
+        Ok(a) => a.into_iter().filter(|a| !a.is_completed).collect::<Vec<_>>(),
+        Err(e) => {
+            eprintln!("❌ Calendar feed query failed: {}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let lead_minutes = match crud::get_reminder_preference(&state.pool, &user_id).await {
+        Ok(Some(pref)) => pref.lead_minutes,
+        _ => models::ReminderPreference::default_for(&user_id).lead_minutes,
+    };
+
+    println!("📅 Calendar feed served ({} assignments)", assignments.len());
+
+    (
+        [
+            (header::CONTENT_TYPE, "text/calendar; charset=utf-8"),
+            (header::CACHE_CONTROL, "private, max-age=900"),
+        ],
+        calendar::build_calendar(&assignments, &lead_minutes, chrono::Utc::now()),
+    )
+        .into_response()
+}
+
 #[allow(non_snake_case)]
 async fn handle_ai_classification(
     pool: PgPool,
     classification: AIClassification, 
+    evidence: &str,
     message_id: &str,
     sender_id: &str,
     debug_group_id: Option<String>,
 ) {
     let message_id = message_id.to_string();
     let sender_id = sender_id.to_string();
+    let evidence = evidence.to_string();
     
     match classification {
         // NEW: Handle multiple assignments
@@ -598,6 +743,9 @@ async fn handle_ai_classification(
                     assignment.deadline,
                     assignment.description,
                     assignment.parallel_code,
+                    assignment.confidence,
+                    false,
+                    &evidence,
                     &msg_id,
                     &sender_id,
                     debug_group_id.clone(),
@@ -607,7 +755,7 @@ async fn handle_ai_classification(
         }
         
         // Single assignment - USE AI FOR DUPLICATE DETECTION
-        AIClassification::AssignmentInfo { course_name, title, deadline, description, parallel_code, .. } => {
+        AIClassification::AssignmentInfo { course_name, title, deadline, description, parallel_code, confidence, low_confidence, .. } => {
             let debug_group = debug_group_id.clone();
             
             tokio::spawn(async move {
@@ -618,6 +766,9 @@ async fn handle_ai_classification(
                     deadline,
                     description,
                     parallel_code,
+                    confidence,
+                    low_confidence,
+                    &evidence,
                     &message_id,
                     &sender_id,
                     debug_group,
@@ -660,6 +811,7 @@ async fn handle_ai_classification(
                     .unwrap_or_default();
                 
                 // ===== SMART UPDATE: Check for re-announcement =====
+                let mut reannounced = None;
                 if let Some(ref title) = new_title {
                     if let (Some(_course_id), Some(cname)) = (course_id, &course_name) {
                         let dup_check = check_duplicate_assignment(
@@ -669,78 +821,92 @@ async fn handle_ai_classification(
                             parallel_code.as_deref(),
                             &active_assignments,
                             &course_map,
+                            &pool,
                         ).await;
                         
                         if let Ok(Some(id)) = dup_check {
                             println!("🔄 RE-ANNOUNCEMENT: {} → Updating existing", title);
-                            
-                            let deadline_parsed = new_deadline.as_ref()
-                                .and_then(|d| crud::parse_deadline(d).ok());
-                            
-                            let _ = crud::update_assignment_fields(
-                                &pool_clone,
-                                id,
-                                deadline_parsed,
-                                None,
-                                new_description.clone(),
-                                parallel_code.clone(),
-                                Some(msg_id.clone()),
-                            ).await;
-                            
-                            if let Some(debug_id) = debug_clone {
-                                let _ = send_reply(
-                                    &debug_id,
-                                    &format!("🔄 *UPDATED*: {}", title)
-                                ).await;
-                            }
-                            return;
+                            reannounced = Some(id);
                         }
                     }
                 }
                 
                 // ===== REGULAR UPDATE MATCHING =====
-                match parser::ai_extractor::match_update_to_assignment(
-                    &changes,
-                    &reference_keywords,
-                    &active_assignments,
-                    &course_map,
-                    parallel_code.as_deref(),
-                ).await {
-                    Ok(Some(assignment_id)) => {
-                        let deadline_parsed = new_deadline.as_ref()
-                            .and_then(|d| crud::parse_deadline(d).ok());
-                        
-                        if let Ok(updated) = crud::update_assignment_fields(
-                            &pool_clone,
-                            assignment_id,
-                            deadline_parsed,
-                            new_title.clone(),
-                            new_description.clone(),
-                            parallel_code.clone(),
-                            Some(msg_id),
-                        ).await {
-                            println!("🔄 UPDATED: {} ({})", updated.title, changes);
+                let (target_id, title_change) = match reannounced {
+                    // A re-announcement keeps the existing title
+                    Some(id) => (id, None),
+                    None => match parser::ai_extractor::match_update_to_assignment(
+                        &changes,
+                        &reference_keywords,
+                        &active_assignments,
+                        &course_map,
+                        parallel_code.as_deref(),
+                    ).await {
+                        Ok(Some(assignment_id)) => (assignment_id, new_title.clone()),
+                        Ok(None) => {
+                            println!("⚠️  No match found for update: {:?}", reference_keywords);
                             
                             if let Some(debug_id) = debug_clone {
                                 let _ = send_reply(
                                     &debug_id,
-                                    &format!("🔄 *UPDATED*: {}", updated.title)
+                                    "⚠️ Could not find assignment to update"
                                 ).await;
                             }
+                            return;
                         }
-                    }
-                    Ok(None) => {
-                        println!("⚠️  No match found for update: {:?}", reference_keywords);
-                        
-                        if let Some(debug_id) = debug_clone {
-                            let _ = send_reply(
-                                &debug_id,
-                                "⚠️ Could not find assignment to update"
-                            ).await;
+                        Err(e) => {
+                            eprintln!("❌ Update matching failed: {}", e);
+                            return;
                         }
-                    }
-                    Err(e) => {
-                        eprintln!("❌ Update matching failed: {}", e);
+                    },
+                };
+                
+                let Some(target) = active_assignments.iter().find(|a| a.id == target_id) else {
+                    eprintln!("❌ Update target {} is not among the active assignments", target_id);
+                    return;
+                };
+                let deadline_parsed = new_deadline.as_ref()
+                    .and_then(|d| crud::parse_deadline(d).ok());
+                
+                // ===== CONFIDENCE GATE: an unsure update waits for #approve like a new task =====
+                let report = confidence::score_update(
+                    &evidence,
+                    title_change.as_deref(),
+                    deadline_parsed.is_some(),
+                    parallel_code.as_deref(),
+                );
+                
+                if report.overall() < confidence::review_threshold() {
+                    let review = update_review(
+                        target,
+                        deadline_parsed,
+                        title_change,
+                        new_description,
+                        parallel_code,
+                        &sender_id,
+                        &msg_id,
+                        report,
+                    );
+                    hold_for_review(&pool_clone, review, false, debug_clone.as_deref()).await;
+                    return;
+                }
+                
+                if let Ok(updated) = crud::update_assignment_fields(
+                    &pool_clone,
+                    target_id,
+                    deadline_parsed,
+                    title_change,
+                    new_description,
+                    parallel_code,
+                    Some(msg_id),
+                ).await {
+                    println!("🔄 UPDATED: {} ({})", updated.title, changes);
+                    
+                    if let Some(debug_id) = debug_clone {
+                        let _ = send_reply(
+                            &debug_id,
+                            &format!("🔄 *UPDATED*: {}", updated.title)
+                        ).await;
                     }
                 }
             });
@@ -752,6 +918,7 @@ async fn handle_ai_classification(
 
 /// Handle a single assignment with improved AI-powered duplicate detection
 #[allow(non_snake_case)]
+#[allow(clippy::too_many_arguments)]
 async fn handle_single_assignment(
     pool: PgPool,
     course_name: Option<String>,
@@ -759,6 +926,9 @@ async fn handle_single_assignment(
     deadline: Option<String>,
     description: Option<String>,
     parallel_code: Option<String>,
+    reported_confidence: FieldConfidence,
+    low_confidence: bool,
+    evidence: &str,
     message_id: &str,
     sender_id: &str,
     debug_group_id: Option<String>,
@@ -771,11 +941,26 @@ async fn handle_single_assignment(
     let parallel_code_parsed = extract_parallel_code(&title);
     let final_parallel = parallel_code.or(parallel_code_parsed);
     
-    let course_id = if let Some(name) = &course_name {
-        crud::get_course_by_name(&pool, name).await.ok().flatten().map(|c| c.id)
+    let course = if let Some(name) = &course_name {
+        crud::get_course_by_name(&pool, name).await.ok().flatten()
     } else { None };
-    
-    // Replace logging in handle_single_assignment
+    let course_id = course.as_ref().map(|c| c.id);
+
+    // ========================================
+    // CONFIDENCE: scored before the duplicate check, a merge overwrites fields too
+    // ========================================
+    let report = confidence::score(
+        evidence,
+        &confidence::Extracted {
+            course: course.as_ref(),
+            title: &title_clone,
+            has_deadline: deadline_parsed.is_some(),
+            parallel_code: final_parallel.as_deref(),
+        },
+        reported_confidence,
+        low_confidence,
+    );
+    let confident = report.overall() >= confidence::review_threshold();
 
     // ========================================
     // IMPROVED DUPLICATE DETECTION
@@ -804,6 +989,7 @@ async fn handle_single_assignment(
                     final_parallel.as_deref(),
                     &existing_assignments,
                     &course_map,
+                    &pool,
                 ).await;
                 
                 //let match_duration = match_start.elapsed();
@@ -812,13 +998,32 @@ async fn handle_single_assignment(
                     Ok(Some(id)) => {
                         println!("🔄 DUPLICATE: {} → Updating existing ({})", title_clone, id);
                         
+                        if !confident {
+                            let Some(target) = existing_assignments.iter().find(|a| a.id == *id) else {
+                                eprintln!("❌ Duplicate {} is not among the candidates", id);
+                                return;
+                            };
+                            let review = update_review(
+                                target,
+                                deadline_parsed,
+                                None,
+                                Some(desc_clone.clone()),
+                                final_parallel.clone(),
+                                sender_id,
+                                message_id,
+                                report,
+                            );
+                            hold_for_review(&pool, review, low_confidence, debug_group_id.as_deref()).await;
+                            return;
+                        }
+                        
                         let update_result = crud::update_assignment_fields(
                             &pool, 
                             *id, 
                             deadline_parsed, 
                             None,
                             Some(desc_clone.clone()), 
-                            final_parallel.as_ref().map(|s| s.clone()),
+                            final_parallel.clone(),
                             Some(message_id.to_string())
                         ).await;
                         
@@ -865,6 +1070,16 @@ async fn handle_single_assignment(
         sender_id: Some(sender_id.to_string()), 
         message_id: message_id.to_string()
     };
+
+    // ========================================
+    // CONFIDENCE GATE: unsure extractions wait for #approve in the debug group
+    // ========================================
+    // Rule-based (no AI) is published and goes through the forced title clarification below
+    if !confident && !low_confidence {
+        let review = NewAssignmentReview { assignment: new_assignment, confidence: report, target_id: None, changed_fields: Vec::new() };
+        hold_for_review(&pool, review, low_confidence, debug_group_id.as_deref()).await;
+        return;
+    }
     
     match crud::create_assignment(&pool, new_assignment).await {
         Ok(_) => {
@@ -875,10 +1090,19 @@ async fn handle_single_assignment(
             if let Some(cid) = course_id {
                 if let Ok(Some(assignment)) = crud::get_assignment_by_title_and_course(&pool, &title_clone, cid).await {
                     if let Ok(Some(full_assign)) = crud::get_assignment_with_course_by_id(&pool, assignment.id).await {
-                        let missing = clarification::identify_missing_fields(&full_assign);
+                        let mut missing = clarification::identify_missing_fields(&full_assign);
+
+                        // Rule-based fallback only guesses the title → always have it confirmed
+                        if low_confidence && !missing.iter().any(|f| f == "title") {
+                            missing.insert(0, "title".to_string());
+                        }
+
                         if !missing.is_empty() {
                             if let Some(debug_id) = &debug_group_id {
-                                let (info_msg, template_msg) = clarification::generate_clarification_messages(&full_assign, &missing);
+                                let (mut info_msg, template_msg) = clarification::generate_clarification_messages(&full_assign, &missing);
+                                if low_confidence {
+                                    info_msg = format!("{}\n\n{}", clarification::generate_low_confidence_notice(), info_msg);
+                                }
                                 
                                 // Send first message (info)
                                 let _ = send_reply(debug_id, &info_msg).await;
@@ -941,13 +1165,82 @@ async fn handle_single_assignment(
 }
 
 
+/// Queue an unsure extraction for #approve / #reject in the debug group.
+/// Without a debug group it still waits in the queue (nothing is discarded),
+/// the card is just not posted anywhere.
+async fn hold_for_review(pool: &PgPool, review: NewAssignmentReview, low_confidence: bool, debug_group_id: Option<&str>) {
+    let title = review.assignment.title.clone();
+    println!("🔍 LOW CONFIDENCE ({:.2}): {} → review queue", review.confidence.overall(), title);
+
+    match crud::create_assignment_review(pool, &review).await {
+        Ok(queued) => {
+            let Some(debug_id) = debug_group_id else {
+                println!("   ⚠️  No DEBUG_GROUP_ID: review {} waits until one is configured", queued.id);
+                return;
+            };
+            let mut card = parser::commands::review::format_review_card(&queued);
+            if low_confidence {
+                card = format!("{}\n\n{}", clarification::generate_low_confidence_notice(), card);
+            }
+            let _ = send_reply(debug_id, &card).await;
+        }
+        Err(e) => {
+            eprintln!("❌ Failed to queue review: {}", e);
+            if let Some(debug_id) = debug_group_id {
+                let _ = send_reply(debug_id, &format!("⚠️ Failed to queue review: {}", title)).await;
+            }
+        }
+    }
+}
+
+/// Review of an update to `target`. Only the fields the update sets are recorded as changed
+/// (None = keep, like `crud::update_assignment_fields`); the rest is shown from the target.
+#[allow(clippy::too_many_arguments)]
+fn update_review(
+    target: &Assignment,
+    deadline: Option<chrono::DateTime<chrono::Utc>>,
+    title: Option<String>,
+    description: Option<String>,
+    parallel_code: Option<String>,
+    sender_id: &str,
+    message_id: &str,
+    confidence: ConfidenceReport,
+) -> NewAssignmentReview {
+    let changed_fields = [
+        ("title", title.is_some()),
+        ("description", description.is_some()),
+        ("deadline", deadline.is_some()),
+        ("parallel_code", parallel_code.is_some()),
+    ]
+    .into_iter()
+    .filter(|(_, changed)| *changed)
+    .map(|(field, _)| field.to_string())
+    .collect();
+
+    NewAssignmentReview {
+        assignment: NewAssignment {
+            course_id: target.course_id,
+            title: title.unwrap_or_else(|| target.title.clone()),
+            description: description.unwrap_or_else(|| target.description.clone()),
+            deadline: deadline.or(target.deadline),
+            parallel_code: parallel_code.map(|p| p.to_lowercase()).or_else(|| target.parallel_code.clone()),
+            sender_id: Some(sender_id.to_string()),
+            message_id: message_id.to_string(),
+        },
+        confidence,
+        target_id: Some(target.id),
+        changed_fields,
+    }
+}
+
+
 async fn send_reply(chat_id: &str, text: &str) -> Result<(), String> {
     let waha_url = "http://localhost:3001/api/sendText";
     let api_key = std::env::var("WAHA_API_KEY").unwrap_or_else(|_| "devkey123".to_string());
     let payload = SendTextRequest { chat_id: chat_id.to_string(), text: text.to_string(), session: "default".to_string() };
     let client = reqwest::Client::new();
     let res = client.post(waha_url).header("X-Api-Key", api_key).json(&payload).send().await.map_err(|e| e.to_string())?;
-    if res.status().is_success() { Ok(()) } else { Err(format!("API Error")) }
+    if res.status().is_success() { Ok(()) } else { Err("API Error".to_string()) }
 }
 
 fn extract_parallel_code(title: &str) -> Option<String> {
@@ -956,7 +1249,7 @@ fn extract_parallel_code(title: &str) -> Option<String> {
     ["K1", "K2", "K3", "P1", "P2", "P3"].iter().find(|&c| u.contains(c)).map(|c| c.to_lowercase())
 }
 
-async fn fetch_image_from_url(url: &str, api_key: &str) -> Result<String, String> {
+async fn fetch_image_from_url(url: &str, api_key: &str) -> Result<Vec<u8>, String> {
     let url = url.replace("http://localhost:3000", "http://localhost:3001");
     let client = reqwest::Client::new();
     let res = client.get(&url).header("X-Api-Key", api_key).send().await.map_err(|e| e.to_string())?;
@@ -966,7 +1259,11 @@ async fn fetch_image_from_url(url: &str, api_key: &str) -> Result<String, String
     }
     
     let bytes = res.bytes().await.map_err(|e| e.to_string())?;
-    
+    Ok(bytes.to_vec())
+}
+
+/// Base64 for the vision models, recompressed when over their size limit
+fn encode_image_for_vision(bytes: &[u8]) -> Result<String, String> {
     use base64::{Engine as _, engine::general_purpose};
     use image::io::Reader as ImageReader;
     use std::io::Cursor;
@@ -974,7 +1271,7 @@ async fn fetch_image_from_url(url: &str, api_key: &str) -> Result<String, String
     if (bytes.len() as f64 / 1_000_000.0) > 3.5 {
          println!("   🔄 Compressing image...");
          
-         let img = ImageReader::new(Cursor::new(&bytes))
+         let img = ImageReader::new(Cursor::new(bytes))
             .with_guessed_format()
             .map_err(|e| format!("Format error: {}", e))?
             .decode()
@@ -987,6 +1284,6 @@ async fn fetch_image_from_url(url: &str, api_key: &str) -> Result<String, String
             
          Ok(general_purpose::STANDARD.encode(&buf))
     } else {
-         Ok(general_purpose::STANDARD.encode(&bytes))
+         Ok(general_purpose::STANDARD.encode(bytes))
     }
 }
\ No newline at end of file
@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use sqlx::FromRow;
 use uuid::Uuid;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveTime, Utc};
 
 // ===== WEBHOOK PAYLOAD TYPES (from WAHA) =====
 
@@ -131,19 +131,60 @@ pub enum MessageType {
 
 #[derive(Debug)]
 pub enum BotCommand {
-    Ping,
-    Tugas,
-    Today,
-    Week,
-    Expand(u32),
-    Todo,
-    Done(u32),
-    Undo,
-    Help,
-    Delete(u32),
+    /// Registered command (see `parser::registry`) with arguments already checked against its schema
+    Run { name: &'static str, args: CommandArgs },
+    /// Known command word, but the arguments don't match its schema
+    InvalidArgs(&'static str),
     UnknownCommand(String),
 }
 
+/// Parsed command argument
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArgValue {
+    Index(u32),
+    Minutes(i32),
+    Text(String),
+}
+
+/// Arguments in schema order; optional arguments that were not given are `None`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CommandArgs(pub Vec<Option<ArgValue>>);
+
+impl CommandArgs {
+    pub fn index(&self, pos: usize) -> Option<u32> {
+        match self.0.get(pos)? {
+            Some(ArgValue::Index(i)) => Some(*i),
+            _ => None,
+        }
+    }
+
+    pub fn minutes(&self, pos: usize) -> Option<i32> {
+        match self.0.get(pos)? {
+            Some(ArgValue::Minutes(m)) => Some(*m),
+            _ => None,
+        }
+    }
+
+    pub fn text(&self, pos: usize) -> Option<&str> {
+        match self.0.get(pos)? {
+            Some(ArgValue::Text(t)) => Some(t.as_str()),
+            _ => None,
+        }
+    }
+}
+
+/// Sub-commands of `#remind` (personal DM reminder settings)
+#[derive(Debug)]
+pub enum RemindAction {
+    Status,
+    On,
+    Off,
+    Times(Vec<NaiveTime>),
+    Lead(Vec<i32>),
+    Quiet(Option<(NaiveTime, NaiveTime)>),
+    Usage,
+}
+
 // ===== AI EXTRACTION RESULTS =====
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -157,8 +198,14 @@ pub enum AIClassification {
         description: Option<String>,
         parallel_code: Option<String>,
         #[serde(default)]
+        confidence: FieldConfidence,
+        #[serde(default)]
         #[serde(skip_serializing_if = "Option::is_none")]
         original_message: Option<String>,
+        /// Set by the rule-based fallback (no LLM) → always ask for clarification
+        #[serde(default)]
+        #[serde(skip_serializing_if = "std::ops::Not::not")]
+        low_confidence: bool,
     },
     
     /// NEW: Multiple assignments in one message
@@ -192,6 +239,30 @@ pub struct AssignmentData {
     pub deadline: Option<String>,
     pub description: Option<String>,
     pub parallel_code: Option<String>,
+    #[serde(default)]
+    pub confidence: FieldConfidence,
+}
+
+/// Model-reported confidence (0–1) per extracted field; None = not reported
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+pub struct FieldConfidence {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub course_name: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deadline: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parallel_code: Option<f32>,
+}
+
+/// Combined confidence per field (model + rule signals), see confidence.rs
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct ConfidenceReport {
+    pub course_name: f32,
+    pub title: f32,
+    pub deadline: f32,
+    pub parallel_code: f32,
 }
 
 // ===== DATABASE MODELS =====
@@ -243,7 +314,7 @@ pub struct NewAssignment {
     pub message_id: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, FromRow)]
 pub struct AssignmentWithCourse {
     pub id: uuid::Uuid,
     pub course_name: String,
@@ -262,12 +333,92 @@ impl AssignmentWithCourse {
     }
 }
 
+/// Assignment the user snoozed or hid (shown by `#hidden`)
+#[derive(Debug, Clone, FromRow)]
+pub struct HiddenAssignment {
+    pub id: Uuid,
+    pub course_name: String,
+    pub title: String,
+    pub deadline: Option<DateTime<Utc>>,
+    pub hidden: bool,
+    pub snoozed_until: Option<DateTime<Utc>>,
+}
+
+/// Private note a user attached to an assignment (`#note`)
+#[derive(Debug, Clone, FromRow)]
+pub struct AssignmentNote {
+    pub assignment_id: Uuid,
+    pub course_name: String,
+    pub title: String,
+    pub deadline: Option<DateTime<Utc>>,
+    pub note: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Soft-deleted assignment (shown by `#trash`)
+#[derive(Debug, Clone, FromRow)]
+pub struct TrashedAssignment {
+    pub id: Uuid,
+    pub course_name: String,
+    pub title: String,
+    pub deadline: Option<DateTime<Utc>>,
+    pub deleted_at: DateTime<Utc>,
+    pub deleted_by: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct UserCompletion {
     pub user_id: String,
     pub assignment_id: Uuid,
 }
 
+/// Per-user settings for personal DM reminders (times are WIB)
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ReminderPreference {
+    pub user_id: String,
+    pub enabled: bool,
+    pub digest_times: Vec<NaiveTime>,
+    pub lead_minutes: Vec<i32>,
+    pub quiet_start: Option<NaiveTime>,
+    pub quiet_end: Option<NaiveTime>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ReminderPreference {
+    /// Defaults used before the user has touched `#remind` (mirrors the table defaults)
+    pub fn default_for(user_id: &str) -> Self {
+        Self {
+            user_id: user_id.to_string(),
+            enabled: false,
+            digest_times: vec![NaiveTime::from_hms_opt(7, 0, 0).unwrap()],
+            lead_minutes: vec![1440, 180],
+            quiet_start: None,
+            quiet_end: None,
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Check if a WIB time falls inside the user's quiet hours (handles ranges past midnight)
+    pub fn is_quiet_at(&self, time: NaiveTime) -> bool {
+        match (self.quiet_start, self.quiet_end) {
+            (Some(start), Some(end)) if start <= end => time >= start && time < end,
+            (Some(start), Some(end)) => time >= start || time < end,
+            _ => false,
+        }
+    }
+
+    /// Human readable lead time: 1440 -> "H-1", 180 -> "3 jam", 30 -> "30 menit"
+    pub fn describe_lead(minutes: i32) -> String {
+        if minutes >= 1440 && minutes % 1440 == 0 {
+            format!("H-{}", minutes / 1440)
+        } else if minutes >= 60 && minutes % 60 == 0 {
+            format!("{} jam", minutes / 60)
+        } else {
+            format!("{} menit", minutes)
+        }
+    }
+}
+
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
 pub struct WaLog {
     pub id: Uuid,
@@ -282,3 +433,71 @@ pub struct NewWaLog {
     pub event_type: Option<String>,
     pub payload: Option<Value>,
 }
+
+// ===== LLM USAGE =====
+
+#[derive(Debug, Clone)]
+pub struct NewLlmUsage {
+    pub provider: String,
+    pub model: String,
+    pub task: String,
+    /// Prompt template version, e.g. "classification@2026-10-18.1+9f1c2ab0"
+    pub prompt_version: Option<String>,
+    pub prompt_tokens: i32,
+    pub completion_tokens: i32,
+    pub latency_ms: i32,
+    pub outcome: String,
+    pub cost_usd: f64,
+}
+
+/// What one provider used since a point in time (budget checks)
+#[derive(Debug, Clone, Default, PartialEq, FromRow)]
+pub struct LlmUsageTotals {
+    pub requests: i64,
+    pub tokens: i64,
+    pub cost_usd: f64,
+}
+
+/// One line of #usage: totals per provider + model
+#[derive(Debug, Clone, FromRow)]
+pub struct LlmUsageSummary {
+    pub provider: String,
+    pub model: String,
+    pub requests: i64,
+    pub failures: i64,
+    pub prompt_tokens: i64,
+    pub completion_tokens: i64,
+    pub cost_usd: f64,
+    pub avg_latency_ms: f64,
+}
+
+/// Low-confidence extraction waiting for #approve / #reject in the debug group
+#[derive(Debug, Clone, FromRow)]
+pub struct AssignmentReview {
+    pub id: Uuid,
+    pub course_id: Option<Uuid>,
+    pub course_name: Option<String>,
+    pub title: String,
+    pub description: String,
+    pub deadline: Option<DateTime<Utc>>,
+    pub parallel_code: Option<String>,
+    pub sender_id: Option<String>,
+    pub message_id: String,
+    pub confidence: sqlx::types::Json<ConfidenceReport>,
+    pub score: f32,
+    pub status: String,
+    /// Existing assignment this review would update (duplicate / update); None = new assignment
+    pub target_id: Option<Uuid>,
+    /// With `target_id`: the only fields #approve writes ("title", "description", "deadline", "parallel_code")
+    pub changed_fields: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+pub struct NewAssignmentReview {
+    /// For an update: the target's fields after the change
+    pub assignment: NewAssignment,
+    pub confidence: ConfidenceReport,
+    pub target_id: Option<Uuid>,
+    pub changed_fields: Vec<String>,
+}
@@ -0,0 +1,161 @@
+// backend/src/ocr.rs
+//
+// Local OCR for announcement images (mostly screenshots of e-learning pages)
+// with the Tesseract CLI. The text read from the image goes into the
+// extraction prompt next to the message, so the text-only models and the
+// rule-based fallback can handle image announcements when the vision models
+// fail. Tesseract is optional: without it images only go to the vision tier.
+
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+const DEFAULT_LANGS: &str = "ind+eng";
+const TIMEOUT: Duration = Duration::from_secs(20);
+/// A whole LMS page is mostly navigation; the prompt only needs the start
+const MAX_CHARS: usize = 3000;
+/// Less readable text than this = a photo or meme, not an announcement
+const MIN_ALPHANUMERIC: usize = 12;
+
+fn command() -> String {
+    std::env::var("TESSERACT_CMD").unwrap_or_else(|_| "tesseract".to_string())
+}
+
+fn languages() -> String {
+    std::env::var("OCR_LANGS").unwrap_or_else(|_| DEFAULT_LANGS.to_string())
+}
+
+/// OCR_ENABLED=false turns it off even if Tesseract is installed
+fn enabled() -> bool {
+    std::env::var("OCR_ENABLED")
+        .map(|v| v.trim() != "false")
+        .unwrap_or(true)
+}
+
+/// Startup check: "tesseract 5.3.4 (ind+eng)", or why OCR won't run
+pub fn status() -> Result<String, String> {
+    if !enabled() {
+        return Err("disabled (OCR_ENABLED=false)".to_string());
+    }
+    let output = std::process::Command::new(command())
+        .arg("--version")
+        .output()
+        .map_err(|e| format!("{} not available: {}", command(), e))?;
+    // Older versions print the banner to stderr
+    let banner = if output.stdout.is_empty() { output.stderr } else { output.stdout };
+    let version = String::from_utf8_lossy(&banner).lines().next().unwrap_or_default().trim().to_string();
+    Ok(format!("{} ({})", version, languages()))
+}
+
+/// Text in the image, cleaned up; `None` if OCR is off, failed, or found nothing readable
+pub async fn extract_text(image: &[u8]) -> Option<String> {
+    if !enabled() {
+        return None;
+    }
+    match run_tesseract(image).await {
+        Ok(raw) => clean(&raw),
+        Err(e) => {
+            eprintln!("   ⚠️  OCR failed: {}", e);
+            None
+        }
+    }
+}
+
+async fn run_tesseract(image: &[u8]) -> Result<String, String> {
+    let mut child = Command::new(command())
+        .args(["stdin", "stdout", "-l", &languages(), "--psm", "3"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("{}: {}", command(), e))?;
+
+    let mut stdin = child.stdin.take().ok_or("no stdin")?;
+    let run = async {
+        stdin.write_all(image).await.map_err(|e| format!("write: {}", e))?;
+        drop(stdin);
+        child.wait_with_output().await.map_err(|e| e.to_string())
+    };
+    let output = tokio::time::timeout(TIMEOUT, run)
+        .await
+        .map_err(|_| format!("timed out after {}s", TIMEOUT.as_secs()))??;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(stderr.lines().find(|l| !l.trim().is_empty()).unwrap_or("unknown error").trim().to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Drop the noise Tesseract reads out of icons and borders ("|", "—~=", "@ ®")
+fn clean(raw: &str) -> Option<String> {
+    let mut text = String::new();
+    let mut alphanumeric = 0;
+
+    for line in raw.lines() {
+        let line = line.split_whitespace().collect::<Vec<_>>().join(" ");
+        let letters = line.chars().filter(|c| c.is_alphanumeric()).count();
+        let visible = line.chars().filter(|c| !c.is_whitespace()).count();
+        if letters < 2 || letters * 2 < visible {
+            continue;
+        }
+        if text.len() + line.len() + 1 > MAX_CHARS {
+            break;
+        }
+        if !text.is_empty() {
+            text.push('\n');
+        }
+        text.push_str(&line);
+        alphanumeric += letters;
+    }
+
+    (alphanumeric >= MIN_ALPHANUMERIC).then_some(text)
+}
+
+/// The message plus what was read from its image, for everything that works on plain text
+pub fn with_image_text(message: &str, image_text: Option<&str>) -> String {
+    match image_text {
+        Some(image_text) if message.trim().is_empty() => image_text.to_string(),
+        Some(image_text) => format!("{}\n{}", message, image_text),
+        None => message.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_drops_noise_lines() {
+        let raw = "  Pemrograman  (KOM120C)\n|\n—~= @ ®\n\nLKP 6: Stack &   Queue\nDue: Wednesday, 21 October 2026, 11:59 PM\n\u{c}";
+
+        assert_eq!(
+            clean(raw).as_deref(),
+            Some("Pemrograman (KOM120C)\nLKP 6: Stack & Queue\nDue: Wednesday, 21 October 2026, 11:59 PM")
+        );
+    }
+
+    #[test]
+    fn test_clean_rejects_images_without_text() {
+        assert_eq!(clean(""), None);
+        assert_eq!(clean("| ~\n@@ —\nwkwk"), None);
+    }
+
+    #[test]
+    fn test_clean_caps_length_at_line_boundary() {
+        let raw = "Baris pengumuman tugas nomor satu\n".repeat(200);
+        let text = clean(&raw).unwrap();
+
+        assert!(text.len() <= MAX_CHARS);
+        assert!(text.lines().all(|l| l == "Baris pengumuman tugas nomor satu"));
+    }
+
+    #[test]
+    fn test_with_image_text() {
+        assert_eq!(with_image_text("info tugas", Some("LKP 6")), "info tugas\nLKP 6");
+        assert_eq!(with_image_text("  ", Some("LKP 6")), "LKP 6");
+        assert_eq!(with_image_text("info tugas", None), "info tugas");
+    }
+}
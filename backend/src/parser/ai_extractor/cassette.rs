@@ -0,0 +1,229 @@
+// backend/src/parser/ai_extractor/cassette.rs
+//
+// Record / replay of LLM HTTP traffic. Recording stores every provider
+// request (URL without API key + JSON body) with the status and body that
+// came back; replay serves those from disk so the same pipeline runs
+// offline and deterministically. Nothing is installed in normal operation.
+//
+// A cassette is either installed for the whole process (eval binary,
+// LLM_CASSETTE env) or scoped to one async block (integration tests, so
+// they can run in parallel with their own fixtures).
+
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+static CASSETTE: OnceCell<Arc<Cassette>> = OnceCell::new();
+
+tokio::task_local! {
+    static SCOPED: Arc<Cassette>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CassetteMode {
+    /// Hit the real APIs and remember every exchange
+    Record,
+    /// Answer from disk only
+    Replay,
+}
+
+/// In replay the recorded `request` only has to be a subset of the real one,
+/// so hand-written fixtures can match on e.g. `{"model": "..."}` alone.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    pub url: String,
+    pub request: Value,
+    pub status: u16,
+    /// Retry-After header (seconds) of a 429
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_after: Option<u64>,
+    pub response: String,
+}
+
+pub struct Cassette {
+    mode: CassetteMode,
+    path: String,
+    interactions: Mutex<Vec<Interaction>>,
+    /// Replay: which recorded interactions were already served
+    used: Mutex<Vec<bool>>,
+    /// Replay: requests nothing was recorded for
+    unmatched: Mutex<Vec<String>>,
+}
+
+impl Cassette {
+    pub fn record(path: &str) -> Self {
+        Self::with(CassetteMode::Record, path, Vec::new())
+    }
+
+    pub fn replay(path: &str) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path).map_err(|e| format!("Failed to read cassette {}: {}", path, e))?;
+        let interactions: Vec<Interaction> =
+            serde_json::from_str(&content).map_err(|e| format!("Invalid cassette {}: {}", path, e))?;
+        Ok(Self::with(CassetteMode::Replay, path, interactions))
+    }
+
+    fn with(mode: CassetteMode, path: &str, interactions: Vec<Interaction>) -> Self {
+        let used = vec![false; interactions.len()];
+        Self {
+            mode,
+            path: path.to_string(),
+            interactions: Mutex::new(interactions),
+            used: Mutex::new(used),
+            unmatched: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// LLM_CASSETTE=<path> with LLM_CASSETTE_MODE=record|replay (default replay)
+    pub fn from_env() -> Result<Option<Self>, String> {
+        let Ok(path) = std::env::var("LLM_CASSETTE") else {
+            return Ok(None);
+        };
+        match std::env::var("LLM_CASSETTE_MODE").as_deref() {
+            Ok("record") => Ok(Some(Self::record(&path))),
+            Ok("replay") | Err(_) => Self::replay(&path).map(Some),
+            Ok(other) => Err(format!("Unknown LLM_CASSETTE_MODE '{}' (record | replay)", other)),
+        }
+    }
+
+    /// Route every provider call of this process through the cassette
+    pub fn install(self) -> Result<Arc<Cassette>, String> {
+        CASSETTE.set(Arc::new(self)).map_err(|_| "A cassette is already installed".to_string())?;
+        Ok(CASSETTE.get().unwrap().clone())
+    }
+
+    /// Route provider calls made inside `fut` (same task) through this cassette
+    pub async fn scope<F: Future>(self: Arc<Self>, fut: F) -> F::Output {
+        SCOPED.scope(self, fut).await
+    }
+
+    pub fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    /// Requests that found no recording (replay)
+    pub fn unmatched(&self) -> Vec<String> {
+        self.unmatched.lock().unwrap().clone()
+    }
+
+    /// Recordings that were never requested (replay)
+    pub fn unused(&self) -> usize {
+        self.used.lock().unwrap().iter().filter(|used| !**used).count()
+    }
+
+    /// Write recorded interactions to disk (no-op in replay)
+    pub fn save(&self) -> Result<(), String> {
+        if self.mode == CassetteMode::Replay {
+            return Ok(());
+        }
+        let interactions = self.interactions.lock().unwrap();
+        let json = serde_json::to_string_pretty(&*interactions).map_err(|e| e.to_string())?;
+        std::fs::write(&self.path, json).map_err(|e| format!("Failed to write cassette {}: {}", self.path, e))
+    }
+
+    /// First unused recording for the same URL whose request matches. Repeated
+    /// identical requests are served in recording order.
+    pub fn find(&self, url: &str, request: &Value) -> Option<(u16, Option<u64>, String)> {
+        let interactions = self.interactions.lock().unwrap();
+        let mut used = self.used.lock().unwrap();
+
+        let Some(index) = interactions
+            .iter()
+            .enumerate()
+            .position(|(i, it)| !used[i] && it.url == url && is_subset(&it.request, request))
+        else {
+            let model = request.get("model").and_then(Value::as_str).unwrap_or("-");
+            self.unmatched.lock().unwrap().push(format!("{} ({})", url, model));
+            return None;
+        };
+        used[index] = true;
+
+        let hit = &interactions[index];
+        Some((hit.status, hit.retry_after, hit.response.clone()))
+    }
+
+    /// Recording is written through, so a server killed mid-run keeps what it saw
+    pub fn store(&self, url: &str, request: &Value, status: u16, retry_after: Option<u64>, response: &str) {
+        self.interactions.lock().unwrap().push(Interaction {
+            url: url.to_string(),
+            request: request.clone(),
+            status,
+            retry_after,
+            response: response.to_string(),
+        });
+        if let Err(e) = self.save() {
+            eprintln!("⚠️  {}", e);
+        }
+    }
+}
+
+/// Scoped cassette of the current task, else the process-wide one
+pub(super) fn active() -> Option<Arc<Cassette>> {
+    SCOPED.try_with(Arc::clone).ok().or_else(|| CASSETTE.get().cloned())
+}
+
+/// Answers come from disk: no credentials needed, and no cross-request state
+/// (circuit breaker) may change which recordings get used
+pub(super) fn replaying() -> bool {
+    active().is_some_and(|c| c.mode() == CassetteMode::Replay)
+}
+
+/// Every key of `pattern` is in `value` with a matching value; non-objects compare equal
+fn is_subset(pattern: &Value, value: &Value) -> bool {
+    match (pattern, value) {
+        (Value::Object(pattern), Value::Object(value)) => pattern
+            .iter()
+            .all(|(key, p)| value.get(key).is_some_and(|v| is_subset(p, v))),
+        _ => pattern == value,
+    }
+}
+
+/// Cassette key for a URL: API keys live in the query string (Gemini), never record them
+pub(super) fn redact_url(url: &str) -> &str {
+    url.split('?').next().unwrap_or(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_replay_serves_in_order_once() {
+        let cassette = Cassette::with(
+            CassetteMode::Replay,
+            "unused.json",
+            vec![
+                Interaction { url: "u".into(), request: json!({"a": 1}), status: 429, retry_after: Some(5), response: "slow down".into() },
+                Interaction { url: "u".into(), request: json!({"a": 1}), status: 200, retry_after: None, response: "ok".into() },
+            ],
+        );
+
+        assert_eq!(cassette.find("u", &json!({"a": 1})), Some((429, Some(5), "slow down".to_string())));
+        assert_eq!(cassette.find("u", &json!({"a": 1})), Some((200, None, "ok".to_string())));
+        assert_eq!(cassette.find("u", &json!({"a": 1})), None);
+        assert_eq!(cassette.find("u", &json!({"a": 2})), None);
+        assert_eq!(cassette.unmatched().len(), 2);
+        assert_eq!(cassette.unused(), 0);
+    }
+
+    #[test]
+    fn test_subset_matching() {
+        let body = json!({"model": "m", "temperature": 0.2, "messages": [{"role": "user", "content": "hi"}]});
+
+        assert!(is_subset(&json!({"model": "m"}), &body));
+        assert!(is_subset(&body, &body));
+        assert!(!is_subset(&json!({"model": "other"}), &body));
+        assert!(!is_subset(&json!({"model": "m", "top_p": 0.9}), &body));
+        assert!(!is_subset(&json!({"messages": []}), &body));
+    }
+
+    #[test]
+    fn test_redact_url() {
+        assert_eq!(
+            redact_url("https://generativelanguage.googleapis.com/v1beta/models/m:generateContent?key=secret"),
+            "https://generativelanguage.googleapis.com/v1beta/models/m:generateContent"
+        );
+    }
+}
@@ -1,13 +1,14 @@
 // backend/src/parser/ai_extractor/context_builder.rs
 
-use chrono::{Duration, FixedOffset, Utc};
 use serde::Deserialize;
-use serde_json::json;
 use sqlx::PgPool;
 
 use super::schedule_oracle::ScheduleOracle;
-use super::parsing::{extract_groq_text, GroqResponse};
-use super::GROQ_TEXT_MODELS;
+use super::date_resolver::{resolve_deadline, MeetingLookup};
+use super::model_chain::{run_chain, LlmTask};
+use super::provider::LlmRequest;
+use super::parsing::now_wib;
+use super::templates::{render, PromptKind};
 
 /// Minimal context needed for main AI prompt
 #[derive(Debug, Clone)]
@@ -62,6 +63,7 @@ pub async fn build_context(
     ).await?;
     
     let course_hints = calculate_course_hints(
+        message,
         &ai_hints,
         schedule_oracle,
     );
@@ -171,7 +173,7 @@ async fn get_sender_history(pool: &PgPool, sender_id: &str) -> Result<SenderHist
         SELECT c.name as course_name, a.parallel_code, COUNT(*) as count
         FROM assignments a
         JOIN courses c ON a.course_id = c.id
-        WHERE a.sender_id = $1 AND a.parallel_code IS NOT NULL
+        WHERE a.sender_id = $1 AND a.parallel_code IS NOT NULL AND a.deleted_at IS NULL
         GROUP BY c.name, a.parallel_code
         ORDER BY count DESC
         LIMIT 10
@@ -237,113 +239,15 @@ async fn call_context_resolver_ai(
         .map(|ctx| format!("\n\nQUOTED MESSAGE CONTEXT:\n{}\n(User is replying to/referencing this message)", ctx))
         .unwrap_or_default();
     
-    let prompt = format!(
-        r#"Analyze this academic message and extract structured course information.
-
-MESSAGE: "{}"
-SENDER HISTORY: {}{}
-
-AVAILABLE COURSES:
-{}
-
-TASK: Identify courses mentioned and classify deadline information.
-
-COURSE IDENTIFICATION:
-• Match against AVAILABLE COURSES list (check both full names and aliases in [aka: ...])
-• Always use the FULL course name, not the alias
-• Assignment titles and project names are NOT courses
-• If QUOTED MESSAGE CONTEXT is present, use it to identify which assignment is being referenced
-• Return empty array if no valid courses identified
-
-PARALLEL CLASS (per course):
-• Valid values: k1, k2, k3, p1, p2, p3, r1, r2, r3, or null
-• Priority: explicit mention > quoted context > sender history > null
-• Each course independent (don't assume shared parallel)
-
-DEADLINE TYPE (per course):
-• "explicit": Specific date (2026-01-15, "5 Januari", "15 Desember")
-• "next_meeting": References next class ("sebelum pertemuan", "before class")
-• "relative": Relative time ("besok", "tomorrow", "minggu depan")
-• "unknown": Course mentioned without deadline
-
-GLOBAL PARALLEL:
-• Set only if ALL courses share identical parallel
-• Otherwise null
-
-USING QUOTED CONTEXT:
-• If message says "diundur" / "berubah" / "updated" and quotes a previous assignment, extract info from quoted context
-• Treat quoted assignment info as the reference point for updates
-
-Return JSON:
-{{
-  "parallel_code": string | null,
-  "parallel_confidence": float,
-  "parallel_source": "explicit" | "quoted_context" | "sender_history" | "unknown",
-  "course_hints": [
-    {{
-      "course_name": string,
-      "parallel_code": string | null,
-      "deadline_type": string
-    }}
-  ]
-}}"#,
-        message,
-        history_text,
-        quoted_section,  // ✅ Include quoted context
-        courses_list
+    let prompt = render(
+        PromptKind::ContextResolver,
+        &[("message", message), ("sender_history", &history_text), ("quoted_section", &quoted_section), ("courses", courses_list)],
     );
     
-    let api_key = std::env::var("GROQ_API_KEY")
-        .map_err(|_| "GROQ_API_KEY not set".to_string())?;
-    
-    for model in GROQ_TEXT_MODELS {
-        match call_groq_api(&api_key, model, &prompt).await {
-            Ok(json_text) => {
-                return parse_ai_hints(&json_text);
-            }
-            Err(e) => {
-                eprintln!("Context AI failed with {}: {}", model, e);
-                continue;
-            }
-        }
-    }
-    
-    Err("All context resolver models failed".to_string())
-}
-
-async fn call_groq_api(api_key: &str, model: &str, prompt: &str) -> Result<String, String> {
-    let url = "https://api.groq.com/openai/v1/chat/completions";
-    
-    let request_body = json!({
-        "model": model,
-        "messages": [{"role": "user", "content": prompt}],
-        "temperature": 0.1,
-        "max_tokens": 1000,
-        "response_format": {"type": "json_object"}
-    });
-    
-    let client = reqwest::Client::new();
-    let response = client
-        .post(url)
-        .header("Authorization", format!("Bearer {}", api_key))
-        .header("Content-Type", "application/json")
-        .json(&request_body)
-        .send()
+    let request = LlmRequest { prompt: &prompt.text, image_base64: None, json_mode: true, prompt_version: Some(&prompt.version) };
+    run_chain(LlmTask::Context, &request, parse_ai_hints)
         .await
-        .map_err(|e| format!("Request failed: {}", e))?;
-    
-    let status = response.status();
-    
-    if !status.is_success() {
-        let error_text = response.text().await
-            .unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!("API error: {} - {}", status, error_text));
-    }
-    
-    let groq_response: GroqResponse = response.json().await
-        .map_err(|e| format!("Parse error: {}", e))?;
-    
-    extract_groq_text(&groq_response)
+        .map_err(|e| format!("Context resolver failed: {}", e))
 }
 
 fn parse_ai_hints(json_text: &str) -> Result<AIHints, String> {
@@ -354,14 +258,13 @@ fn parse_ai_hints(json_text: &str) -> Result<AIHints, String> {
 // ===== DEADLINE CALCULATION (PER-COURSE) =====
 
 fn calculate_course_hints(
+    message: &str,
     hints: &AIHints,
     schedule_oracle: &ScheduleOracle,
 ) -> Vec<CourseHint> {
     let mut course_hints = Vec::new();
     
-    let gmt7 = FixedOffset::east_opt(7 * 3600).unwrap();
-    let now = Utc::now().with_timezone(&gmt7);
-    let today = now.date_naive();
+    let now = now_wib().naive_local();
     
     for ai_course_hint in &hints.course_hints {
         println!("│");
@@ -369,40 +272,41 @@ fn calculate_course_hints(
         println!("│    Parallel: {:?}", ai_course_hint.parallel_code);
         println!("│    Deadline Type: {}", ai_course_hint.deadline_type);
         
+        let meeting = ai_course_hint.parallel_code
+            .as_deref()
+            .filter(|p| !matches!(*p, "all" | "null" | ""))
+            .map(|parallel_code| MeetingLookup {
+                oracle: schedule_oracle,
+                course_name: &ai_course_hint.course_name,
+                parallel_code,
+            });
+        
         let deadline_hint = match ai_course_hint.deadline_type.as_str() {
-            "next_meeting" => {
-                let has_valid_parallel = ai_course_hint.parallel_code
-                    .as_ref()
-                    .map(|p| p != "all" && p != "null" && !p.is_empty())
-                    .unwrap_or(false);
-                
-                if !has_valid_parallel {
-                    println!("│    ⏭️  Result: Skipped (needs parallel for schedule)");
+            "explicit" | "relative" | "next_meeting" => {
+                if let Some(resolved) = resolve_deadline(message, now, meeting) {
+                    println!("│    ✅ Result: {} (\"{}\")", resolved.canonical(), resolved.phrase);
+                    Some(resolved.canonical())
+                } else if ai_course_hint.deadline_type != "next_meeting" {
+                    println!("│    📅 Result: No date phrase resolved (main AI will parse)");
                     None
-                } else {
-                    let parallel = ai_course_hint.parallel_code.as_ref().unwrap();
-                    
-                    if let Some((meeting_date, meeting_time)) = schedule_oracle
-                        .get_next_meeting_with_time(&ai_course_hint.course_name, parallel, today)
-                    {
-                        let hint = format!("{} {}", meeting_date, meeting_time);
-                        println!("│    ✅ Result: Next meeting at {}", hint);
-                        Some(hint)
-                    } else {
-                        println!("│    ⏭️  Result: No schedule found");
-                        None
+                } else if let Some(meeting) = meeting {
+                    // Meeting-relative wording the resolver doesn't know: ask the schedule directly
+                    match schedule_oracle.next_meeting_after(meeting.course_name, meeting.parallel_code, now) {
+                        Some((meeting_date, meeting_time)) => {
+                            let hint = format!("{} {}", meeting_date, meeting_time);
+                            println!("│    ✅ Result: Next meeting at {}", hint);
+                            Some(hint)
+                        }
+                        None => {
+                            println!("│    ⏭️  Result: No schedule found");
+                            None
+                        }
                     }
+                } else {
+                    println!("│    ⏭️  Result: Skipped (needs parallel for schedule)");
+                    None
                 }
             },
-            "relative" => {
-                let hint = format!("{} 23:59", today + Duration::days(1));
-                println!("│    ✅ Result: Tomorrow EOD ({})", hint);
-                Some(hint)
-            },
-            "explicit" => {
-                println!("│    📅 Result: Explicit date (main AI will parse)");
-                None
-            },
             _ => {
                 println!("│    ❓ Result: Unknown type (no hint generated)");
                 None
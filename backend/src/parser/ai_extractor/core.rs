@@ -1,16 +1,22 @@
 use crate::models::{AIClassification, Assignment};
 use uuid::Uuid;
-use serde_json::json;
 use std::collections::HashMap;
 use sqlx::PgPool;
 
 use super::schedule_oracle::ScheduleOracle;
+use super::date_resolver::{resolve_deadline, MeetingLookup};
 use once_cell::sync::Lazy;
 
 use super::prompts::*;
 use super::parsing::*;
-use super::{GROQ_REASONING_MODELS, GROQ_VISION_MODELS, GROQ_TEXT_MODELS, GEMINI_MODELS};
+use super::model_chain::{run_chain, run_chain_with_repair, LlmTask};
+use super::schema::validate_classification;
+use super::provider::LlmRequest;
 use super::context_builder::build_context;  // Fixes build_context error
+use super::rule_based::extract_rule_based;
+use crate::database::crud::{get_all_courses, get_assignment_vectors, get_term_document_frequencies, upsert_assignment_vector};
+use crate::similarity::{self, Corpus, TermVector, Verdict};
+use crate::ocr::with_image_text;
 
 
 pub static SCHEDULE_ORACLE: Lazy<ScheduleOracle> = Lazy::new(|| {
@@ -21,12 +27,14 @@ pub static SCHEDULE_ORACLE: Lazy<ScheduleOracle> = Lazy::new(|| {
 
 // ===== MAIN AI EXTRACTION FUNCTION =====
 
+#[allow(clippy::too_many_arguments)]
 pub async fn extract_with_ai(
     text: &str,
     available_courses: &str,
     active_assignments: &[Assignment],
     course_map: &HashMap<Uuid, String>,
     image_base64: Option<&str>,
+    image_text: Option<&str>,
     sender_id: &str,
     pool: &PgPool,
     quoted_message: Option<&str>,  
@@ -47,7 +55,7 @@ pub async fn extract_with_ai(
         text, 
         sender_id, 
         pool, 
-        &*SCHEDULE_ORACLE,
+        &SCHEDULE_ORACLE,
         quoted_message  
     ).await {
         Ok(ctx) => {
@@ -85,6 +93,7 @@ pub async fn extract_with_ai(
     // Single prompt with optional context
     let prompt = build_classification_prompt(
         text, 
+        image_text,
         available_courses, 
         active_assignments,
         course_map, 
@@ -98,396 +107,65 @@ pub async fn extract_with_ai(
     if image_base64.is_some() {
         println!("│ 🖼️  Image    : Attached (may be irrelevant meme)");
     }
+    if let Some(image_text) = image_text {
+        println!("│ 🔤 OCR      : {} chars \x1b[36m\"{}\"\x1b[0m", image_text.chars().count(), truncate_for_log(image_text, 50));
+    }
     println!("│ 📊 Context  : {} active assignments", active_assignments.len());
     println!("│ 📅 Time     : {}", current_datetime);
+    println!("│ 📝 Prompt   : {}", prompt.version);
     
-    // [Rest of the function remains the same...]
+    let text_request = LlmRequest { prompt: &prompt.text, image_base64: None, json_mode: true, prompt_version: Some(&prompt.version) };
+    let course_names: Vec<&str> = course_map.values().map(String::as_str).collect();
+    let parse_extraction = |ai_text: &str| validate_classification(ai_text, &course_names);
+    // Deadline pinning and the rule-based tier read the image's text too
+    let full_text = with_image_text(text, image_text);
+
     // TIER 1: Try vision model if image present
     if let Some(img) = image_base64 {
-        match try_groq_vision(&prompt, img).await {
-            Ok(classification) => {
-                match classification {
-                    AIClassification::Unrecognized => {
-                        println!("│ ℹ️  Vision Result: Unrecognized (image likely irrelevant)");
-                        println!("│ 🔄 Retrying with text-only analysis...");
-                        
-                        match try_groq_reasoning(&prompt).await {
-                            Ok(text_result) => {
-                                match text_result {
-                                    AIClassification::Unrecognized => {
-                                        println!("│ ⚠️  Text-only: Still unrecognized");
-                                        println!("\x1b[1;30m└──────────────────────────────────────────────\x1b[0m");
-                                        return Ok(AIClassification::Unrecognized);
-                                    }
-                                    _ => {
-                                        log_classification_success(&text_result);
-                                        println!("\x1b[1;30m└──────────────────────────────────────────────\x1b[0m");
-                                        return Ok(text_result);
-                                    }
-                                }
-                            }
-                            Err(e) => {
-                                eprintln!("│ ⚠️  Text fallback failed: {}", e);
-                            }
-                        }
-                    }
-                    _ => {
-                        log_classification_success(&classification);
-                        println!("\x1b[1;30m└──────────────────────────────────────────────\x1b[0m");
-                        return Ok(classification);
-                    }
-                }
-            }
-            Err(e) => {
-                eprintln!("│ ⚠️  Vision model error: {}", e);
-                println!("│ 🔄 Trying text-only...");
-                
-                match try_groq_reasoning(&prompt).await {
-                    Ok(classification) => {
-                        log_classification_success(&classification);
-                        println!("\x1b[1;30m└──────────────────────────────────────────────\x1b[0m");
-                        return Ok(classification);
-                    }
-                    Err(e) => {
-                        eprintln!("│ ⚠️  Text fallback failed: {}", e);
-                    }
-                }
-            }
-        }
-    } else {
-        match try_groq_reasoning(&prompt).await {
-            Ok(classification) => {
-                log_classification_success(&classification);
-                println!("\x1b[1;30m└──────────────────────────────────────────────\x1b[0m");
-                return Ok(classification);
-            }
-            Err(e) => {
-                eprintln!("│ ⚠️  Groq Reasoning failed: {}", e);
-                eprintln!("│ 🔄 Falling back to Gemini...");
+        let vision_request = LlmRequest { image_base64: Some(img), ..text_request };
+
+        match run_chain_with_repair(LlmTask::Vision, &vision_request, parse_extraction).await {
+            Ok(AIClassification::Unrecognized) => {
+                println!("│ ℹ️  Vision Result: Unrecognized (image likely irrelevant)");
+                println!("│ 🔄 Retrying with text-only analysis...");
             }
-        }
-    }
-    
-    // TIER 2: Gemini fallback
-    for (index, model) in GEMINI_MODELS.iter().enumerate() {
-        println!("│ 🔄 Model    : {} (Gemini Fallback {}/{})", model, index + 1, GEMINI_MODELS.len());
-        
-        match try_gemini_model(model, &prompt).await {
-            Ok(classification) => {
+            Ok(mut classification) => {
+                pin_deadline(&full_text, &mut classification);
                 log_classification_success(&classification);
                 println!("\x1b[1;30m└──────────────────────────────────────────────\x1b[0m");
                 return Ok(classification);
             }
             Err(e) => {
-                eprintln!("│ ❌ Failed   : {}", e);
-                if index == GEMINI_MODELS.len() - 1 {
-                    println!("\x1b[1;30m└──────────────────────────────────────────────\x1b[0m");
-                    return Err("All models failed".to_string());
-                }
-            }
-        }
-    }
-    
-    println!("\x1b[1;30m└──────────────────────────────────────────────\x1b[0m");
-    Err("No models available".to_string())
-}
-
-// ===== GROQ REASONING MODELS (PRIORITY) =====
-
-async fn try_groq_reasoning(prompt: &str) -> Result<AIClassification, String> {
-    let api_key = std::env::var("GROQ_API_KEY")
-        .map_err(|_| "GROQ_API_KEY not set in .env".to_string())?;
-    
-    for (index, model) in GROQ_REASONING_MODELS.iter().enumerate() {
-        println!("│ 🔄 Model    : {} (Reasoning {}/{})", model, index + 1, GROQ_REASONING_MODELS.len());
-        
-        let url = "https://api.groq.com/openai/v1/chat/completions";
-        
-        let request_body = json!({
-            "model": model,
-            "messages": [
-                {
-                    "role": "user",
-                    "content": prompt
-                }
-            ],
-            "temperature": 0.6,  // Reasoning models work better at 0.5-0.7
-            "top_p": 0.95,
-            "max_completion_tokens": 8192,
-            "response_format": { "type": "json_object" }
-        });
-        
-        let client = reqwest::Client::new();
-        let response = match client
-            .post(url)
-            .header("Authorization", format!("Bearer {}", api_key))
-            .header("Content-Type", "application/json")
-            .json(&request_body)
-            .send()
-            .await
-        {
-            Ok(r) => r,
-            Err(e) => {
-                eprintln!("│ \x1b[31m❌ REQUEST FAILED\x1b[0m : {}", e);
-                continue;
-            }
-        };
-        
-        let status = response.status();
-        
-        if status.is_success() {
-            println!("│ \x1b[32m✅ SUCCESS\x1b[0m  : Groq Reasoning response");
-            
-            let groq_response: GroqResponse = response.json().await
-                .map_err(|e| format!("Failed to deserialize: {}", e))?;
-            
-            let ai_text = extract_groq_text(&groq_response)?;
-            //println!("│ 📄 Result   : {}", truncate_for_log(&ai_text, 60));
-            
-            let classification = parse_classification(&ai_text)?;
-            
-            if matches!(classification, AIClassification::Unrecognized) && !ai_text.contains("unrecognized") {
-                eprintln!("│ ⚠️  Invalid JSON from Groq, trying next model");
-                continue;
-            }
-            
-            return Ok(classification);
-        }
-        
-        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-            eprintln!("│ ⚠️  RATE LIMIT : {}", model);
-            if index < GROQ_REASONING_MODELS.len() - 1 {
-                continue;
-            } else {
-                eprintln!("│ 🔄 Reasoning models exhausted, trying standard models...");
-                return try_groq_standard_text(prompt).await;
+                eprintln!("│ ⚠️  Vision model error: {}", e);
+                println!("│ 🔄 Trying text-only...");
             }
         }
-        
-        let error_text = response.text().await
-            .unwrap_or_else(|_| "Unknown error".to_string());
-        eprintln!("│ ❌ ERROR    : {} - {}", status, truncate_for_log(&error_text, 60));
-        
-        if index < GROQ_REASONING_MODELS.len() - 1 {
-            continue;
-        }
     }
-    
-    eprintln!("│ 🔄 All reasoning models failed, trying standard models...");
-    try_groq_standard_text(prompt).await
-}
 
-// ===== GROQ STANDARD TEXT MODELS (FALLBACK) =====
+    // TIER 2: Text chain (Groq reasoning → Groq standard → Gemini by default)
+    let mut result = match run_chain_with_repair(LlmTask::Extraction, &text_request, parse_extraction).await {
+        Ok(classification) => Ok(classification),
+        Err(e) => {
+            eprintln!("│ ❌ Failed   : {}", e);
 
-async fn try_groq_standard_text(prompt: &str) -> Result<AIClassification, String> {
-    let api_key = std::env::var("GROQ_API_KEY")
-        .map_err(|_| "GROQ_API_KEY not set in .env".to_string())?;
-    
-    for (index, model) in GROQ_TEXT_MODELS.iter().enumerate() {
-        println!("│ 🔄 Model    : {} (Standard {}/{})", model, index + 1, GROQ_TEXT_MODELS.len());
-        
-        let url = "https://api.groq.com/openai/v1/chat/completions";
-        
-        let request_body = json!({
-            "model": model,
-            "messages": [{"role": "user", "content": prompt}],
-            "temperature": 0.2,
-            "max_tokens": 4096,
-            "response_format": { "type": "json_object" }
-        });
-        
-        let client = reqwest::Client::new();
-        let response = match client.post(url)
-            .header("Authorization", format!("Bearer {}", api_key))
-            .header("Content-Type", "application/json")
-            .json(&request_body)
-            .send()
-            .await
-        {
-            Ok(r) => r,
-            Err(e) => {
-                eprintln!("│ \x1b[31m❌ REQUEST FAILED\x1b[0m : {}", e);
-                continue;
-            }
-        };
-        
-        let status = response.status();
-        
-        if status.is_success() {
-            println!("│ \x1b[33m⚠️  STANDARD\x1b[0m : Using non-reasoning model");
-            
-            let groq_response: GroqResponse = response.json().await
-                .map_err(|e| format!("Failed to deserialize: {}", e))?;
-            
-            let ai_text = extract_groq_text(&groq_response)?;
-            //println!("│ 📄 Result   : {}", truncate_for_log(&ai_text, 60));
-            
-            let classification = parse_classification(&ai_text)?;
-            
-            if matches!(classification, AIClassification::Unrecognized) && !ai_text.contains("unrecognized") {
-                eprintln!("│ ⚠️  Invalid JSON, trying next model");
-                continue;
-            }
-            
-            return Ok(classification);
+            // TIER 3: No model answered → rules only, flagged low confidence
+            println!("│ 📏 Tier 3   : Rule-based extraction (no AI)");
+            let courses = get_all_courses(pool).await.map_err(|db| format!("{} (courses: {})", e, db))?;
+            let now = get_current_datetime_naive();
+            Ok(extract_rule_based(&full_text, &courses, now))
         }
-        
-        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-            eprintln!("│ ⚠️  RATE LIMIT : {}", model);
-            if index < GROQ_TEXT_MODELS.len() - 1 {
-                continue;
-            } else {
-                return Err("All Groq standard models rate limited".to_string());
-            }
-        }
-        
-        let error_text = response.text().await
-            .unwrap_or_else(|_| "Unknown error".to_string());
-        eprintln!("│ ❌ ERROR    : {} - {}", status, truncate_for_log(&error_text, 60));
-        
-        if index < GROQ_TEXT_MODELS.len() - 1 {
-            continue;
-        }
-    }
-    
-    Err("All Groq standard models failed".to_string())
-}
-
-// ===== GROQ VISION MODELS =====
+    };
 
-async fn try_groq_vision(prompt: &str, image_base64: &str) -> Result<AIClassification, String> {
-    let api_key = std::env::var("GROQ_API_KEY")
-        .map_err(|_| "GROQ_API_KEY not set in .env".to_string())?;
-    
-    for (index, model) in GROQ_VISION_MODELS.iter().enumerate() {
-        println!("│ 🔄 Model    : {} (Vision {}/{})", model, index + 1, GROQ_VISION_MODELS.len());
-        
-        let url = "https://api.groq.com/openai/v1/chat/completions";
-        
-        let request_body = json!({
-            "model": model,
-            "messages": [{
-                "role": "user",
-                "content": [
-                    {"type": "text", "text": prompt},
-                    {
-                        "type": "image_url",
-                        "image_url": {
-                            "url": format!("data:image/jpeg;base64,{}", image_base64)
-                        }
-                    }
-                ]
-            }],
-            "temperature": 0.2,
-            "max_tokens": 4096,
-            "response_format": { "type": "json_object" }
-        });
-        
-        let client = reqwest::Client::new();
-        let response = match client.post(url)
-            .header("Authorization", format!("Bearer {}", api_key))
-            .header("Content-Type", "application/json")
-            .json(&request_body)
-            .send()
-            .await
-        {
-            Ok(r) => r,
-            Err(e) => {
-                eprintln!("│ \x1b[31m❌ REQUEST FAILED\x1b[0m : {}", e);
-                continue;
-            }
-        };
-        
-        let status = response.status();
-        
-        if status.is_success() {
-            println!("│ \x1b[32m✅ SUCCESS\x1b[0m  : Groq Vision response");
-            
-            let groq_response: GroqResponse = response.json().await
-                .map_err(|e| format!("Failed to deserialize: {}", e))?;
-            
-            let ai_text = extract_groq_text(&groq_response)?;
-            //println!("│ 📄 Result   : {}", truncate_for_log(&ai_text, 60));
-            
-            let classification = parse_classification(&ai_text)?;
-            
-            if matches!(classification, AIClassification::Unrecognized) && !ai_text.contains("unrecognized") {
-                eprintln!("│ ⚠️  Invalid JSON from Groq, trying next model");
-                continue;
-            }
-            
-            return Ok(classification);
-        }
-        
-        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-            eprintln!("│ ⚠️  RATE LIMIT : {}", model);
-            if index < GROQ_VISION_MODELS.len() - 1 {
-                continue;
-            } else {
-                return Err("All Groq vision models rate limited".to_string());
-            }
-        }
-        
-        let error_text = response.text().await
-            .unwrap_or_else(|_| "Unknown error".to_string());
-        eprintln!("│ ❌ ERROR    : {} - {}", status, truncate_for_log(&error_text, 60));
-        
-        if index < GROQ_VISION_MODELS.len() - 1 {
-            continue;
-        }
+    if let Ok(classification) = &mut result {
+        pin_deadline(&full_text, classification);
+        log_classification_success(classification);
     }
-    
-    Err("All Groq vision models failed".to_string())
-}
 
-// ===== GEMINI FALLBACK =====
-
-async fn try_gemini_model(model: &str, prompt: &str) -> Result<AIClassification, String> {
-    let api_key = std::env::var("GEMINI_API_KEY")
-        .map_err(|_| "GEMINI_API_KEY not set in .env".to_string())?;
-    
-    let url = format!(
-        "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
-        model, api_key
-    );
-    
-    let request_body = json!({
-        "contents": [{"parts": [{"text": prompt}]}],
-        "generationConfig": {
-            "temperature": 0.2,
-            "maxOutputTokens": 4096,
-            "responseMimeType": "application/json"
-        }
-    });
-    
-    let client = reqwest::Client::new();
-    let response = client.post(&url).json(&request_body).send().await
-        .map_err(|e| format!("Request failed: {}", e))?;
-    
-    let status = response.status();
-    
-    if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-        return Err("Rate limited".to_string());
-    }
-    
-    if !status.is_success() {
-        let error_text = response.text().await
-            .unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!("Status {}: {}", status, truncate_for_log(&error_text, 60)));
-    }
-    
-    println!("│ \x1b[32m✅ SUCCESS\x1b[0m    : Gemini response");
-    
-    let gemini_response: GeminiResponse = response.json().await
-        .map_err(|e| format!("Failed to deserialize: {}", e))?;
-    
-    let ai_text = extract_ai_text(&gemini_response)?;
-    //println!("│ 📄 Result   : {}", truncate_for_log(ai_text, 60));
-    
-    parse_classification(ai_text)
+    println!("\x1b[1;30m└──────────────────────────────────────────────\x1b[0m");
+    result
 }
 
-// ===== MATCHING (GEMINI ONLY) =====
+// ===== MATCHING =====
 
 pub async fn match_update_to_assignment(
     changes: &str,
@@ -496,83 +174,29 @@ pub async fn match_update_to_assignment(
     course_map: &HashMap<Uuid, String>,
     parallel_code: Option<&str>,
 ) -> Result<Option<Uuid>, String> {
-    let api_key = std::env::var("GEMINI_API_KEY")
-        .map_err(|_| "GEMINI_API_KEY not set in .env".to_string())?;
-    
     let prompt = build_matching_prompt(changes, keywords, active_assignments, course_map, parallel_code);
     
-    println!("\x1b[1;30m┌── 🤖 AI MATCHING (GEMINI ONLY) ─────────────\x1b[0m");
+    println!("\x1b[1;30m┌── 🤖 AI MATCHING ────────────────────────────\x1b[0m");
     println!("│ 🔍 Keywords   : {:?}", keywords);
     if let Some(pc) = parallel_code {
         println!("│ 🧩 Parallel   : {}", pc);
     }
     
-    for (index, model) in GEMINI_MODELS.iter().enumerate() {
-        println!("│ 🔄 Model      : {} (Attempt {}/{})", model, index + 1, GEMINI_MODELS.len());
-        
-        let url = format!(
-            "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
-            model, api_key
-        );
-        
-        let request_body = json!({
-            "contents": [{"parts": [{"text": prompt}]}],
-            "generationConfig": {
-                "temperature": 0.2,
-                "maxOutputTokens": 4096,
-                "responseMimeType": "application/json"
-            }
-        });
-        
-        let client = reqwest::Client::new();
-        let response = match client.post(&url).json(&request_body).send().await {
-            Ok(r) => r,
-            Err(e) => {
-                eprintln!("│ ❌ Failed   : {}", e);
-                continue;
-            }
-        };
-        
-        let status = response.status();
-        
-        if status.is_success() {
-            println!("│ \x1b[32m✅ SUCCESS\x1b[0m    : Match analysis complete");
-            
-            let gemini_response: GeminiResponse = response.json().await
-                .map_err(|e| e.to_string())?;
-            let ai_text = extract_ai_text(&gemini_response)?;
-            
-            let result = parse_match_result(ai_text)?;
-            println!("\x1b[1;30m└──────────────────────────────────────────────\x1b[0m");
-            
-            return Ok(result);
-        }
-        
-        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-            eprintln!("│ ⚠️  RATE LIMIT : {}", model);
-            if index < GEMINI_MODELS.len() - 1 {
-                continue;
-            } else {
-                println!("\x1b[1;30m└──────────────────────────────────────────────\x1b[0m");
-                return Err("All Gemini models rate limited for matching.".to_string());
-            }
-        }
-        
-        if index < GEMINI_MODELS.len() - 1 {
-            continue;
-        } else {
-            println!("\x1b[1;30m└──────────────────────────────────────────────\x1b[0m");
-            return Err(format!("AI matching failed with all models: {}", status));
-        }
-    }
+    let request = LlmRequest { prompt: &prompt.text, image_base64: None, json_mode: true, prompt_version: Some(&prompt.version) };
+    let result = run_chain(LlmTask::Matching, &request, parse_match_result).await;
     
+    if result.is_ok() {
+        println!("│ \x1b[32m✅ SUCCESS\x1b[0m    : Match analysis complete");
+    }
     println!("\x1b[1;30m└──────────────────────────────────────────────\x1b[0m");
-    Err("No models available for matching".to_string())
+    
+    result
 }
 
 // ===== DEDUPLICATION AI =====
 
-/// Check if a new assignment is a duplicate (STRICT logic with better filtering)
+/// Check if a new assignment is a duplicate: local TF-IDF similarity decides
+/// clear cases, only ambiguous candidates (top 3) go to the LLM
 pub async fn check_duplicate_assignment(
     title: &str,
     description: &str,
@@ -580,12 +204,11 @@ pub async fn check_duplicate_assignment(
     parallel_code: Option<&str>,
     existing_assignments: &[Assignment],
     course_map: &HashMap<Uuid, String>,
+    pool: &PgPool,
 ) -> Result<Option<Uuid>, String> {
     
     // ===== PRE-FILTERING (keep quiet) =====
-    let new_numbers = extract_numbers(title);
-    let new_type = extract_assignment_type(title);
-    
+    // Numbers and assignment types are compared by the similarity score
     let filtered: Vec<&Assignment> = existing_assignments
         .iter()
         .filter(|a| {
@@ -600,20 +223,6 @@ pub async fn check_duplicate_assignment(
                 if !new_p.eq_ignore_ascii_case(existing_p) { return false; }
             }
             
-            let existing_numbers = extract_numbers(&a.title);
-            if !new_numbers.is_empty() && !existing_numbers.is_empty() {
-                if new_numbers != existing_numbers { return false; }
-            }
-            
-            if let Some(ref new_t) = new_type {
-                if let Some(existing_t) = extract_assignment_type(&a.title) {
-                    if new_t != &existing_t { return false; }
-                }
-            }
-            
-            let similarity = calculate_word_overlap(title, &a.title);
-            if similarity < 0.2 { return false; }
-            
             true
         })
         .collect();
@@ -622,15 +231,32 @@ pub async fn check_duplicate_assignment(
         return Ok(None);
     }
     
-    if filtered.len() > 3 {
-        return Ok(None); // Too ambiguous
-    }
+    // ===== LOCAL SIMILARITY (clear cases need no LLM) =====
+    let new_vector = similarity::term_vector(title, description);
+    let candidates = candidate_vectors(pool, &filtered).await;
+    let corpus = idf_corpus(pool, &new_vector, &candidates).await;
+    let title_of = |id: Uuid| filtered.iter().find(|a| a.id == id).map(|a| a.title.as_str()).unwrap_or("?");
     
-    // ===== AI CHECK (clean output) =====
-    let api_key = std::env::var("GEMINI_API_KEY")
-        .map_err(|_| "GEMINI_API_KEY not set".to_string())?;
+    let ambiguous = match similarity::judge(&new_vector, &candidates, &corpus) {
+        Verdict::Duplicate { id, score } => {
+            println!("🔍 Duplicate detected: {} ≈ {} (similarity {:.2})", title, title_of(id), score);
+            return Ok(Some(id));
+        }
+        Verdict::Distinct { best } => {
+            println!("🧮 Similarity: {} is new (best {:.2} of {} candidates)", title, best, candidates.len());
+            return Ok(None);
+        }
+        Verdict::Ambiguous(top) => top,
+    };
+    
+    let scores: Vec<String> = ambiguous.iter().map(|(id, score)| format!("{} {:.2}", title_of(*id), score)).collect();
+    println!("🧮 Similarity: {} is ambiguous ({}) — asking the model", title, scores.join(", "));
     
-    let filtered_owned: Vec<Assignment> = filtered.into_iter().cloned().collect();
+    // ===== AI CHECK (clean output) =====
+    let filtered_owned: Vec<Assignment> = ambiguous
+        .iter()
+        .filter_map(|(id, _)| filtered.iter().find(|a| a.id == *id).map(|a| (*a).clone()))
+        .collect();
     let prompt = build_duplicate_detection_prompt(
         title,
         description,
@@ -640,68 +266,113 @@ pub async fn check_duplicate_assignment(
         course_map,
     );
     
-    for (index, model) in GEMINI_MODELS.iter().enumerate() {
-        let url = format!(
-            "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
-            model, api_key
-        );
-        
-        let request_body = serde_json::json!({
-            "contents": [{"parts": [{"text": prompt}]}],
-            "generationConfig": {
-                "temperature": 0.0,
-                "maxOutputTokens": 1024,
-                "responseMimeType": "application/json"
+    let request = LlmRequest { prompt: &prompt.text, image_base64: None, json_mode: true, prompt_version: Some(&prompt.version) };
+    let result: DuplicateCheckResult = run_chain(LlmTask::Dedup, &request, |ai_text| {
+        serde_json::from_str(ai_text).map_err(|e| format!("JSON error: {}", e))
+    })
+    .await?;
+    
+    // Only return if high confidence
+    if result.is_duplicate && result.confidence == "high" {
+        if let Some(id_str) = result.matched_assignment_id {
+            if let Ok(uuid) = Uuid::parse_str(&id_str) {
+                println!("🔍 Duplicate detected: {} - Reason: {}", title, result.reason);
+                return Ok(Some(uuid));
             }
-        });
-        
-        let client = reqwest::Client::new();
-        let response = match client.post(&url).json(&request_body).send().await {
-            Ok(r) => r,
-            Err(_) => continue,
-        };
-        
-        let status = response.status();
-        
-        if status.is_success() {
-            let gemini_response: super::parsing::GeminiResponse = response.json().await
-                .map_err(|e| format!("Parse error: {}", e))?;
-            
-            let ai_text = extract_ai_text(&gemini_response)?;
-            
-            let result: DuplicateCheckResult = serde_json::from_str(ai_text)
-                .map_err(|e| format!("JSON error: {}", e))?;
-            
-            // Only return if high confidence
-            if result.is_duplicate && result.confidence == "high" {
-                if let Some(id_str) = result.matched_assignment_id {
-                    if let Ok(uuid) = Uuid::parse_str(&id_str) {
-                        println!("🔍 Duplicate detected: {} - Reason: {}", title, result.reason);
-                        return Ok(Some(uuid));
+        }
+    } else if result.is_duplicate {
+        println!("⚠️  Low confidence duplicate: {} - Reason: {}", title, result.reason);
+    }
+    
+    Ok(None)
+}
+
+/// Stored vectors for the candidates; missing or outdated ones are computed (and stored)
+async fn candidate_vectors(pool: &PgPool, candidates: &[&Assignment]) -> Vec<(Uuid, TermVector)> {
+    let ids: Vec<Uuid> = candidates.iter().map(|a| a.id).collect();
+    let stored = match get_assignment_vectors(pool, &ids).await {
+        Ok(stored) => Some(stored),
+        Err(e) => {
+            eprintln!("⚠️  Assignment vectors unavailable ({}), computing locally", e);
+            None
+        }
+    };
+    
+    let mut vectors = Vec::with_capacity(candidates.len());
+    for assignment in candidates {
+        let vector = match stored.as_ref().and_then(|s| s.get(&assignment.id)) {
+            Some(vector) => vector.clone(),
+            None => {
+                let vector = similarity::term_vector(&assignment.title, &assignment.description);
+                // Backfill assignments created before vectors existed
+                if stored.is_some() {
+                    if let Err(e) = upsert_assignment_vector(pool, assignment.id, &vector).await {
+                        eprintln!("⚠️  Failed to store assignment vector: {}", e);
                     }
                 }
-            } else if result.is_duplicate {
-                println!("⚠️  Low confidence duplicate: {} - Reason: {}", title, result.reason);
+                vector
             }
-            
-            return Ok(None);
-        }
-        
-        if status == reqwest::StatusCode::TOO_MANY_REQUESTS 
-            && index < GEMINI_MODELS.len() - 1 {
-            continue;
-        }
-        
-        if index == GEMINI_MODELS.len() - 1 {
-            return Err("All models failed".to_string());
-        }
+        };
+        vectors.push((assignment.id, vector));
     }
+    vectors
+}
+
+/// IDF over every stored assignment; just the vectors at hand if the database is unreachable
+async fn idf_corpus(pool: &PgPool, new_vector: &TermVector, candidates: &[(Uuid, TermVector)]) -> Corpus {
+    let mut terms: Vec<String> = new_vector
+        .keys()
+        .chain(candidates.iter().flat_map(|(_, v)| v.keys()))
+        .cloned()
+        .collect();
+    terms.sort();
+    terms.dedup();
     
-    Err("No models available".to_string())
+    get_term_document_frequencies(pool, &terms)
+        .await
+        .unwrap_or_else(|_| Corpus::from_vectors(std::iter::once(new_vector).chain(candidates.iter().map(|(_, v)| v))))
 }
 
 // ===== HELPERS =====
 
+/// Date arithmetic is done here, not by the model: a deadline phrase the
+/// resolver understands ("deadline besok jam 8", "dikumpulkan Jumat depan")
+/// replaces the model's date; an unmarked date phrase only when it agrees
+fn pin_deadline(text: &str, classification: &mut AIClassification) {
+    let (deadline, course_name, parallel_code) = match classification {
+        AIClassification::AssignmentInfo { deadline, course_name, parallel_code, .. } => {
+            (deadline, course_name.as_deref(), parallel_code.as_deref())
+        }
+        // Only when the update is about the deadline at all
+        AIClassification::AssignmentUpdate { new_deadline: deadline @ Some(_), parallel_code, .. } => {
+            (deadline, None, parallel_code.as_deref())
+        }
+        _ => return,
+    };
+
+    let meeting = course_name
+        .zip(parallel_code.filter(|p| *p != "all"))
+        .map(|(course_name, parallel_code)| MeetingLookup { oracle: &SCHEDULE_ORACLE, course_name, parallel_code });
+
+    let Some(resolved) = resolve_deadline(text, get_current_datetime_naive(), meeting) else {
+        return;
+    };
+    let canonical = resolved.canonical();
+    if !resolved.overrides(deadline.as_deref()) {
+        println!("│ 📅 Deadline : kept {} (\"{}\" is not marked as the deadline)", deadline.as_deref().unwrap_or("none"), resolved.phrase);
+        return;
+    }
+    if deadline.as_deref() != Some(canonical.as_str()) {
+        println!(
+            "│ 📅 Deadline : {} from \"{}\" (model: {})",
+            canonical,
+            resolved.phrase,
+            deadline.as_deref().unwrap_or("none")
+        );
+        *deadline = Some(canonical);
+    }
+}
+
 fn log_classification_success(classification: &AIClassification) {
     match classification {
         AIClassification::MultipleAssignments { assignments, .. } => {
@@ -0,0 +1,543 @@
+// backend/src/parser/ai_extractor/date_resolver.rs
+//
+// Deadline phrases resolved in Rust instead of by the model: "besok jam 8
+// pagi", "Jumat depan", "minggu depan", "akhir bulan", "25 Oktober 23.59",
+// "before next class", ... against a WIB `now`. Meeting-relative phrases
+// ("sebelum pertemuan berikutnya") go through the ScheduleOracle. The output
+// is the canonical "YYYY-MM-DD HH:MM" that `crud::parse_deadline` reads.
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+
+use super::schedule_oracle::ScheduleOracle;
+
+const MONTHS: &[(&str, u32)] = &[
+    ("januari", 1), ("january", 1), ("jan", 1), ("februari", 2), ("february", 2), ("feb", 2),
+    ("maret", 3), ("march", 3), ("mar", 3), ("april", 4), ("apr", 4), ("mei", 5), ("may", 5),
+    ("juni", 6), ("june", 6), ("jun", 6), ("juli", 7), ("july", 7), ("jul", 7),
+    ("agustus", 8), ("august", 8), ("agu", 8), ("agt", 8), ("aug", 8),
+    ("september", 9), ("sept", 9), ("sep", 9), ("oktober", 10), ("october", 10), ("okt", 10), ("oct", 10),
+    ("november", 11), ("nov", 11), ("desember", 12), ("december", 12), ("des", 12), ("dec", 12),
+];
+
+const NUMBER_WORDS: &[(&str, i64)] = &[
+    ("satu", 1), ("se", 1), ("a", 1), ("one", 1), ("dua", 2), ("two", 2), ("tiga", 3), ("three", 3),
+    ("empat", 4), ("four", 4), ("lima", 5), ("five", 5), ("enam", 6), ("six", 6), ("tujuh", 7), ("seven", 7),
+];
+
+/// Which class "sebelum pertemuan berikutnya" refers to
+#[derive(Clone, Copy)]
+pub struct MeetingLookup<'a> {
+    pub oracle: &'a ScheduleOracle,
+    pub course_name: &'a str,
+    pub parallel_code: &'a str,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedDeadline {
+    /// WIB
+    pub at: NaiveDateTime,
+    /// The words it came from, for logs
+    pub phrase: String,
+    /// Right after a deadline cue ("deadline", "dikumpulkan", "paling lambat", "due", "sebelum")
+    pub cued: bool,
+}
+
+impl ResolvedDeadline {
+    /// "YYYY-MM-DD HH:MM", the format `crud::parse_deadline` expects
+    pub fn canonical(&self) -> String {
+        self.at.format("%Y-%m-%d %H:%M").to_string()
+    }
+
+    /// Whether this should replace the model's deadline: always when cued; otherwise the
+    /// phrase may be about something else ("dibuka besok jam 8"), so only when the model
+    /// put the deadline on the same day
+    pub fn overrides(&self, model_deadline: Option<&str>) -> bool {
+        self.cued
+            || model_deadline
+                .and_then(|d| NaiveDateTime::parse_from_str(d, "%Y-%m-%d %H:%M").ok())
+                .is_some_and(|d| d.date() == self.at.date())
+    }
+}
+
+/// How many words before a date a deadline cue may stand ("dikumpulkan paling lambat hari Jumat")
+const CUE_WINDOW: usize = 4;
+
+fn is_deadline_cue(token: &str) -> bool {
+    matches!(token, "deadline" | "dl" | "due" | "tenggat" | "batas" | "lambat" | "sebelum" | "before" | "maks" | "maksimal")
+        || ["kumpul", "dikumpul", "pengumpul", "submit"].iter().any(|p| token.starts_with(p))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DateRef {
+    On(NaiveDate),
+    NextMeeting,
+}
+
+/// Deadline written in `text`, relative to `now` (WIB). No time given → 23:59.
+/// `None` when there is no date, or two phrases point at different days
+/// (then the model's reading is kept).
+pub fn resolve_deadline(text: &str, now: NaiveDateTime, meeting: Option<MeetingLookup>) -> Option<ResolvedDeadline> {
+    let lower = text.to_lowercase();
+    let tokens: Vec<&str> = lower
+        .split_whitespace()
+        .map(|t| t.trim_matches(|c: char| !c.is_alphanumeric()))
+        .filter(|t| !t.is_empty())
+        .collect();
+    let today = now.date();
+
+    let mut dates: Vec<(NaiveDate, Option<NaiveTime>, String)> = Vec::new();
+    let mut time: Option<(NaiveTime, String)> = None;
+    let mut cued = false;
+    // The phrase's own first word counts too: "sebelum pertemuan berikutnya"
+    let cued_at = |start: usize| tokens[start.saturating_sub(CUE_WINDOW)..=start].iter().any(|t| is_deadline_cue(t));
+
+    let mut i = 0;
+    while i < tokens.len() {
+        if let Some((used, date)) = match_date(&tokens, i, today) {
+            let phrase = tokens[i..i + used].join(" ");
+            match date {
+                DateRef::On(date) => dates.push((date, None, phrase)),
+                DateRef::NextMeeting => {
+                    // Unknown class or no schedule: the phrase just doesn't count
+                    if let Some((date, start)) = meeting.and_then(|m| m.oracle.next_meeting_after(m.course_name, m.parallel_code, now)) {
+                        dates.push((date, NaiveTime::parse_from_str(&start, "%H:%M").ok(), phrase));
+                    }
+                }
+            }
+            cued |= cued_at(i);
+            i += used;
+        } else if let Some((used, found)) = match_time(&tokens, i) {
+            time.get_or_insert((found, tokens[i..i + used].join(" ")));
+            cued |= cued_at(i);
+            i += used;
+        } else {
+            i += 1;
+        }
+    }
+
+    let (date, meeting_time, date_phrase) = dates.first().cloned()?;
+    if dates.iter().any(|(other, _, _)| *other != date) {
+        return None;
+    }
+
+    let end_of_day = NaiveTime::from_hms_opt(23, 59, 0).unwrap();
+    let (at, phrase) = match time {
+        Some((time, time_phrase)) => (date.and_time(time), format!("{} {}", date_phrase, time_phrase)),
+        None => (date.and_time(meeting_time.unwrap_or(end_of_day)), date_phrase),
+    };
+    Some(ResolvedDeadline { at, phrase, cued })
+}
+
+/// A date phrase starting at `tokens[i]`: (tokens used, date)
+fn match_date(tokens: &[&str], i: usize, today: NaiveDate) -> Option<(usize, DateRef)> {
+    let at = |k: usize| tokens.get(i + k).copied().unwrap_or("");
+    let on = |used: usize, date: NaiveDate| Some((used, DateRef::On(date)));
+
+    match (at(0), at(1), at(2)) {
+        ("hari" | "malam", "ini", _) | ("nanti", "malam", _) => return on(2, today),
+        ("today" | "tonight", _, _) => return on(1, today),
+        ("besok", "lusa", _) | ("day", "after", "tomorrow") => return on(if at(0) == "day" { 3 } else { 2 }, today + Duration::days(2)),
+        ("lusa", _, _) => return on(1, today + Duration::days(2)),
+        ("besok" | "bsk" | "tomorrow", _, _) => return on(1, today + Duration::days(1)),
+        ("minggu" | "pekan", "depan", _) | ("next", "week", _) => return on(2, today + Duration::days(7)),
+        ("seminggu" | "sepekan", "lagi", _) => return on(2, today + Duration::days(7)),
+        ("akhir", "bulan", _) => return on(2, end_of_month(today)),
+        ("end", "of", "month") => return on(3, end_of_month(today)),
+        ("end", "of", "the") if at(3) == "month" => return on(4, end_of_month(today)),
+        ("akhir", "minggu" | "pekan", _) | ("weekend", _, _) => {
+            let used = if at(0) == "weekend" { 1 } else { 2 };
+            return on(used, upcoming(today, Weekday::Sun));
+        }
+        ("pertemuan" | "kelas", "berikutnya" | "selanjutnya", _) | ("next", "class" | "meeting" | "lecture", _) => {
+            return Some((2, DateRef::NextMeeting));
+        }
+        ("sebelum", "pertemuan" | "kelas" | "praktikum", _) | ("before", "class" | "lecture", _) => {
+            let modifier = matches!(at(2), "berikutnya" | "selanjutnya");
+            return Some((if modifier { 3 } else { 2 }, DateRef::NextMeeting));
+        }
+        ("before", "next", "class" | "meeting" | "lecture") => return Some((3, DateRef::NextMeeting)),
+        _ => {}
+    }
+
+    // "dalam 3 hari", "3 hari lagi", "in 3 days", "2 minggu lagi"
+    let days = |unit: &str| match unit {
+        "hari" | "day" | "days" => Some(1),
+        "minggu" | "pekan" | "week" | "weeks" => Some(7),
+        _ => None,
+    };
+    if let ("dalam" | "in", n, unit) = (at(0), at(1), at(2)) {
+        if let (Some(n), Some(unit)) = (count(n), days(unit)) {
+            return on(3, today + Duration::days(n * unit));
+        }
+    }
+    if let (n, unit, "lagi") = (at(0), at(1), at(2)) {
+        if let (Some(n), Some(unit)) = (count(n), days(unit)) {
+            return on(3, today + Duration::days(n * unit));
+        }
+    }
+
+    // Weekdays: "jumat" (this one, today included), "jumat depan" / "next friday" (next week's)
+    let (prefix, name) = match at(0) {
+        "next" | "this" | "hari" => (1, at(1)),
+        _ => (0, at(0)),
+    };
+    if let Some(weekday) = weekday(name).or_else(|| (at(0) == "hari" && name == "minggu").then_some(Weekday::Sun)) {
+        let rest = (at(prefix + 1), at(prefix + 2));
+        let (suffix, next_week) = match rest {
+            ("depan", _) | ("minggu" | "pekan", "depan") | ("next", "week") => (if rest.0 == "depan" { 1 } else { 2 }, true),
+            ("ini", _) => (1, false),
+            _ => (0, at(0) == "next"),
+        };
+        let date = if next_week { following_week(today, weekday) } else { upcoming(today, weekday) };
+        return on(prefix + 1 + suffix, date);
+    }
+
+    // "25 oktober [2026]", "tanggal 25 okt", "october 25 [2026]"
+    let year_at = |k: usize| at(k).parse::<i32>().ok().filter(|y| (2000..=2100).contains(y));
+    if let (Some(day), Some(month)) = (at(0).parse::<u32>().ok(), month(at(1))) {
+        let year = year_at(2);
+        return resolve_day_month(day, month, year, today).map(|d| (2 + year.is_some() as usize, DateRef::On(d)));
+    }
+    if let (Some(month), Some(day)) = (month(at(0)), at(1).parse::<u32>().ok()) {
+        let year = year_at(2);
+        return resolve_day_month(day, month, year, today).map(|d| (2 + year.is_some() as usize, DateRef::On(d)));
+    }
+
+    // "2026-10-25", "25/10", "25/10/26", "25-10-2026"
+    if let Some(date) = numeric_date(at(0), today) {
+        return on(1, date);
+    }
+
+    // "tanggal 25" → the next 25th
+    if matches!(at(0), "tanggal" | "tgl") && month(at(2)).is_none() {
+        if let Some(day) = at(1).parse::<u32>().ok().filter(|d| (1..=31).contains(d)) {
+            return next_day_of_month(day, today).map(|d| (2, DateRef::On(d)));
+        }
+    }
+
+    None
+}
+
+/// A time phrase starting at `tokens[i]`: (tokens used, time)
+fn match_time(tokens: &[&str], i: usize) -> Option<(usize, NaiveTime)> {
+    let at = |k: usize| tokens.get(i + k).copied().unwrap_or("");
+
+    if matches!((at(0), at(1)), ("tengah", "malam")) {
+        return Some((2, NaiveTime::from_hms_opt(23, 59, 0).unwrap()));
+    }
+    if at(0) == "midnight" {
+        return Some((1, NaiveTime::from_hms_opt(23, 59, 0).unwrap()));
+    }
+
+    // "jam 8", "pukul 08.30", "at 5pm"
+    let keyword = matches!(at(0), "jam" | "pukul" | "pkl" | "at") as usize;
+    let token = at(keyword);
+
+    let (hour, minute, mut meridiem) = if let Some((h, m)) = token.split_once([':', '.']) {
+        let minute = m.strip_suffix("am").or_else(|| m.strip_suffix("pm")).unwrap_or(m);
+        if minute.len() != 2 {
+            return None;
+        }
+        (h.parse::<u32>().ok()?, minute.parse::<u32>().ok()?, meridiem_of(m))
+    } else if let Some(hour) = token.strip_suffix("am").or_else(|| token.strip_suffix("pm")) {
+        (hour.parse::<u32>().ok()?, 0, meridiem_of(token))
+    } else if keyword == 1 || matches!(at(1), "am" | "pm") {
+        (token.parse::<u32>().ok()?, 0, None)
+    } else {
+        return None;
+    };
+
+    let mut used = keyword + 1;
+    if meridiem.is_none() {
+        meridiem = match at(used) {
+            "am" | "pagi" => Some(false),
+            "pm" | "siang" | "sore" | "malam" => Some(true),
+            _ => None,
+        };
+        if meridiem.is_some() {
+            used += 1;
+        }
+    }
+
+    // "jam 12 malam" / "24:00" mean the end of that day, not the start
+    if (hour == 12 && minute == 0 && at(used - 1) == "malam") || (hour == 24 && minute == 0) {
+        return Some((used, NaiveTime::from_hms_opt(23, 59, 0).unwrap()));
+    }
+
+    let hour = match meridiem {
+        // "jam 11 siang" stays 11:00, "jam 1 siang" is 13:00
+        Some(true) if hour < 12 && !(at(used - 1) == "siang" && hour >= 10) => hour + 12,
+        Some(false) if hour == 12 => 0,
+        _ => hour,
+    };
+    NaiveTime::from_hms_opt(hour, minute, 0).map(|t| (used, t))
+}
+
+fn meridiem_of(token: &str) -> Option<bool> {
+    if token.ends_with("pm") {
+        Some(true)
+    } else if token.ends_with("am") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+fn count(word: &str) -> Option<i64> {
+    word.parse::<i64>()
+        .ok()
+        .filter(|n| (1..=60).contains(n))
+        .or_else(|| NUMBER_WORDS.iter().find(|(name, _)| *name == word).map(|(_, n)| *n))
+}
+
+fn month(word: &str) -> Option<u32> {
+    MONTHS.iter().find(|(name, _)| *name == word).map(|(_, m)| *m)
+}
+
+fn weekday(word: &str) -> Option<Weekday> {
+    match word {
+        "senin" | "monday" | "mon" => Some(Weekday::Mon),
+        "selasa" | "tuesday" | "tue" => Some(Weekday::Tue),
+        "rabu" | "wednesday" | "wed" => Some(Weekday::Wed),
+        "kamis" | "thursday" | "thu" => Some(Weekday::Thu),
+        "jumat" | "jum'at" | "friday" | "fri" => Some(Weekday::Fri),
+        "sabtu" | "saturday" | "sat" => Some(Weekday::Sat),
+        // "minggu" only after "hari": otherwise it's "minggu depan/ini" (week)
+        "sunday" | "sun" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+/// This `weekday`, today included
+fn upcoming(today: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let ahead = (weekday.num_days_from_monday() as i64 - today.weekday().num_days_from_monday() as i64).rem_euclid(7);
+    today + Duration::days(ahead)
+}
+
+/// `weekday` in the Monday–Sunday week after this one
+fn following_week(today: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let monday = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+    monday + Duration::days(7 + weekday.num_days_from_monday() as i64)
+}
+
+fn end_of_month(today: NaiveDate) -> NaiveDate {
+    let (year, month) = if today.month() == 12 { (today.year() + 1, 1) } else { (today.year(), today.month() + 1) };
+    NaiveDate::from_ymd_opt(year, month, 1).unwrap() - Duration::days(1)
+}
+
+/// Without a year, a date already behind us means next year
+fn resolve_day_month(day: u32, month: u32, year: Option<i32>, today: NaiveDate) -> Option<NaiveDate> {
+    match year {
+        Some(year) => NaiveDate::from_ymd_opt(year, month, day),
+        None => NaiveDate::from_ymd_opt(today.year(), month, day)
+            .filter(|date| *date >= today)
+            .or_else(|| NaiveDate::from_ymd_opt(today.year() + 1, month, day)),
+    }
+}
+
+fn next_day_of_month(day: u32, today: NaiveDate) -> Option<NaiveDate> {
+    (0..12).find_map(|ahead| {
+        let index = today.month0() + ahead;
+        let date = NaiveDate::from_ymd_opt(today.year() + (index / 12) as i32, index % 12 + 1, day)?;
+        (date >= today).then_some(date)
+    })
+}
+
+fn numeric_date(token: &str, today: NaiveDate) -> Option<NaiveDate> {
+    if let Ok(date) = NaiveDate::parse_from_str(token, "%Y-%m-%d") {
+        return Some(date);
+    }
+
+    let parts: Vec<&str> = token.split(['/', '-']).collect();
+    // "1-2" is more often a range than a date: dashes need a year
+    let slashed = token.contains('/') && !token.contains('-');
+    let (day, month, year) = match parts.as_slice() {
+        [d, m] if slashed => (d, m, None),
+        [d, m, y] if y.len() == 2 || y.len() == 4 => (d, m, Some(y)),
+        _ => return None,
+    };
+
+    let year = match year.map(|y| y.parse::<i32>()) {
+        Some(Ok(y)) if y < 100 => Some(2000 + y),
+        Some(Ok(y)) => Some(y),
+        Some(Err(_)) => return None,
+        None => None,
+    };
+    resolve_day_month(day.parse().ok()?, month.parse().ok()?, year, today)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::crud::parse_deadline;
+    use chrono::FixedOffset;
+    use proptest::prelude::*;
+
+    fn now() -> NaiveDateTime {
+        // Minggu, 18 Okt 2026 10:00 WIB
+        NaiveDate::from_ymd_opt(2026, 10, 18).unwrap().and_hms_opt(10, 0, 0).unwrap()
+    }
+
+    fn resolve(text: &str) -> Option<String> {
+        resolve_deadline(text, now(), None).map(|d| d.canonical())
+    }
+
+    fn oracle() -> ScheduleOracle {
+        ScheduleOracle::from_json(r#"{
+            "Senin": [{"course": "KOM120H - Struktur Data", "parallel": "K1", "schedule": "10:00-11:40"}],
+            "Selasa": [], "Rabu": [],
+            "Kamis": [{"course": "KOM120H - Struktur Data", "parallel": "K1", "schedule": "08:00-09:40"}],
+            "Jumat": []
+        }"#).unwrap()
+    }
+
+    #[test]
+    fn test_relative_phrases() {
+        assert_eq!(resolve("kuis metkuan P1 besok jam 8 pagi").as_deref(), Some("2026-10-19 08:00"));
+        assert_eq!(resolve("dikumpulkan lusa").as_deref(), Some("2026-10-20 23:59"));
+        assert_eq!(resolve("deadline hari ini jam 5 sore").as_deref(), Some("2026-10-18 17:00"));
+        assert_eq!(resolve("LKP 6 deadline Rabu 23:59").as_deref(), Some("2026-10-21 23:59"));
+        // Sunday closes the week, so "depan" is already the coming one
+        assert_eq!(resolve("kumpul Jumat depan").as_deref(), Some("2026-10-23 23:59"));
+        assert_eq!(resolve("due next friday 5pm").as_deref(), Some("2026-10-23 17:00"));
+        let monday = NaiveDate::from_ymd_opt(2026, 10, 19).unwrap().and_hms_opt(8, 0, 0).unwrap();
+        assert_eq!(resolve_deadline("kumpul Jumat depan", monday, None).unwrap().canonical(), "2026-10-30 23:59");
+        assert_eq!(resolve("presentasi minggu depan").as_deref(), Some("2026-10-25 23:59"));
+        assert_eq!(resolve("laporan akhir bulan").as_deref(), Some("2026-10-31 23:59"));
+        assert_eq!(resolve("3 hari lagi ya").as_deref(), Some("2026-10-21 23:59"));
+        assert_eq!(resolve("in two days at 9:30 am").as_deref(), Some("2026-10-20 09:30"));
+        assert_eq!(resolve("hari minggu jam 12 malam").as_deref(), Some("2026-10-18 23:59"));
+    }
+
+    #[test]
+    fn test_absolute_dates() {
+        assert_eq!(resolve("dikumpulkan tanggal 25 Oktober jam 23.59 di LMS").as_deref(), Some("2026-10-25 23:59"));
+        assert_eq!(resolve("due October 25, 2027").as_deref(), Some("2027-10-25 23:59"));
+        assert_eq!(resolve("deadline 3/1").as_deref(), Some("2027-01-03 23:59"));
+        assert_eq!(resolve("deadline 2026-11-02 14:00").as_deref(), Some("2026-11-02 14:00"));
+        assert_eq!(resolve("paling lambat tgl 5 pukul 7 malam").as_deref(), Some("2026-11-05 19:00"));
+    }
+
+    #[test]
+    fn test_no_date_or_ambiguous() {
+        assert_eq!(resolve("LKP 6 pemrog K1 stack & queue"), None);
+        assert_eq!(resolve("jam 8 ya"), None);
+        assert_eq!(resolve("halaman 1-2, soal 10.5"), None);
+        // Two different days: leave it to the model
+        assert_eq!(resolve("dibagikan hari ini, dikumpulkan Jumat"), None);
+        assert_eq!(resolve("besok (Senin) jam 10").as_deref(), Some("2026-10-19 10:00"));
+    }
+
+    #[test]
+    fn test_only_cued_phrases_override_the_model() {
+        let opening = resolve_deadline("LKP 7 dibuka besok jam 8, dikumpulkan 3 hari setelah dibuka", now(), None).unwrap();
+        assert_eq!(opening.canonical(), "2026-10-19 08:00");
+        assert!(!opening.cued);
+        // The model's reading (3 days after opening) is kept
+        assert!(!opening.overrides(Some("2026-10-22 08:00")));
+        assert!(!opening.overrides(None));
+        // ...unless the model put it on the same day anyway
+        assert!(opening.overrides(Some("2026-10-19 23:59")));
+
+        for text in ["deadline besok jam 8", "dikumpulkan paling lambat hari Jumat", "due tomorrow 5pm", "kumpul lusa", "LKP 6 dl Rabu 23:59"] {
+            let resolved = resolve_deadline(text, now(), None).unwrap();
+            assert!(resolved.cued, "{}", text);
+            assert!(resolved.overrides(Some("2026-12-01 23:59")), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_next_meeting_uses_schedule() {
+        let oracle = oracle();
+        let lookup = MeetingLookup { oracle: &oracle, course_name: "Struktur Data", parallel_code: "k1" };
+
+        let resolved = resolve_deadline("kumpulkan sebelum pertemuan berikutnya", now(), Some(lookup)).unwrap();
+        assert_eq!(resolved.canonical(), "2026-10-19 10:00");
+        assert_eq!(resolved.phrase, "sebelum pertemuan berikutnya");
+
+        // Monday 10:30: today's class already started → Thursday
+        let later = NaiveDate::from_ymd_opt(2026, 10, 19).unwrap().and_hms_opt(10, 30, 0).unwrap();
+        let resolved = resolve_deadline("before next class", later, Some(lookup)).unwrap();
+        assert_eq!(resolved.canonical(), "2026-10-22 08:00");
+
+        // No schedule to ask
+        assert_eq!(resolve("sebelum kelas"), None);
+    }
+
+    fn any_now() -> impl Strategy<Value = NaiveDateTime> {
+        (0i64..3650, 0u32..24, 0u32..60).prop_map(|(days, hour, minute)| {
+            (NaiveDate::from_ymd_opt(2024, 1, 1).unwrap() + Duration::days(days)).and_hms_opt(hour, minute, 0).unwrap()
+        })
+    }
+
+    const WEEKDAYS: [(&str, Weekday); 6] = [
+        ("senin", Weekday::Mon),
+        ("selasa", Weekday::Tue),
+        ("rabu", Weekday::Wed),
+        ("kamis", Weekday::Thu),
+        ("jumat", Weekday::Fri),
+        ("sabtu", Weekday::Sat),
+    ];
+
+    proptest! {
+        #[test]
+        fn prop_never_panics(text in "\\PC{0,80}", now in any_now()) {
+            let _ = resolve_deadline(&text, now, None);
+        }
+
+        #[test]
+        fn prop_canonical_round_trips_through_parse_deadline(now in any_now(), phrase in prop::sample::select(vec![
+            "besok", "lusa", "minggu depan", "akhir bulan", "jumat depan", "senin", "tanggal 31", "28 februari", "5 hari lagi",
+        ])) {
+            let resolved = resolve_deadline(phrase, now, None).unwrap();
+            let stored = parse_deadline(&resolved.canonical()).unwrap();
+            let wib = FixedOffset::east_opt(7 * 3600).unwrap();
+            prop_assert_eq!(stored.with_timezone(&wib).naive_local(), resolved.at);
+            prop_assert!(resolved.at.date() >= now.date());
+        }
+
+        #[test]
+        fn prop_relative_days(now in any_now(), n in 1i64..=30) {
+            let date = |text: &str| resolve_deadline(text, now, None).unwrap().at.date();
+            prop_assert_eq!(date("besok"), now.date() + Duration::days(1));
+            prop_assert_eq!(date("lusa"), now.date() + Duration::days(2));
+            prop_assert_eq!(date("minggu depan"), now.date() + Duration::days(7));
+            prop_assert_eq!(date(&format!("{} hari lagi", n)), now.date() + Duration::days(n));
+            prop_assert_eq!(date(&format!("dalam {} hari", n)), date(&format!("in {} days", n)));
+
+            let end = date("akhir bulan");
+            prop_assert_eq!(end.month(), now.month());
+            prop_assert_eq!(end.succ_opt().unwrap().day(), 1);
+        }
+
+        #[test]
+        fn prop_weekdays(now in any_now(), (name, weekday) in prop::sample::select(WEEKDAYS.to_vec())) {
+            let this = resolve_deadline(name, now, None).unwrap().at.date();
+            prop_assert_eq!(this.weekday(), weekday);
+            prop_assert!((0..7).contains(&(this - now.date()).num_days()));
+
+            let next = resolve_deadline(&format!("{} depan", name), now, None).unwrap().at.date();
+            prop_assert_eq!(next.weekday(), weekday);
+            prop_assert_eq!(next.iso_week().week(), (now.date() + Duration::days(7)).iso_week().week());
+            prop_assert_eq!(Some(next), resolve_deadline(&format!("NEXT {}", name), now, None).map(|d| d.at.date()));
+        }
+
+        #[test]
+        fn prop_day_month_is_next_occurrence(now in any_now(), day in 1u32..=28, month in 1u32..=12) {
+            let (name, _) = MONTHS.iter().find(|(_, m)| *m == month).unwrap();
+            let date = resolve_deadline(&format!("{} {}", day, name), now, None).unwrap().at.date();
+            prop_assert_eq!((date.day(), date.month()), (day, month));
+            prop_assert!(date >= now.date() && (date - now.date()).num_days() < 366);
+        }
+
+        #[test]
+        fn prop_times(now in any_now(), hour in 1u32..=11, minute in 0u32..60) {
+            let time = |text: &str| resolve_deadline(&format!("besok {}", text), now, None).unwrap().at.time();
+            let expect = |h: u32| NaiveTime::from_hms_opt(h, minute, 0).unwrap();
+            prop_assert_eq!(time(&format!("{}:{:02}", hour, minute)), expect(hour));
+            prop_assert_eq!(time(&format!("jam {}.{:02} pagi", hour, minute)), expect(hour));
+            prop_assert_eq!(time(&format!("pukul {}.{:02} malam", hour, minute)), expect(hour + 12));
+            prop_assert_eq!(time(&format!("{}:{:02}pm", hour, minute)), expect(hour + 12));
+        }
+    }
+}
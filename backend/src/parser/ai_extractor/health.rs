@@ -0,0 +1,220 @@
+// backend/src/parser/ai_extractor/health.rs
+//
+// Per-model circuit breaker shared by every chain. A model that keeps
+// failing (or says 429 / "model not found") is skipped for a cooldown
+// instead of costing every message another round trip, and chains try
+// models without recent failures first. State lives in memory only: a
+// restart gives every model a clean slate.
+
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use super::provider::{LlmError, ModelStep};
+
+pub(super) static HEALTH: Lazy<HealthRegistry> = Lazy::new(HealthRegistry::default);
+
+/// Hard failures in a row before the circuit opens
+const FAILURES_TO_OPEN: u32 = 3;
+/// First cooldown after repeated errors; doubles on every trip, capped
+const BASE_COOLDOWN: Duration = Duration::from_secs(30);
+const MAX_COOLDOWN: Duration = Duration::from_secs(10 * 60);
+/// 429 without a Retry-After
+const RATE_LIMIT_COOLDOWN: Duration = Duration::from_secs(60);
+/// Model renamed / decommissioned — it won't come back soon
+const MISSING_MODEL_COOLDOWN: Duration = Duration::from_secs(60 * 60);
+/// Trouble older than this no longer lowers a model's place in the chain,
+/// so a recovered primary model goes back to its configured position
+const DEGRADED_WINDOW: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Debug, Default, Clone)]
+struct ModelHealth {
+    /// Errors / rate limits since the last success
+    consecutive_failures: u32,
+    /// Answers the caller couldn't use since the last success
+    unusable: u32,
+    /// Times the circuit opened without a success in between
+    trips: u32,
+    open_until: Option<Instant>,
+    /// Latest failure or unusable answer
+    last_trouble: Option<Instant>,
+}
+
+#[derive(Default)]
+pub(super) struct HealthRegistry {
+    models: Mutex<HashMap<String, ModelHealth>>,
+}
+
+fn key(step: &ModelStep) -> String {
+    format!("{}:{}", step.provider.name(), step.model)
+}
+
+impl HealthRegistry {
+    /// Time left until `step` may be tried again, if its circuit is open
+    pub fn open_for(&self, step: &ModelStep, now: Instant) -> Option<Duration> {
+        let models = self.models.lock().unwrap();
+        let open_until = models.get(&key(step))?.open_until?;
+        open_until.checked_duration_since(now).filter(|left| !left.is_zero())
+    }
+
+    pub fn record_success(&self, step: &ModelStep) {
+        self.models.lock().unwrap().remove(&key(step));
+    }
+
+    /// The model answered, but not with anything usable. Lowers its place in
+    /// the chain; never opens the circuit (the next prompt may work fine).
+    pub fn record_unusable(&self, step: &ModelStep, now: Instant) {
+        let mut models = self.models.lock().unwrap();
+        let health = models.entry(key(step)).or_default();
+        health.unusable += 1;
+        health.last_trouble = Some(now);
+    }
+
+    /// Returns the cooldown when this failure opened the circuit
+    pub fn record_failure(&self, step: &ModelStep, error: &LlmError, now: Instant) -> Option<Duration> {
+        let cooldown_now = match error {
+            // Never reached the provider
+            LlmError::NotConfigured(_) => return None,
+            LlmError::InvalidResponse(_) => {
+                self.record_unusable(step, now);
+                return None;
+            }
+            LlmError::RateLimited(retry_after) => Some(retry_after.unwrap_or(RATE_LIMIT_COOLDOWN)),
+            LlmError::Status(status, body) if is_missing_model(*status, body) => Some(MISSING_MODEL_COOLDOWN),
+            LlmError::Status(..) | LlmError::Request(_) => None,
+        };
+
+        let mut models = self.models.lock().unwrap();
+        let health = models.entry(key(step)).or_default();
+        health.consecutive_failures += 1;
+        health.last_trouble = Some(now);
+
+        let cooldown = cooldown_now.or_else(|| {
+            (health.consecutive_failures >= FAILURES_TO_OPEN)
+                .then(|| (BASE_COOLDOWN * 2u32.saturating_pow(health.trips)).min(MAX_COOLDOWN))
+        })?;
+
+        health.trips += 1;
+        health.open_until = Some(now + cooldown);
+        Some(cooldown)
+    }
+
+    /// Models without recent trouble (DEGRADED_WINDOW) first, then the degraded
+    /// ones, circuits still open last; configured order is kept within each group
+    pub fn order<'a>(&self, chain: &'a [ModelStep], now: Instant) -> Vec<&'a ModelStep> {
+        let models = self.models.lock().unwrap();
+        let rank = |step: &ModelStep| match models.get(&key(step)) {
+            None => (false, 0),
+            Some(health) => (
+                health.open_until.is_some_and(|until| until > now),
+                if health.last_trouble.is_some_and(|at| now.saturating_duration_since(at) < DEGRADED_WINDOW) {
+                    health.consecutive_failures + health.unusable
+                } else {
+                    0
+                },
+            ),
+        };
+
+        let mut ordered: Vec<&ModelStep> = chain.iter().collect();
+        ordered.sort_by_key(|step| rank(step));
+        ordered
+    }
+}
+
+/// 404, or Groq's 400 for a decommissioned model
+fn is_missing_model(status: u16, body: &str) -> bool {
+    status == 404 || (status == 400 && (body.contains("model_not_found") || body.contains("decommissioned")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::provider::ProviderKind;
+
+    fn step(model: &str) -> ModelStep {
+        ModelStep { provider: ProviderKind::Groq, model: model.to_string(), temperature: 0.2, max_tokens: 1000, top_p: None }
+    }
+
+    fn models(ordered: Vec<&ModelStep>) -> Vec<&str> {
+        ordered.iter().map(|s| s.model.as_str()).collect()
+    }
+
+    #[test]
+    fn test_rate_limit_honors_retry_after() {
+        let health = HealthRegistry::default();
+        let now = Instant::now();
+        let a = step("a");
+
+        let cooldown = health.record_failure(&a, &LlmError::RateLimited(Some(Duration::from_secs(12))), now);
+        assert_eq!(cooldown, Some(Duration::from_secs(12)));
+        assert_eq!(health.open_for(&a, now + Duration::from_secs(2)), Some(Duration::from_secs(10)));
+        assert_eq!(health.open_for(&a, now + Duration::from_secs(12)), None);
+
+        assert_eq!(health.record_failure(&a, &LlmError::RateLimited(None), now), Some(RATE_LIMIT_COOLDOWN));
+    }
+
+    #[test]
+    fn test_repeated_errors_open_with_backoff() {
+        let health = HealthRegistry::default();
+        let now = Instant::now();
+        let a = step("a");
+        let error = LlmError::Status(503, "overloaded".to_string());
+
+        assert_eq!(health.record_failure(&a, &error, now), None);
+        assert_eq!(health.record_failure(&a, &error, now), None);
+        assert_eq!(health.open_for(&a, now), None);
+        assert_eq!(health.record_failure(&a, &error, now), Some(BASE_COOLDOWN));
+
+        // Half-open trial after the cooldown fails → reopens for longer
+        let later = now + BASE_COOLDOWN;
+        assert_eq!(health.open_for(&a, later), None);
+        assert_eq!(health.record_failure(&a, &error, later), Some(BASE_COOLDOWN * 2));
+
+        health.record_success(&a);
+        assert_eq!(health.open_for(&a, later), None);
+        assert_eq!(health.record_failure(&a, &error, later), None);
+    }
+
+    #[test]
+    fn test_missing_model_and_unusable_answers() {
+        let health = HealthRegistry::default();
+        let now = Instant::now();
+
+        let gone = LlmError::Status(400, r#"{"error":{"code":"model_decommissioned"}}"#.to_string());
+        assert_eq!(health.record_failure(&step("a"), &gone, now), Some(MISSING_MODEL_COOLDOWN));
+        assert_eq!(health.record_failure(&step("b"), &LlmError::Status(400, "bad request".to_string()), now), None);
+
+        for _ in 0..5 {
+            health.record_failure(&step("c"), &LlmError::InvalidResponse("not json".to_string()), now);
+        }
+        assert_eq!(health.open_for(&step("c"), now), None);
+        assert_eq!(health.record_failure(&step("d"), &LlmError::NotConfigured("KEY"), now), None);
+    }
+
+    #[test]
+    fn test_order_prefers_healthy_models() {
+        let health = HealthRegistry::default();
+        let now = Instant::now();
+        let chain = vec![step("a"), step("b"), step("c"), step("d")];
+
+        assert_eq!(models(health.order(&chain, now)), ["a", "b", "c", "d"]);
+
+        health.record_failure(&chain[0], &LlmError::RateLimited(None), now);
+        health.record_unusable(&chain[1], now);
+        assert_eq!(models(health.order(&chain, now)), ["c", "d", "b", "a"]);
+
+        // Cooldown over: still behind the models that never failed for a while
+        let later = now + RATE_LIMIT_COOLDOWN;
+        assert_eq!(models(health.order(&chain, later)), ["c", "d", "a", "b"]);
+
+        // Nothing went wrong since: back to the configured order
+        let recovered = now + DEGRADED_WINDOW;
+        assert_eq!(models(health.order(&chain, recovered)), ["a", "b", "c", "d"]);
+
+        // A fresh failure only demotes until its own window passes
+        health.record_unusable(&chain[0], recovered);
+        assert_eq!(models(health.order(&chain, recovered)), ["b", "c", "d", "a"]);
+        assert_eq!(models(health.order(&chain, recovered + DEGRADED_WINDOW)), ["a", "b", "c", "d"]);
+    }
+}
@@ -3,7 +3,17 @@ mod prompts;
 mod parsing;
 
 mod schedule_oracle;
+mod date_resolver;
 mod context_builder;
+mod model_chain;
+mod templates;
+mod cassette;
+mod health;
+mod provider;
+mod query_intent;
+mod rule_based;
+mod schema;
+mod usage;
 
 // ===== MODEL CONFIGURATION =====
 
@@ -37,10 +47,18 @@ pub const GEMINI_MODELS: &[&str; 5] = &[
 
 // ===== PUBLIC API =====
 
-pub use core::{extract_with_ai, match_update_to_assignment, check_duplicate_assignment};
-pub use schedule_oracle::ScheduleOracle;
+pub use core::{extract_with_ai, match_update_to_assignment, check_duplicate_assignment, SCHEDULE_ORACLE};
+pub use schedule_oracle::{ClassSlot, ScheduleOracle};
+pub use date_resolver::{resolve_deadline, MeetingLookup, ResolvedDeadline};
 pub use context_builder::build_context;
+pub use query_intent::{extract_query_intent, QueryIntent};
 pub use parsing::extract_numbers; // Export number extraction utility
+pub use parsing::extract_assignment_type;
+pub use parsing::set_fixed_now;
+pub use cassette::{Cassette, CassetteMode};
+pub use templates::load_prompt_templates;
+pub use provider::ProviderKind;
+pub use usage::{configured_budgets, init as init_usage_tracking, Budget, BudgetPeriod};
 
 // ===== HELPER =====
 
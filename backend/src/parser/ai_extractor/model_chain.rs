@@ -0,0 +1,315 @@
+// backend/src/parser/ai_extractor/model_chain.rs
+//
+// Per-task model chains. Each task tries its models in order until one
+// returns an answer the caller can parse. Chains come from `llm_models.json`
+// (path via LLM_MODELS_FILE); tasks missing from the file keep the defaults
+// built from the model lists in mod.rs. The same file holds per-provider
+// budgets and model prices (see usage.rs).
+
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::Instant;
+
+use super::cassette;
+use super::health::{HealthRegistry, HEALTH};
+use super::prompts::build_repair_prompt;
+use super::provider::{provider_for, LlmError, LlmRequest, ModelStep, ProviderKind, TokenUsage};
+use super::usage::{self, Budget, Outcome, Price};
+use super::{GEMINI_MODELS, GROQ_REASONING_MODELS, GROQ_TEXT_MODELS, GROQ_VISION_MODELS};
+
+pub static LLM_CONFIG: Lazy<LlmConfig> = Lazy::new(LlmConfig::load);
+
+/// What the model is used for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LlmTask {
+    /// Stage 1: course / parallel hints
+    Context,
+    /// Stage 2: full assignment extraction (text)
+    Extraction,
+    /// Stage 2 with an image attached
+    Vision,
+    /// Which assignment an update refers to
+    Matching,
+    /// Is a new assignment a duplicate
+    Dedup,
+    /// Intent of a private-chat question
+    Query,
+}
+
+impl LlmTask {
+    pub fn label(&self) -> &'static str {
+        match self {
+            LlmTask::Context => "context",
+            LlmTask::Extraction => "extraction",
+            LlmTask::Vision => "vision",
+            LlmTask::Matching => "matching",
+            LlmTask::Dedup => "dedup",
+            LlmTask::Query => "query",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct LlmConfig {
+    pub context: Vec<ModelStep>,
+    pub extraction: Vec<ModelStep>,
+    pub vision: Vec<ModelStep>,
+    pub matching: Vec<ModelStep>,
+    pub dedup: Vec<ModelStep>,
+    pub query: Vec<ModelStep>,
+    /// Usage caps per provider; none by default
+    pub budgets: Vec<Budget>,
+    /// Model name → USD per 1M tokens, for `llm_usage.cost_usd`
+    pub prices: HashMap<String, Price>,
+}
+
+impl Default for LlmConfig {
+    fn default() -> Self {
+        let steps = |provider, models: &[&str], temperature, max_tokens, top_p| -> Vec<ModelStep> {
+            models
+                .iter()
+                .map(|model| ModelStep { provider, model: model.to_string(), temperature, max_tokens, top_p })
+                .collect()
+        };
+
+        // Reasoning models work better at 0.5-0.7
+        let mut extraction = steps(ProviderKind::Groq, GROQ_REASONING_MODELS, 0.6, 8192, Some(0.95));
+        extraction.extend(steps(ProviderKind::Groq, GROQ_TEXT_MODELS, 0.2, 4096, None));
+        extraction.extend(steps(ProviderKind::Gemini, GEMINI_MODELS, 0.2, 4096, None));
+
+        Self {
+            context: steps(ProviderKind::Groq, GROQ_TEXT_MODELS, 0.1, 1000, None),
+            extraction,
+            vision: steps(ProviderKind::Groq, GROQ_VISION_MODELS, 0.2, 4096, None),
+            matching: steps(ProviderKind::Gemini, GEMINI_MODELS, 0.2, 4096, None),
+            dedup: steps(ProviderKind::Gemini, GEMINI_MODELS, 0.0, 1024, None),
+            query: steps(ProviderKind::Groq, GROQ_TEXT_MODELS, 0.1, 1000, None),
+            budgets: Vec::new(),
+            prices: HashMap::new(),
+        }
+    }
+}
+
+impl LlmConfig {
+    /// Read LLM_MODELS_FILE (default `llm_models.json`); fall back to defaults if absent or invalid
+    pub fn load() -> Self {
+        let path = std::env::var("LLM_MODELS_FILE").unwrap_or_else(|_| "llm_models.json".to_string());
+
+        match std::fs::read_to_string(&path) {
+            Ok(content) => match Self::from_json(&content) {
+                Ok(config) => {
+                    println!("🧠 Loaded LLM model chains from {}", path);
+                    config
+                }
+                Err(e) => {
+                    eprintln!("⚠️  {} — using default model chains", e);
+                    Self::default()
+                }
+            },
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn from_json(content: &str) -> Result<Self, String> {
+        serde_json::from_str(content).map_err(|e| format!("Failed to parse LLM model config: {}", e))
+    }
+
+    pub fn chain(&self, task: LlmTask) -> &[ModelStep] {
+        match task {
+            LlmTask::Context => &self.context,
+            LlmTask::Extraction => &self.extraction,
+            LlmTask::Vision => &self.vision,
+            LlmTask::Matching => &self.matching,
+            LlmTask::Dedup => &self.dedup,
+            LlmTask::Query => &self.query,
+        }
+    }
+}
+
+/// Try every model of `task`'s chain until `parse` accepts an answer.
+/// Healthy models go first and open circuits are skipped (health.rs, not
+/// while replaying a cassette); providers over budget are skipped too.
+/// Every call is recorded in `llm_usage`.
+pub async fn run_chain<T>(
+    task: LlmTask,
+    request: &LlmRequest<'_>,
+    parse: impl Fn(&str) -> Result<T, String>,
+) -> Result<T, String> {
+    run_steps(task, request, |text: &str| parse(text).map_err(|e| vec![e]), false).await
+}
+
+/// Like `run_chain`, but an answer that fails `validate` gets one repair
+/// prompt (the errors, same model) before the chain moves on
+pub async fn run_chain_with_repair<T>(
+    task: LlmTask,
+    request: &LlmRequest<'_>,
+    validate: impl Fn(&str) -> Result<T, Vec<String>>,
+) -> Result<T, String> {
+    run_steps(task, request, validate, true).await
+}
+
+async fn run_steps<T>(
+    task: LlmTask,
+    request: &LlmRequest<'_>,
+    parse: impl Fn(&str) -> Result<T, Vec<String>>,
+    repair: bool,
+) -> Result<T, String> {
+    let chain = LLM_CONFIG.chain(task);
+    let health = (!cassette::replaying()).then(|| &*HEALTH);
+    let steps = match health {
+        Some(health) => health.order(chain, Instant::now()),
+        None => chain.iter().collect(),
+    };
+
+    for (index, step) in steps.into_iter().enumerate() {
+        let provider = provider_for(step.provider);
+        if let Some(left) = health.and_then(|h| h.open_for(step, Instant::now())) {
+            println!("│ ⚡ Skipped  : {} — circuit open ({}s left)", step.model, left.as_secs());
+            continue;
+        }
+        if let Some(reason) = usage::over_budget(step.provider).await {
+            println!("│ 💸 Skipped  : {} — {}", step.model, reason);
+            continue;
+        }
+        println!(
+            "│ 🔄 Model    : {} ({} {}/{}, {})",
+            step.model,
+            task.label(),
+            index + 1,
+            chain.len(),
+            provider.name()
+        );
+
+        let Some(answer) = attempt(task, step, request, &parse, health).await else {
+            continue;
+        };
+        let (text, errors) = match answer {
+            Ok(result) => return Ok(result),
+            Err(rejected) => rejected,
+        };
+        eprintln!("│ ⚠️  Unusable answer from {}: {}", step.model, errors.join("; "));
+        if !repair {
+            continue;
+        }
+
+        println!("│ 🩹 Repair   : {} ({} error(s))", step.model, errors.len());
+        let prompt = build_repair_prompt(request.prompt, &text, &errors);
+        // The first answer already holds what the image said
+        let repair_request = LlmRequest { prompt: &prompt, image_base64: None, ..*request };
+
+        match attempt(task, step, &repair_request, &parse, health).await {
+            Some(Ok(result)) => return Ok(result),
+            Some(Err((_, errors))) => eprintln!("│ ⚠️  Repair failed: {}", errors.join("; ")),
+            None => {}
+        }
+    }
+
+    Err(format!("All {} models failed", task.label()))
+}
+
+/// One call to `step`, parsed, with usage and health bookkeeping. `None` when
+/// the call itself failed; a rejected answer comes back with its errors.
+async fn attempt<T>(
+    task: LlmTask,
+    step: &ModelStep,
+    request: &LlmRequest<'_>,
+    parse: &impl Fn(&str) -> Result<T, Vec<String>>,
+    health: Option<&HealthRegistry>,
+) -> Option<Result<T, (String, Vec<String>)>> {
+    let started = Instant::now();
+
+    match provider_for(step.provider).complete(step, request).await {
+        Ok(completion) => {
+            let parsed = parse(&completion.text);
+            let outcome = if parsed.is_ok() { Outcome::Ok } else { Outcome::Invalid };
+            usage::record(task, step, request.prompt_version, completion.usage, started.elapsed(), outcome);
+
+            if let Some(health) = health {
+                match parsed {
+                    Ok(_) => health.record_success(step),
+                    Err(_) => health.record_unusable(step, Instant::now()),
+                }
+            }
+            Some(parsed.map_err(|errors| (completion.text, errors)))
+        }
+        Err(e) => {
+            let outcome = match e {
+                // Never reached the provider, nothing to account for
+                LlmError::NotConfigured(_) => None,
+                LlmError::RateLimited(_) => Some(Outcome::RateLimited),
+                _ => Some(Outcome::Error),
+            };
+            if let Some(outcome) = outcome {
+                usage::record(task, step, request.prompt_version, TokenUsage::default(), started.elapsed(), outcome);
+            }
+            eprintln!("│ ❌ Failed   : {} — {}", step.model, e);
+
+            let opened = health.and_then(|h| h.record_failure(step, &e, Instant::now()));
+            if let Some(cooldown) = opened {
+                println!("│ ⚡ Circuit  : {} open for {}s", step.model, cooldown.as_secs());
+            }
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_chains_match_model_lists() {
+        let config = LlmConfig::default();
+
+        assert_eq!(
+            config.extraction.len(),
+            GROQ_REASONING_MODELS.len() + GROQ_TEXT_MODELS.len() + GEMINI_MODELS.len()
+        );
+        assert_eq!(config.extraction[0].model, GROQ_REASONING_MODELS[0]);
+        assert_eq!(config.extraction.last().unwrap().provider, ProviderKind::Gemini);
+        assert_eq!(config.chain(LlmTask::Vision)[0].model, GROQ_VISION_MODELS[0]);
+        assert_eq!(config.chain(LlmTask::Dedup)[0].temperature, 0.0);
+    }
+
+    #[test]
+    fn test_config_overrides_only_given_tasks() {
+        let config = LlmConfig::from_json(r#"{
+            "matching": [
+                {"provider": "groq", "model": "llama-3.3-70b-versatile"},
+                {"provider": "gemini", "model": "gemini-2.5-flash", "temperature": 0.0, "max_tokens": 512}
+            ]
+        }"#).unwrap();
+
+        assert_eq!(config.matching.len(), 2);
+        assert_eq!(config.matching[0].temperature, 0.2);
+        assert_eq!(config.matching[0].max_tokens, 4096);
+        assert_eq!(config.matching[1].max_tokens, 512);
+        assert_eq!(config.extraction, LlmConfig::default().extraction);
+
+        let local = LlmConfig::from_json(r#"{"dedup": [{"provider": "openai", "model": "qwen2.5:7b"}]}"#).unwrap();
+        assert_eq!(local.dedup[0].provider, ProviderKind::Local);
+
+        assert!(LlmConfig::from_json(r#"{"dedup": [{"provider": "anthropic", "model": "x"}]}"#).is_err());
+    }
+
+    #[test]
+    fn test_config_budgets_and_prices() {
+        let config = LlmConfig::from_json(r#"{
+            "budgets": [
+                {"provider": "groq", "max_requests": 1000},
+                {"provider": "gemini", "period": "monthly", "max_cost_usd": 5.0}
+            ],
+            "prices": {"gemini-2.5-flash": {"input": 0.3, "output": 2.5}}
+        }"#).unwrap();
+
+        assert_eq!(config.budgets.len(), 2);
+        assert_eq!(config.budgets[0].period, usage::BudgetPeriod::Daily);
+        assert_eq!(config.budgets[0].max_tokens, None);
+        assert_eq!(config.budgets[1].period, usage::BudgetPeriod::Monthly);
+        assert_eq!(config.prices["gemini-2.5-flash"].output, 2.5);
+        assert_eq!(config.extraction, LlmConfig::default().extraction);
+        assert!(LlmConfig::default().budgets.is_empty());
+    }
+}
@@ -1,13 +1,23 @@
-use crate::models::AIClassification;
 use uuid::Uuid;
 use serde::Deserialize;
-use chrono::{Utc, FixedOffset};
+use chrono::{DateTime, FixedOffset, NaiveDateTime, Utc};
+use std::sync::RwLock;
 
 // ===== API RESPONSE STRUCTURES =====
 
 #[derive(Debug, Deserialize)]
 pub(super) struct GroqResponse {
     pub choices: Vec<GroqChoice>,
+    #[serde(default)]
+    pub usage: Option<GroqUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct GroqUsage {
+    #[serde(default)]
+    pub prompt_tokens: u32,
+    #[serde(default)]
+    pub completion_tokens: u32,
 }
 
 #[derive(Debug, Deserialize)]
@@ -23,6 +33,16 @@ pub(super) struct GroqMessage {
 #[derive(Debug, Deserialize)]
 pub(super) struct GeminiResponse {
     pub candidates: Vec<Candidate>,
+    #[serde(default, rename = "usageMetadata")]
+    pub usage_metadata: Option<GeminiUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct GeminiUsage {
+    #[serde(default, rename = "promptTokenCount")]
+    pub prompt_token_count: u32,
+    #[serde(default, rename = "candidatesTokenCount")]
+    pub candidates_token_count: u32,
 }
 
 #[derive(Debug, Deserialize)]
@@ -72,29 +92,6 @@ pub(super) fn extract_ai_text(gemini_response: &GeminiResponse) -> Result<&str,
 
 // ===== PARSERS =====
 
-pub(super) fn parse_classification(ai_text: &str) -> Result<AIClassification, String> {
-    let cleaned = ai_text
-        .trim()
-        .trim_start_matches("```json")
-        .trim_start_matches("```")
-        .trim_end_matches("```")
-        .trim();
-    
-    if !is_valid_json_object(cleaned) {
-        eprintln!("⚠️  Response is not a valid JSON object");
-        return Ok(AIClassification::Unrecognized);
-    }
-    
-    match serde_json::from_str::<AIClassification>(cleaned) {
-        Ok(classification) => Ok(classification),
-        Err(e) => {
-            eprintln!("❌ JSON parse error: {}", e);
-            eprintln!("   Tried to parse: {}", cleaned);
-            Ok(AIClassification::Unrecognized)
-        }
-    }
-}
-
 pub(super) fn parse_match_result(ai_text: &str) -> Result<Option<Uuid>, String> {
     let cleaned = ai_text.trim()
         .trim_start_matches("```json")
@@ -187,56 +184,43 @@ pub fn extract_assignment_type(title: &str) -> Option<String> {
     None
 }
 
-// ===== SIMILARITY CALCULATION =====
+// ===== HELPERS =====
 
-pub fn calculate_word_overlap(s1: &str, s2: &str) -> f32 {
-    // Create owned strings first to avoid temporary value issues
-    let s1_lower = s1.to_lowercase();
-    let s2_lower = s2.to_lowercase();
-    
-    let words1: std::collections::HashSet<&str> = s1_lower
-        .split_whitespace()
-        .collect();
-    let words2: std::collections::HashSet<&str> = s2_lower
-        .split_whitespace()
-        .collect();
-    
-    if words1.is_empty() || words2.is_empty() {
-        return 0.0;
-    }
-    
-    let common = words1.intersection(&words2).count() as f32;
-    let total = words1.len().max(words2.len()) as f32;
-    
-    common / total
+/// Pinned "now" (WIB) for the eval harness; `None` = real clock
+static FIXED_NOW: RwLock<Option<NaiveDateTime>> = RwLock::new(None);
+
+/// Make every prompt and date hint use `now` (WIB) instead of the system clock
+pub fn set_fixed_now(now: Option<NaiveDateTime>) {
+    *FIXED_NOW.write().unwrap() = now;
 }
 
-// ===== HELPERS =====
+pub(super) fn now_wib() -> DateTime<FixedOffset> {
+    let gmt7 = FixedOffset::east_opt(7 * 3600).unwrap();
+    match *FIXED_NOW.read().unwrap() {
+        Some(fixed) => fixed.and_local_timezone(gmt7).unwrap(),
+        None => Utc::now().with_timezone(&gmt7),
+    }
+}
 
 pub(super) fn get_current_datetime() -> String {
-    let gmt7 = FixedOffset::east_opt(7 * 3600).unwrap();
-    let now = Utc::now().with_timezone(&gmt7);
-    now.format("%Y-%m-%d %H:%M:%S").to_string()
+    now_wib().format("%Y-%m-%d %H:%M:%S").to_string()
 }
 
-pub(super) fn get_current_date() -> String {
-    let gmt7 = FixedOffset::east_opt(7 * 3600).unwrap();
-    let now = Utc::now().with_timezone(&gmt7);
-    now.format("%Y-%m-%d").to_string()
+pub(super) fn get_current_datetime_naive() -> NaiveDateTime {
+    now_wib().naive_local()
 }
 
-pub(super) fn is_valid_json_object(s: &str) -> bool {
-    s.starts_with('{') 
-        && s.ends_with('}') 
-        && s.matches('{').count() == s.matches('}').count()
+pub(super) fn get_current_date() -> String {
+    now_wib().format("%Y-%m-%d").to_string()
 }
 
+/// At most `max_len` characters (not bytes: OCR text is full of "—", "•" and curly quotes)
 pub(super) fn truncate_for_log(text: &str, max_len: usize) -> String {
     let clean_text = text.replace('\n', " ");
-    if clean_text.len() <= max_len {
+    if clean_text.chars().count() <= max_len {
         clean_text
     } else {
-        format!("{}...", &clean_text[..max_len])
+        format!("{}...", clean_text.chars().take(max_len).collect::<String>())
     }
 }
 
@@ -261,9 +245,9 @@ mod tests {
     }
 
     #[test]
-    fn test_word_overlap() {
-        assert!(calculate_word_overlap("LKP 15", "LKP 15") > 0.9);
-        assert!(calculate_word_overlap("Quiz Data Structures", "Quiz Algorithms") > 0.3);
-        assert!(calculate_word_overlap("Quiz", "Lab") < 0.1);
+    fn test_truncate_for_log_counts_chars() {
+        assert_eq!(truncate_for_log("LKP 6\nstack", 20), "LKP 6 stack");
+        // Byte 4 is inside "—": slicing by bytes would panic
+        assert_eq!(truncate_for_log("abc—def • “ghi”", 4), "abc—...");
     }
-}
\ No newline at end of file
+}
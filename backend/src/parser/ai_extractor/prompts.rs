@@ -1,7 +1,9 @@
+use super::templates::{render, PromptKind, RenderedPrompt};
 use crate::models::Assignment;
 use std::collections::HashMap;
 use uuid::Uuid;
-use chrono::{Utc, FixedOffset, Duration}; 
+use chrono::{Utc, Duration};
+use super::parsing::now_wib;
 use super::context_builder::{MessageContext};
 
 /// Build assignment context list for the prompt
@@ -48,24 +50,27 @@ fn truncate_for_log(text: &str, max_len: usize) -> String {
     if clean_text.len() <= max_len { 
         clean_text 
     } else { 
-        format!("{}...", &clean_text[..max_len]) 
+        // Never cut inside a multi-byte character (emoji, "—" in OCR text)
+        let end = (0..=max_len).rev().find(|&i| clean_text.is_char_boundary(i)).unwrap_or(0);
+        format!("{}...", &clean_text[..end]) 
     }
 }
 
 /// Build the classification prompt for AI models
+#[allow(clippy::too_many_arguments)]
 pub fn build_classification_prompt(
     text: &str, 
+    image_text: Option<&str>,
     available_courses: &str, 
     active_assignments: &[Assignment],
     course_map: &HashMap<Uuid, String>,
     current_datetime: &str, 
     current_date: &str,
     context: Option<&MessageContext>,
-) -> String {
+) -> RenderedPrompt {
     let assignments_context = build_context_assignments_list(active_assignments, course_map);
 
-    let gmt7 = FixedOffset::east_opt(7 * 3600).unwrap();
-    let now = Utc::now().with_timezone(&gmt7);
+    let now = now_wib();
     
     let tomorrow_str = (now + Duration::days(1)).format("%Y-%m-%d").to_string();
     let lusa_str = (now + Duration::days(2)).format("%Y-%m-%d").to_string();
@@ -126,144 +131,28 @@ pub fn build_classification_prompt(
         String::new()
     };
     
-    format!(
-        r#"You are a bilingual (Indonesian/English) academic assistant that extracts structured assignment information from WhatsApp messages.
-
-CONTEXT
-═══════════════════════════════════════════════════════════════════
-Current time (GMT+7): {}
-Today's date: {}
-
-REFERENCE DATES (USE THESE EXACT DATES - END OF DAY 23:59):
-- Besok / Tomorrow : {} 23:59
-- Lusa / Day after tomorrow : {} 23:59
-- Minggu depan / Next week : {} 23:59
-
-Message: "{}"
-
-Available courses:
-{}
-
-Active assignments (recent):
-{}{}
-
-TASK
-═══════════════════════════════════════════════════════════════════
-Classify this message as:
-1. **MULTIPLE_ASSIGNMENTS** - Message contains 2+ assignments (CHECK FIRST)
-2. **NEW_ASSIGNMENT** - Announcing a single new task
-3. **UPDATE_ASSIGNMENT** - Modifying/clarifying existing assignment
-4. **UNRECOGNIZED** - Not about assignments
-
-CLASSIFICATION GUIDELINES
-═══════════════════════════════════════════════════════════════════
-
-**QUOTED MESSAGE HANDLING (PRIORITY):**
-- If QUOTED MESSAGE REFERENCE is present in context, the user is replying to a previous assignment
-- Common patterns when replying:
-  * "diundur" / "berubah" / "changed" = UPDATE to quoted assignment
-  * "diperjelas" / "clarification" = UPDATE with more details
-  * "ada lagi" / "another one" = NEW assignment (NOT updating the quoted one)
-- Extract course/parallel/existing info from quoted context to improve matching
-
-**MULTIPLE_ASSIGNMENTS (PRIORITY CHECK):**
-Signals:
-- Numbered lists: "1. Pemrog LKP 14...\n2. Kalkulus Tugas 3..."
-- Multiple course mentions: "Pemrog dan Fisika ada tugas"
-- Bullet points with different assignments
-- "ada 2 tugas", "3 assignments today"
-
-Extract each as separate assignment with ALL fields (course, title, deadline, description, parallel)
-
-**DEADLINE HANDLING:**
-- **If no deadline info exists in EITHER the message OR hints → deadline MUST be NULL**
-- If deadline hint is provided in RESOLVED CONTEXT, you MAY use it if appropriate
-- For dates WITHOUT specific time (e.g., "besok", "deadline Jumat") → USE 23:59 (end of day)
-- For dates WITH specific time (e.g., "jam 10 pagi") → USE that time
-- NEVER hallucinate dates when none are mentioned
-
-NEW_ASSIGNMENT signals:
-- "ada tugas baru", "new assignment", clear announcement
-- Contains: course + deadline + description
-- Sequential numbering not in DB (LKP 15 when only LKP 14 exists)
-- "ada lagi" when replying = NEW, not update
-
-UPDATE_ASSIGNMENT patterns:
-- **Explicit change words**: "berubah", "ganti", "diundur", "dimajuin", "revisi", "update", "correction"
-- **Clarification with reference**: "Tugas yang kemarin", "assignment from yesterday"
-- **Replying to quoted message** with change indicators
-- **MUST have change language** - don't assume update just because assignment exists
-
-**Key distinction**:
-- "Ada tugas LKP 15 lagi" → NEW (re-announcement, check for duplicate)
-- "LKP 15 deadline berubah" → UPDATE (explicit change)
-- Replying with "diundur" → UPDATE (use quoted context)
-- Replying with "ada lagi yang ini" → NEW (different assignment)
-
-**Matching logic for updates:**
-Use semantic understanding, not exact strings:
-- "coding pake kertas" can match "Coding on Paper Assignment"
-- Match by: course + identifying keywords (topic/number)
-- If QUOTED MESSAGE present: strongly prioritize that assignment
-- If reasonable match in DB → UPDATE
-
-UNRECOGNIZED:
-- No course mentioned, social chat, vague references without context
-
-PARALLEL CODES
-═══════════════════════════════════════════════════════════════════
-Valid codes (lowercase): k1, k2, k3, p1, p2, p3, r1, r2, r3, all, null
-Different codes = different assignments (K1 ≠ K2)
-Extract from quoted context if replying and not explicitly mentioned
-
-**CRITICAL: DESCRIPTION FIELD IS MANDATORY**
-═══════════════════════════════════════════════════════════════════
-**NEVER leave description empty or null.** Always generate a meaningful description.
-If minimal, use: "[Course] [assignment type] [identifier]"
-
-OUTPUT FORMATS
-═══════════════════════════════════════════════════════════════════
-
-MULTIPLE_ASSIGNMENTS:
-{{
-  "type": "multiple_assignments",
-  "assignments": [
-    {{ "course_name": "Pemrograman", "title": "LKP 14", "deadline": "2025-12-31 08:00", "description": "Programming lab assignment 14", "parallel_code": "k1" }},
-    {{ "course_name": "Kalkulus", "title": "Problem Set 5", "deadline": null, "description": "Calculus problem set 5", "parallel_code": null }}
-  ]
-}}
-
-NEW_ASSIGNMENT (single):
-{{"type":"assignment_info","course_name":"Pemrograman","title":"LKP 14","deadline":"2025-12-31 23:59","description":"Programming lab assignment 14","parallel_code":"k1"}}
-
-UPDATE_ASSIGNMENT:
-{{"type":"assignment_update","reference_keywords":["CourseName","identifier"],"changes":"what changed","new_deadline":"2025-12-30 14:00","new_title":null,"new_description":null,"parallel_code":"all"}}
-
-UNRECOGNIZED:
-{{"type":"unrecognized"}}
-
-PRINCIPLES
-═══════════════════════════════════════════════════════════════════
-1. **Check for QUOTED MESSAGE first** - prioritize context from replies
-2. **Check for multiple assignments SECOND** before single assignment
-3. **Semantic over literal**: Understand intent, not just keywords
-4. **Context matters**: Use DB, RESOLVED CONTEXT hints, and QUOTED references
-5. **ALWAYS GENERATE DESCRIPTIONS**: Never leave description field empty
-6. **Deadline format**: YYYY-MM-DD HH:MM (use provided time from hints, 23:59 for dates without time, NULL if no info)
-7. **Confidence-based**: High confidence → classify; Low → UNRECOGNIZED
-8. **Course boundaries**: Never match updates across different courses
-9. **When uncertain**: NEW > UPDATE (avoid bad matches); Classification > UNRECOGNIZED (avoid noise)
-
-Return ONLY valid JSON. No markdown, no explanations."#,
-        current_datetime,
-        current_date,
-        tomorrow_str,
-        lusa_str,
-        next_week_str,
-        text,
-        available_courses,
-        assignments_context,
-        context_hints
+    // OCR of the attached image: lets text-only models read screenshots
+    let image_section = image_text
+        .map(|t| format!(
+            "\n\nText read from the attached image (OCR, may contain recognition errors; treat it as part of the message):\n\"\"\"\n{}\n\"\"\"",
+            t
+        ))
+        .unwrap_or_default();
+    
+    render(
+        PromptKind::Classification,
+        &[
+            ("current_datetime", current_datetime),
+            ("current_date", current_date),
+            ("tomorrow", &tomorrow_str),
+            ("day_after_tomorrow", &lusa_str),
+            ("next_week", &next_week_str),
+            ("message", text),
+            ("image_text", &image_section),
+            ("courses", available_courses),
+            ("assignments", &assignments_context),
+            ("context_hints", &context_hints),
+        ],
     )
 }
 
@@ -274,12 +163,12 @@ pub fn build_matching_prompt(
     assignments: &[Assignment],
     course_map: &HashMap<Uuid, String>,
     parallel_code: Option<&str>,  
-) -> String {
+) -> RenderedPrompt {
     let assignments_list = assignments.iter().enumerate().map(|(i, a)| {
         let parallel_str = a.parallel_code.as_deref().unwrap_or("N/A");
         let course_name = a.course_id.and_then(|id| course_map.get(&id)).map(|s| s.as_str()).unwrap_or("Unknown Course");
         
-        let created_ago = Utc::now().signed_duration_since(a.created_at);
+        let created_ago = now_wib().with_timezone(&Utc).signed_duration_since(a.created_at);
         let time_ago = if created_ago.num_minutes() < 60 { format!("{} min ago", created_ago.num_minutes()) }
             else if created_ago.num_hours() < 24 { format!("{} hr ago", created_ago.num_hours()) }
             else { format!("{} days ago", created_ago.num_days()) };
@@ -289,23 +178,20 @@ pub fn build_matching_prompt(
         format!("#{}: {} | {} | \"{}\" | Parallel: {} | Desc: \"{}\" | {}", i + 1, a.id, course_name, a.title, parallel_str, desc_preview, time_ago)
     }).collect::<Vec<_>>().join("\n");
     
-    let gmt7 = FixedOffset::east_opt(7 * 3600).unwrap();
-    let now = Utc::now().with_timezone(&gmt7);
+    let now = now_wib();
     let current_time = now.format("%Y-%m-%d %H:%M:%S").to_string();
     
     let parallel_info = parallel_code.map(|pc| format!("Parallel code in update: {}", pc)).unwrap_or_else(|| "Parallel code: (not specified)".to_string());
     
-    format!(
-        r#"Match this update to an existing assignment.
-CONTEXT
-Time: {} | Update: "{}" | Keywords: {:?}
-{}
-Assignments:
-{}
-TASK: Find which assignment this update refers to, or return null if no match.
-OUTPUT: {{"assignment_id":"uuid","confidence":"high","reason":"..."}} or {{"assignment_id":null,"confidence":"low","reason":"..."}}
-Return ONLY valid JSON."#,
-        current_time, changes, keywords, parallel_info, assignments_list
+    render(
+        PromptKind::Matching,
+        &[
+            ("current_time", current_time.as_str()),
+            ("changes", changes),
+            ("keywords", &format!("{:?}", keywords)),
+            ("parallel_info", &parallel_info),
+            ("assignments", &assignments_list),
+        ],
     )
 }
 
@@ -317,7 +203,7 @@ pub fn build_duplicate_detection_prompt(
     parallel_code: Option<&str>,
     existing_assignments: &[Assignment],
     course_map: &HashMap<Uuid, String>,
-) -> String {
+) -> RenderedPrompt {
     let assignments_list = existing_assignments.iter().enumerate().map(|(i, a)| {
         let parallel_str = a.parallel_code.as_deref().unwrap_or("null");
         let course = a.course_id.and_then(|id| course_map.get(&id)).map(|s| s.as_str()).unwrap_or("Unknown");
@@ -336,49 +222,42 @@ pub fn build_duplicate_detection_prompt(
         .map(|pc| format!("Parallel: {}", pc))
         .unwrap_or_else(|| "Parallel: null".to_string());
     
-    format!(
-        r#"STRICT DUPLICATE DETECTION
+    render(
+        PromptKind::DuplicateDetection,
+        &[
+            ("course_name", course_name),
+            ("title", title),
+            ("description", description),
+            ("parallel_info", &parallel_info),
+            ("candidates", &assignments_list),
+        ],
+    )
+}
 
-NEW ASSIGNMENT:
-Course: {}
-Title: "{}"
-Description: "{}"
-{}
+/// Second chance for the same model: its answer plus every validation error
+pub fn build_repair_prompt(original_prompt: &str, answer: &str, errors: &[String]) -> String {
+    let error_list = errors
+        .iter()
+        .map(|e| format!("- {}", e))
+        .collect::<Vec<_>>()
+        .join("\n");
 
-CANDIDATES (pre-filtered by course/parallel/numbers/type):
-{}
+    format!(
+        r#"{}
 
-CRITICAL RULES:
 ═══════════════════════════════════════════════════════════════════
-1. Sequential numbers = DIFFERENT (LKP 15 ≠ LKP 14 ≠ LKP 17)
-2. Assignment types must match (quiz ≠ lab ≠ homework)
-3. Topics must be similar
-4. When uncertain → NOT duplicate (safer to create new)
-
-TRUE DUPLICATES (rare cases only):
-- Exact match: "LKP 15" = "LKP 15" ✓
-- Semantic match: "Lab Report 3" = "Laboratory Report 3" ✓
-- Reannouncement: "Quiz tomorrow" posted twice ✓
-- Clarification: "Quiz 5 updated" vs "Quiz 5" ✓
+YOUR PREVIOUS ANSWER WAS REJECTED
 
-NOT DUPLICATES:
-- Different numbers: "LKP 15" ≠ "LKP 14" ✗
-- Different types: "Quiz 5" ≠ "Lab 5" ✗
-- Different topics: "Data Structures" ≠ "Algorithms" ✗
+PREVIOUS ANSWER:
+{}
 
-OUTPUT FORMAT:
-{{
-  "is_duplicate": boolean,
-  "confidence": "high" | "medium" | "low",
-  "reason": "detailed explanation",
-  "matched_assignment_id": "uuid" or null
-}}
+ERRORS:
+{}
 
-Be STRICT. Default to false. Only mark as duplicate with HIGH confidence."#,
-        course_name,
-        title,
-        description,
-        parallel_info,
-        assignments_list
+Fix ONLY these errors and answer again with the complete JSON object.
+Keep every other field as it was. No markdown, no explanation."#,
+        original_prompt,
+        answer.trim(),
+        error_list
     )
-}
\ No newline at end of file
+}
@@ -0,0 +1,435 @@
+// backend/src/parser/ai_extractor/provider.rs
+//
+// One place that knows how to talk to each LLM API. Callers describe WHAT
+// they want (prompt, optional image, JSON output) and a `ModelStep` says
+// which provider/model/sampling to use; see model_chain.rs for the chains.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::fmt;
+use std::time::Duration;
+
+use super::cassette::{self, CassetteMode};
+use super::parsing::{extract_ai_text, extract_groq_text, GeminiResponse, GroqResponse};
+
+/// Which API a model lives on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProviderKind {
+    Groq,
+    Gemini,
+    /// Any OpenAI-compatible server (Ollama, llama.cpp server, vLLM) at LOCAL_LLM_URL
+    #[serde(alias = "openai")]
+    Local,
+}
+
+impl ProviderKind {
+    /// Name used in logs and `llm_usage.provider`
+    pub fn name(&self) -> &'static str {
+        provider_for(*self).name()
+    }
+}
+
+/// One model in a chain, with its sampling settings
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ModelStep {
+    pub provider: ProviderKind,
+    pub model: String,
+    #[serde(default = "default_temperature")]
+    pub temperature: f32,
+    #[serde(default = "default_max_tokens")]
+    pub max_tokens: u32,
+    #[serde(default)]
+    pub top_p: Option<f32>,
+}
+
+fn default_temperature() -> f32 {
+    0.2
+}
+
+fn default_max_tokens() -> u32 {
+    4096
+}
+
+/// What to send
+#[derive(Debug, Clone, Copy)]
+pub struct LlmRequest<'a> {
+    pub prompt: &'a str,
+    /// Base64 JPEG for vision models
+    pub image_base64: Option<&'a str>,
+    /// Ask the API to return a JSON object
+    pub json_mode: bool,
+    /// Template the prompt was rendered from, stored with the usage row
+    pub prompt_version: Option<&'a str>,
+}
+
+/// Tokens billed for one call, as reported by the API (0 when it doesn't say)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TokenUsage {
+    pub prompt_tokens: u32,
+    pub completion_tokens: u32,
+}
+
+/// Answer text plus what it cost
+#[derive(Debug, Clone, PartialEq)]
+pub struct Completion {
+    pub text: String,
+    pub usage: TokenUsage,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LlmError {
+    /// API key env var missing
+    NotConfigured(&'static str),
+    /// 429, with how long the provider asked us to wait
+    RateLimited(Option<Duration>),
+    Request(String),
+    Status(u16, String),
+    InvalidResponse(String),
+}
+
+impl fmt::Display for LlmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LlmError::NotConfigured(var) => write!(f, "{} not set in .env", var),
+            LlmError::RateLimited(Some(wait)) => write!(f, "Rate limited (retry after {}s)", wait.as_secs()),
+            LlmError::RateLimited(None) => write!(f, "Rate limited"),
+            LlmError::Request(e) => write!(f, "Request failed: {}", e),
+            LlmError::Status(status, body) => write!(f, "Status {}: {}", status, body),
+            LlmError::InvalidResponse(e) => write!(f, "Invalid response: {}", e),
+        }
+    }
+}
+
+#[async_trait]
+pub trait LlmProvider: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Send one request to `step.model` and return the raw text of the answer
+    async fn complete(&self, step: &ModelStep, request: &LlmRequest<'_>) -> Result<Completion, LlmError>;
+}
+
+pub struct GroqProvider;
+pub struct GeminiProvider;
+pub struct LocalProvider;
+
+pub fn provider_for(kind: ProviderKind) -> &'static dyn LlmProvider {
+    match kind {
+        ProviderKind::Groq => &GroqProvider,
+        ProviderKind::Gemini => &GeminiProvider,
+        ProviderKind::Local => &LocalProvider,
+    }
+}
+
+#[async_trait]
+impl LlmProvider for GroqProvider {
+    fn name(&self) -> &'static str {
+        "groq"
+    }
+
+    async fn complete(&self, step: &ModelStep, request: &LlmRequest<'_>) -> Result<Completion, LlmError> {
+        let api_key = api_key("GROQ_API_KEY")?;
+
+        let body = openai_body(step, request, "max_completion_tokens");
+        let text = post_json("https://api.groq.com/openai/v1/chat/completions", Some(&api_key), &body).await?;
+
+        let groq_response: GroqResponse =
+            serde_json::from_str(&text).map_err(|e| LlmError::InvalidResponse(e.to_string()))?;
+
+        openai_completion(&groq_response)
+    }
+}
+
+#[async_trait]
+impl LlmProvider for GeminiProvider {
+    fn name(&self) -> &'static str {
+        "gemini"
+    }
+
+    async fn complete(&self, step: &ModelStep, request: &LlmRequest<'_>) -> Result<Completion, LlmError> {
+        let api_key = api_key("GEMINI_API_KEY")?;
+
+        let url = format!(
+            "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
+            step.model, api_key
+        );
+
+        let text = post_json(&url, None, &gemini_body(step, request)).await?;
+
+        let gemini_response: GeminiResponse =
+            serde_json::from_str(&text).map_err(|e| LlmError::InvalidResponse(e.to_string()))?;
+
+        let text = extract_ai_text(&gemini_response).map_err(LlmError::InvalidResponse)?;
+        let usage = gemini_response
+            .usage_metadata
+            .as_ref()
+            .map(|u| TokenUsage { prompt_tokens: u.prompt_token_count, completion_tokens: u.candidates_token_count })
+            .unwrap_or_default();
+
+        Ok(Completion { text: text.to_string(), usage })
+    }
+}
+
+/// Appended to the prompt when the server can't enforce JSON itself
+const JSON_ONLY_INSTRUCTION: &str =
+    "\n\nRespond with ONE valid JSON object only. No markdown, no code fences, no explanation.";
+
+#[async_trait]
+impl LlmProvider for LocalProvider {
+    fn name(&self) -> &'static str {
+        "local"
+    }
+
+    async fn complete(&self, step: &ModelStep, request: &LlmRequest<'_>) -> Result<Completion, LlmError> {
+        let base_url = std::env::var("LOCAL_LLM_URL").unwrap_or_else(|_| "http://localhost:11434/v1".to_string());
+        let url = format!("{}/chat/completions", base_url.trim_end_matches('/'));
+
+        // LOCAL_LLM_JSON_MODE=prompt skips response_format for servers known not to support it
+        let native_json = std::env::var("LOCAL_LLM_JSON_MODE")
+            .map(|mode| mode.trim() != "prompt")
+            .unwrap_or(true);
+
+        if !request.json_mode {
+            return local_chat(&url, &openai_body(step, request, "max_tokens")).await;
+        }
+
+        if native_json {
+            match local_chat(&url, &openai_body(step, request, "max_tokens")).await {
+                Ok(completion) => return json_only(completion),
+                // Server rejected response_format → fall through to prompt-only JSON
+                Err(LlmError::Status(400 | 422 | 501, body)) => {
+                    eprintln!("│ ⚠️  {} has no native JSON mode ({}), asking via prompt", step.model, body);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        let prompt = format!("{}{}", request.prompt, JSON_ONLY_INSTRUCTION);
+        let fallback = LlmRequest { prompt: &prompt, json_mode: false, ..*request };
+        json_only(local_chat(&url, &openai_body(step, &fallback, "max_tokens")).await?)
+    }
+}
+
+fn json_only(completion: Completion) -> Result<Completion, LlmError> {
+    let text = extract_json_object(&completion.text)
+        .ok_or_else(|| LlmError::InvalidResponse("No JSON object in answer".to_string()))?;
+    Ok(Completion { text, ..completion })
+}
+
+async fn local_chat(url: &str, body: &Value) -> Result<Completion, LlmError> {
+    let api_key = std::env::var("LOCAL_LLM_API_KEY").ok();
+    let text = post_json(url, api_key.as_deref(), body).await?;
+
+    // Same response shape as Groq (OpenAI chat completion)
+    let chat_response: GroqResponse =
+        serde_json::from_str(&text).map_err(|e| LlmError::InvalidResponse(e.to_string()))?;
+
+    openai_completion(&chat_response)
+}
+
+fn openai_completion(response: &GroqResponse) -> Result<Completion, LlmError> {
+    let text = extract_groq_text(response).map_err(LlmError::InvalidResponse)?;
+    let usage = response
+        .usage
+        .as_ref()
+        .map(|u| TokenUsage { prompt_tokens: u.prompt_tokens, completion_tokens: u.completion_tokens })
+        .unwrap_or_default();
+
+    Ok(Completion { text, usage })
+}
+
+/// Pull the JSON object out of a chatty answer (code fences, "Here is the JSON: {...}")
+fn extract_json_object(text: &str) -> Option<String> {
+    let trimmed = text.trim();
+    if serde_json::from_str::<serde_json::Map<String, Value>>(trimmed).is_ok() {
+        return Some(trimmed.to_string());
+    }
+
+    let start = trimmed.find('{')?;
+    let end = trimmed.rfind('}')?;
+    let candidate = trimmed.get(start..=end)?;
+
+    serde_json::from_str::<serde_json::Map<String, Value>>(candidate)
+        .ok()
+        .map(|_| candidate.to_string())
+}
+
+/// Replaying a cassette needs no credentials
+fn api_key(var: &'static str) -> Result<String, LlmError> {
+    if cassette::replaying() {
+        return Ok(String::new());
+    }
+    std::env::var(var).map_err(|_| LlmError::NotConfigured(var))
+}
+
+/// POST a JSON body and return the response body of a 2xx answer.
+/// Goes through the record/replay cassette when one is installed.
+async fn post_json(url: &str, bearer: Option<&str>, body: &Value) -> Result<String, LlmError> {
+    let cassette = cassette::active();
+    let key = cassette::redact_url(url);
+
+    let (status, retry_after, text) = match cassette.as_deref() {
+        Some(c) if c.mode() == CassetteMode::Replay => c
+            .find(key, body)
+            .ok_or_else(|| LlmError::Request(format!("No recorded response for {}", key)))?,
+        _ => {
+            let mut http = reqwest::Client::new().post(url).header("Content-Type", "application/json");
+            if let Some(token) = bearer {
+                http = http.header("Authorization", format!("Bearer {}", token));
+            }
+
+            let response = http.json(body).send().await.map_err(|e| LlmError::Request(e.to_string()))?;
+            let status = response.status().as_u16();
+            // Retry-After in seconds (the HTTP-date form isn't used by these APIs)
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse::<u64>().ok());
+            let text = response.text().await.map_err(|e| LlmError::Request(e.to_string()))?;
+
+            if let Some(c) = cassette {
+                c.store(key, body, status, retry_after, &text);
+            }
+            (status, retry_after, text)
+        }
+    };
+
+    check_status(status, retry_after, text)
+}
+
+fn check_status(status: u16, retry_after: Option<u64>, text: String) -> Result<String, LlmError> {
+    if status == 429 {
+        let wait = retry_after.or_else(|| retry_delay_in_body(&text));
+        return Err(LlmError::RateLimited(wait.map(Duration::from_secs)));
+    }
+    if !(200..300).contains(&status) {
+        let body: String = text.chars().take(200).collect();
+        return Err(LlmError::Status(status, body));
+    }
+
+    Ok(text)
+}
+
+/// Gemini puts the wait in the error body: `{"error": {"details": [{"retryDelay": "31s"}]}}`
+fn retry_delay_in_body(text: &str) -> Option<u64> {
+    let body: Value = serde_json::from_str(text).ok()?;
+    body["error"]["details"]
+        .as_array()?
+        .iter()
+        .find_map(|detail| detail["retryDelay"].as_str())
+        .and_then(|delay| delay.trim_end_matches('s').parse::<f64>().ok())
+        .map(|secs| secs.ceil() as u64)
+}
+
+/// OpenAI-compatible chat completion body. Groq wants `max_completion_tokens`,
+/// most local servers still only read `max_tokens`
+fn openai_body(step: &ModelStep, request: &LlmRequest<'_>, tokens_field: &str) -> Value {
+    let content = match request.image_base64 {
+        Some(image) => json!([
+            {"type": "text", "text": request.prompt},
+            {"type": "image_url", "image_url": {"url": format!("data:image/jpeg;base64,{}", image)}}
+        ]),
+        None => json!(request.prompt),
+    };
+
+    let mut body = json!({
+        "model": step.model,
+        "messages": [{"role": "user", "content": content}],
+        "temperature": step.temperature,
+    });
+    body[tokens_field] = json!(step.max_tokens);
+    if let Some(top_p) = step.top_p {
+        body["top_p"] = json!(top_p);
+    }
+    if request.json_mode {
+        body["response_format"] = json!({"type": "json_object"});
+    }
+    body
+}
+
+fn gemini_body(step: &ModelStep, request: &LlmRequest<'_>) -> Value {
+    let mut parts = vec![json!({"text": request.prompt})];
+    if let Some(image) = request.image_base64 {
+        parts.push(json!({"inline_data": {"mime_type": "image/jpeg", "data": image}}));
+    }
+
+    let mut generation_config = json!({
+        "temperature": step.temperature,
+        "maxOutputTokens": step.max_tokens,
+    });
+    if let Some(top_p) = step.top_p {
+        generation_config["topP"] = json!(top_p);
+    }
+    if request.json_mode {
+        generation_config["responseMimeType"] = json!("application/json");
+    }
+
+    json!({
+        "contents": [{"parts": parts}],
+        "generationConfig": generation_config,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(provider: ProviderKind) -> ModelStep {
+        ModelStep { provider, model: "m".to_string(), temperature: 0.6, max_tokens: 8192, top_p: Some(0.95) }
+    }
+
+    #[test]
+    fn test_openai_body() {
+        let request = LlmRequest { prompt: "hi", image_base64: None, json_mode: true, prompt_version: None };
+        let body = openai_body(&step(ProviderKind::Groq), &request, "max_completion_tokens");
+
+        assert_eq!(body["messages"][0]["content"], "hi");
+        assert_eq!(body["max_completion_tokens"], 8192);
+        assert_eq!(body["response_format"]["type"], "json_object");
+        assert!(body["top_p"].is_number());
+
+        let vision = LlmRequest { prompt: "hi", image_base64: Some("AAAA"), json_mode: false, prompt_version: None };
+        let body = openai_body(&step(ProviderKind::Local), &vision, "max_tokens");
+        assert_eq!(body["messages"][0]["content"][1]["image_url"]["url"], "data:image/jpeg;base64,AAAA");
+        assert_eq!(body["max_tokens"], 8192);
+        assert!(body.get("response_format").is_none());
+        assert!(body.get("max_completion_tokens").is_none());
+    }
+
+    #[test]
+    fn test_extract_json_object() {
+        assert_eq!(extract_json_object(r#" {"a": 1} "#), Some(r#"{"a": 1}"#.to_string()));
+        assert_eq!(
+            extract_json_object("Here you go:\n```json\n{\"type\": \"unrecognized\"}\n```"),
+            Some(r#"{"type": "unrecognized"}"#.to_string())
+        );
+        assert_eq!(extract_json_object("no json here"), None);
+        assert_eq!(extract_json_object("{broken"), None);
+    }
+
+    #[test]
+    fn test_rate_limit_wait() {
+        assert_eq!(check_status(429, Some(7), String::new()), Err(LlmError::RateLimited(Some(Duration::from_secs(7)))));
+        assert_eq!(check_status(429, None, "slow down".to_string()), Err(LlmError::RateLimited(None)));
+
+        let gemini = r#"{"error": {"code": 429, "details": [
+            {"@type": "type.googleapis.com/google.rpc.QuotaFailure"},
+            {"@type": "type.googleapis.com/google.rpc.RetryInfo", "retryDelay": "30.5s"}
+        ]}}"#;
+        assert_eq!(check_status(429, None, gemini.to_string()), Err(LlmError::RateLimited(Some(Duration::from_secs(31)))));
+        assert_eq!(check_status(200, None, "ok".to_string()), Ok("ok".to_string()));
+    }
+
+    #[test]
+    fn test_gemini_body() {
+        let request = LlmRequest { prompt: "hi", image_base64: Some("AAAA"), json_mode: true, prompt_version: None };
+        let body = gemini_body(&step(ProviderKind::Gemini), &request);
+
+        assert_eq!(body["contents"][0]["parts"][0]["text"], "hi");
+        assert_eq!(body["contents"][0]["parts"][1]["inline_data"]["data"], "AAAA");
+        assert_eq!(body["generationConfig"]["responseMimeType"], "application/json");
+        assert_eq!(body["generationConfig"]["maxOutputTokens"], 8192);
+    }
+}
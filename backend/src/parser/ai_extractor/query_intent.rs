@@ -0,0 +1,234 @@
+// backend/src/parser/ai_extractor/query_intent.rs
+//
+// Intent & slot extraction for natural-language questions in private chats
+// ("tugas apa aja besok?", "kapan deadline LKP 5 pemrog?"). The LLM only
+// decides WHAT is asked — answers always come from the database.
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use serde::Deserialize;
+
+use super::model_chain::{run_chain, LlmTask};
+use super::provider::LlmRequest;
+
+/// What the user is asking about
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryIntent {
+    /// Tugas dengan deadline pada tanggal tertentu
+    DueOn(NaiveDate),
+    /// Tugas dengan deadline dalam n hari ke depan
+    DueWithin(u32),
+    /// Cari tugas berdasarkan kata kunci (judul / matkul)
+    Search(String),
+    Todo,
+    Overdue,
+    Unknown,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawIntent {
+    intent: String,
+    date: Option<String>,
+    days: Option<u32>,
+    keywords: Option<String>,
+}
+
+/// Classify a DM question. Cheap keyword rules first, then the LLM,
+/// then a keyword-search fallback when no model is reachable.
+pub async fn extract_query_intent(text: &str, today: NaiveDate) -> QueryIntent {
+    if let Some(intent) = rule_based_intent(text, today) {
+        println!("🧭 Query intent (rules): {:?}", intent);
+        return intent;
+    }
+
+    match call_intent_ai(text, today).await {
+        Ok(intent) => {
+            println!("🧭 Query intent (AI): {:?}", intent);
+            intent
+        }
+        Err(e) => {
+            eprintln!("⚠️ Query intent AI failed: {}", e);
+            fallback_intent(text)
+        }
+    }
+}
+
+async fn call_intent_ai(text: &str, today: NaiveDate) -> Result<QueryIntent, String> {
+    let prompt = format!(
+        r#"You classify questions that students send to an academic assignment bot (Indonesian or English).
+
+TODAY: {} ({:?})
+QUESTION: "{}"
+
+INTENTS:
+• "due_date": assignments due on ONE specific day ("besok", "hari jumat", "tanggal 20") → set "date"
+• "due_range": assignments due within the next N days ("minggu ini", "3 hari lagi") → set "days"
+• "search": a specific assignment or course ("kapan deadline LKP 5 pemrog?") → set "keywords"
+• "todo": all pending assignments ("tugasku apa aja?")
+• "overdue": missed / late assignments ("ada yang telat?")
+• "none": anything else (greetings, unrelated questions)
+
+RULES:
+• "date" is YYYY-MM-DD, resolved relative to TODAY; weekdays mean the next occurrence
+• "keywords" keeps assignment names, numbers and course names/aliases exactly as written; drop question words
+• Never answer the question yourself
+
+Return JSON:
+{{"intent": string, "date": string | null, "days": number | null, "keywords": string | null}}"#,
+        today.format("%Y-%m-%d"),
+        today.weekday(),
+        text.replace('"', "'")
+    );
+
+    let request = LlmRequest { prompt: &prompt, image_base64: None, json_mode: true, prompt_version: None };
+    run_chain(LlmTask::Query, &request, parse_intent_json).await
+}
+
+/// Map the model's JSON onto a `QueryIntent`, rejecting missing slots
+pub(crate) fn parse_intent_json(json_text: &str) -> Result<QueryIntent, String> {
+    let raw: RawIntent = serde_json::from_str(json_text)
+        .map_err(|e| format!("Failed to parse query intent: {}", e))?;
+
+    let intent = match raw.intent.as_str() {
+        "due_date" => {
+            let date = raw.date.ok_or("due_date without date")?;
+            let date = NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+                .map_err(|e| format!("Invalid date '{}': {}", date, e))?;
+            QueryIntent::DueOn(date)
+        }
+        "due_range" => QueryIntent::DueWithin(raw.days.ok_or("due_range without days")?.clamp(1, 31)),
+        "search" => {
+            let keywords = raw.keywords.map(|k| k.trim().to_string()).unwrap_or_default();
+            if keywords.is_empty() {
+                return Err("search without keywords".to_string());
+            }
+            QueryIntent::Search(keywords)
+        }
+        "todo" => QueryIntent::Todo,
+        "overdue" => QueryIntent::Overdue,
+        _ => QueryIntent::Unknown,
+    };
+
+    Ok(intent)
+}
+
+/// Unambiguous phrasings that don't need the LLM
+pub(crate) fn rule_based_intent(text: &str, today: NaiveDate) -> Option<QueryIntent> {
+    let lower = text.to_lowercase();
+    let words = words_of(&lower);
+    let has = |w: &str| words.contains(&w);
+
+    let asks_about_tasks = ["tugas", "deadline", "dl", "tugasku", "pr"].iter().any(|w| has(w));
+    if !asks_about_tasks {
+        return None;
+    }
+
+    if ["telat", "terlewat", "overdue", "kelewat"].iter().any(|w| has(w)) {
+        return Some(QueryIntent::Overdue);
+    }
+    if lower.contains("hari ini") || has("today") {
+        return Some(QueryIntent::DueOn(today));
+    }
+    if has("besok") || has("tomorrow") {
+        return Some(QueryIntent::DueOn(today + Duration::days(1)));
+    }
+    if has("lusa") {
+        return Some(QueryIntent::DueOn(today + Duration::days(2)));
+    }
+    if lower.contains("minggu ini") || has("seminggu") {
+        return Some(QueryIntent::DueWithin(7));
+    }
+    if let Some(weekday) = words.iter().find_map(|w| parse_weekday(w)) {
+        let ahead = (weekday.num_days_from_monday() as i64 - today.weekday().num_days_from_monday() as i64)
+            .rem_euclid(7);
+        return Some(QueryIntent::DueOn(today + Duration::days(ahead)));
+    }
+
+    None
+}
+
+/// Without an LLM: questions about a task become a keyword search
+pub(crate) fn fallback_intent(text: &str) -> QueryIntent {
+    const STOPWORDS: &[&str] = &[
+        "kapan", "deadline", "dl", "tugas", "apa", "aja", "saja", "ya", "yg", "yang", "ada",
+        "dong", "nih", "sih", "kah", "buat", "untuk", "the", "when", "is", "min", "bot",
+    ];
+
+    let lower = text.to_lowercase();
+    let words = words_of(&lower);
+    if !words.iter().any(|w| matches!(*w, "kapan" | "deadline" | "dl" | "tugas" | "when")) {
+        return QueryIntent::Unknown;
+    }
+
+    let keywords: Vec<&str> = words.into_iter().filter(|w| !STOPWORDS.contains(w)).collect();
+    if keywords.is_empty() {
+        QueryIntent::Todo
+    } else {
+        QueryIntent::Search(keywords.join(" "))
+    }
+}
+
+fn words_of(lower: &str) -> Vec<&str> {
+    lower
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+pub(super) fn parse_weekday(word: &str) -> Option<Weekday> {
+    match word {
+        "senin" => Some(Weekday::Mon),
+        "selasa" => Some(Weekday::Tue),
+        "rabu" => Some(Weekday::Wed),
+        "kamis" => Some(Weekday::Thu),
+        "jumat" => Some(Weekday::Fri),
+        "sabtu" => Some(Weekday::Sat),
+        // "minggu" sengaja tidak: bentrok dengan "minggu ini/depan"
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn today() -> NaiveDate {
+        // Minggu, 18 Okt 2026
+        NaiveDate::from_ymd_opt(2026, 10, 18).unwrap()
+    }
+
+    #[test]
+    fn test_rule_based_intent() {
+        let d = |day| NaiveDate::from_ymd_opt(2026, 10, day).unwrap();
+
+        assert_eq!(rule_based_intent("tugas apa aja besok?", today()), Some(QueryIntent::DueOn(d(19))));
+        assert_eq!(rule_based_intent("deadline hari ini apa?", today()), Some(QueryIntent::DueOn(d(18))));
+        assert_eq!(rule_based_intent("ada tugas minggu ini?", today()), Some(QueryIntent::DueWithin(7)));
+        assert_eq!(rule_based_intent("tugas hari senin apa", today()), Some(QueryIntent::DueOn(d(19))));
+        assert_eq!(rule_based_intent("tugas yang telat apa aja", today()), Some(QueryIntent::Overdue));
+        assert_eq!(rule_based_intent("kapan deadline LKP 5 pemrog?", today()), None);
+        assert_eq!(rule_based_intent("besok libur ga?", today()), None);
+    }
+
+    #[test]
+    fn test_parse_intent_json() {
+        assert_eq!(
+            parse_intent_json(r#"{"intent":"due_date","date":"2026-10-23","days":null,"keywords":null}"#),
+            Ok(QueryIntent::DueOn(NaiveDate::from_ymd_opt(2026, 10, 23).unwrap()))
+        );
+        assert_eq!(
+            parse_intent_json(r#"{"intent":"search","keywords":"LKP 5 pemrog"}"#),
+            Ok(QueryIntent::Search("LKP 5 pemrog".to_string()))
+        );
+        assert_eq!(parse_intent_json(r#"{"intent":"due_range","days":90}"#), Ok(QueryIntent::DueWithin(31)));
+        assert_eq!(parse_intent_json(r#"{"intent":"none"}"#), Ok(QueryIntent::Unknown));
+        assert!(parse_intent_json(r#"{"intent":"due_date","date":"besok"}"#).is_err());
+        assert!(parse_intent_json(r#"{"intent":"search","keywords":" "}"#).is_err());
+    }
+
+    #[test]
+    fn test_fallback_intent() {
+        assert_eq!(fallback_intent("kapan deadline LKP 5 pemrog?"), QueryIntent::Search("lkp 5 pemrog".to_string()));
+        assert_eq!(fallback_intent("tugas apa aja?"), QueryIntent::Todo);
+        assert_eq!(fallback_intent("halo bot"), QueryIntent::Unknown);
+    }
+}
@@ -0,0 +1,174 @@
+// backend/src/parser/ai_extractor/rule_based.rs
+//
+// Last tier of `extract_with_ai`: when every model in the chain fails, read
+// the announcement with plain rules (course names/aliases, assignment type,
+// parallel code, dates via date_resolver) instead of dropping it. The result is
+// marked `low_confidence` so it always goes through the clarification flow.
+
+use chrono::NaiveDateTime;
+
+use super::date_resolver::resolve_deadline;
+use crate::clarification::detect_parallel_code;
+use crate::models::{AIClassification, Course, FieldConfidence};
+
+/// Assignment keywords → title prefix. Longer / more specific first.
+const ASSIGNMENT_TYPES: &[(&str, &str)] = &[
+    ("lkp", "LKP"),
+    ("kuis", "Kuis"),
+    ("quiz", "Kuis"),
+    ("uts", "UTS"),
+    ("uas", "UAS"),
+    ("praktikum", "Praktikum"),
+    ("responsi", "Responsi"),
+    ("laporan", "Laporan"),
+    ("makalah", "Makalah"),
+    ("presentasi", "Presentasi"),
+    ("proyek", "Proyek"),
+    ("project", "Proyek"),
+    ("tugas", "Tugas"),
+    ("pr", "PR"),
+];
+
+/// Read an announcement without any LLM. `now` is WIB.
+pub fn extract_rule_based(text: &str, courses: &[Course], now: NaiveDateTime) -> AIClassification {
+    let lower = text.to_lowercase();
+    let words = words_of(&lower);
+
+    let Some(title) = detect_title(&words) else {
+        return AIClassification::Unrecognized;
+    };
+
+    // Assignments are stored per course — without one there's nothing to clarify
+    let Some(course_name) = match_course(&words, courses) else {
+        return AIClassification::Unrecognized;
+    };
+
+    AIClassification::AssignmentInfo {
+        course_name: Some(course_name),
+        title,
+        deadline: resolve_deadline(text, now, None).map(|d| d.canonical()),
+        description: Some(text.trim().to_string()),
+        parallel_code: detect_parallel_code(text),
+        confidence: FieldConfidence::default(),
+        original_message: Some(text.to_string()),
+        low_confidence: true,
+    }
+}
+
+fn words_of(lower: &str) -> Vec<&str> {
+    lower
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+/// Longest course name or alias that appears as whole words
+fn match_course(words: &[&str], courses: &[Course]) -> Option<String> {
+    let haystack = format!(" {} ", words.join(" "));
+
+    courses
+        .iter()
+        .flat_map(|course| {
+            std::iter::once(course.name.as_str())
+                .chain(course.aliases.iter().flatten().map(String::as_str))
+                .map(move |candidate| (course, candidate))
+        })
+        .filter_map(|(course, candidate)| {
+            let needle = words_of(&candidate.to_lowercase()).join(" ");
+            (needle.len() >= 2 && haystack.contains(&format!(" {} ", needle)))
+                .then_some((needle.len(), course))
+        })
+        .max_by_key(|(len, _)| *len)
+        .map(|(_, course)| course.name.clone())
+}
+
+/// "LKP 5", "lkp5", "kuis 2" → "LKP 5", "LKP 5", "Kuis 2"; "tugas" alone → "Tugas"
+fn detect_title(words: &[&str]) -> Option<String> {
+    for (keyword, label) in ASSIGNMENT_TYPES {
+        for (i, word) in words.iter().enumerate() {
+            let Some(rest) = word.strip_prefix(keyword) else { continue };
+
+            if rest.is_empty() {
+                let number = words
+                    .get(i + 1)
+                    .filter(|next| next.len() <= 3 && next.chars().all(|c| c.is_ascii_digit()));
+                return Some(match number {
+                    Some(n) => format!("{} {}", label, n),
+                    None => label.to_string(),
+                });
+            }
+            if rest.chars().all(|c| c.is_ascii_digit()) && rest.len() <= 3 {
+                return Some(format!("{} {}", label, rest));
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, Utc};
+    use uuid::Uuid;
+
+    fn courses() -> Vec<Course> {
+        let course = |name: &str, aliases: &[&str]| Course {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            aliases: Some(aliases.iter().map(|a| a.to_string()).collect()),
+            created_at: Utc::now(),
+        };
+        vec![
+            course("Pemrograman", &["pemrog", "prog"]),
+            course("Struktur Data", &["strukdat", "sd"]),
+            course("Basis Data", &["basdat"]),
+        ]
+    }
+
+    fn now() -> NaiveDateTime {
+        // Minggu, 18 Okt 2026 10:00 WIB
+        NaiveDate::from_ymd_opt(2026, 10, 18).unwrap().and_hms_opt(10, 0, 0).unwrap()
+    }
+
+    fn extract(text: &str) -> AIClassification {
+        extract_rule_based(text, &courses(), now())
+    }
+
+    #[test]
+    fn test_extract_full_announcement() {
+        match extract("Info LKP 5 Pemrog K2 dikumpulkan tanggal 25 Oktober jam 23.59 ya") {
+            AIClassification::AssignmentInfo { course_name, title, deadline, parallel_code, low_confidence, .. } => {
+                assert_eq!(course_name.as_deref(), Some("Pemrograman"));
+                assert_eq!(title, "LKP 5");
+                assert_eq!(deadline.as_deref(), Some("2026-10-25 23:59"));
+                assert_eq!(parallel_code.as_deref(), Some("k2"));
+                assert!(low_confidence);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_extract_relative_dates_and_aliases() {
+        let deadline_of = |text: &str| match extract(text) {
+            AIClassification::AssignmentInfo { deadline, course_name, .. } => (course_name, deadline),
+            other => panic!("unexpected {:?}", other),
+        };
+
+        assert_eq!(
+            deadline_of("kuis2 strukdat besok pukul 8"),
+            (Some("Struktur Data".to_string()), Some("2026-10-19 08:00".to_string()))
+        );
+        assert_eq!(deadline_of("tugas basis data kumpul jumat").1.as_deref(), Some("2026-10-23 23:59"));
+        assert_eq!(deadline_of("laporan basdat deadline 3/1").1.as_deref(), Some("2027-01-03 23:59"));
+        assert_eq!(deadline_of("tugas pemrog").1, None);
+    }
+
+    #[test]
+    fn test_extract_needs_type_and_course() {
+        assert!(matches!(extract("besok kelas pemrog diganti online"), AIClassification::Unrecognized));
+        assert!(matches!(extract("LKP 5 dikumpulkan besok"), AIClassification::Unrecognized));
+        // "sd" must be a whole word, not part of "asdos"
+        assert!(matches!(extract("tugas dari asdos besok"), AIClassification::Unrecognized));
+    }
+}
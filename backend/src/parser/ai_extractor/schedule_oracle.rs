@@ -1,6 +1,6 @@
 // backend/src/parser/ai_extractor/schedule_oracle.rs
 
-use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
 use serde::Deserialize;
 use std::collections::HashMap;
 
@@ -28,6 +28,17 @@ struct CourseSchedule {
 pub struct ScheduleOracle {
     // Map: (course_code, parallel) -> Vec<(Weekday, start_time)>
     schedules: HashMap<(String, String), Vec<(Weekday, String)>>,
+    // Map: course_code -> course name (e.g., "KOM120H" -> "Struktur Data")
+    course_names: HashMap<String, String>,
+}
+
+/// One class meeting in the weekly timetable
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassSlot {
+    pub course_code: String,
+    pub course_name: String,
+    pub parallel: String,
+    pub start_time: String,
 }
 
 impl ScheduleOracle {
@@ -36,19 +47,35 @@ impl ScheduleOracle {
         let content = std::fs::read_to_string(path)
             .map_err(|e| format!("Failed to read schedule file: {}", e))?;
         
-        let data: ScheduleData = serde_json::from_str(&content)
+        Self::from_json(&content)
+    }
+    
+    /// Parse schedule JSON (same format as schedule.json)
+    pub fn from_json(content: &str) -> Result<Self, String> {
+        let data: ScheduleData = serde_json::from_str(content)
             .map_err(|e| format!("Failed to parse schedule JSON: {}", e))?;
         
         let mut schedules: HashMap<(String, String), Vec<(Weekday, String)>> = HashMap::new();
+        let mut course_names: HashMap<String, String> = HashMap::new();
         
         // Process each day
-        Self::process_day(&mut schedules, &data.senin, Weekday::Mon);
-        Self::process_day(&mut schedules, &data.selasa, Weekday::Tue);
-        Self::process_day(&mut schedules, &data.rabu, Weekday::Wed);
-        Self::process_day(&mut schedules, &data.kamis, Weekday::Thu);
-        Self::process_day(&mut schedules, &data.jumat, Weekday::Fri);
+        for (day, weekday) in [
+            (&data.senin, Weekday::Mon),
+            (&data.selasa, Weekday::Tue),
+            (&data.rabu, Weekday::Wed),
+            (&data.kamis, Weekday::Thu),
+            (&data.jumat, Weekday::Fri),
+        ] {
+            Self::process_day(&mut schedules, day, weekday);
+            
+            for schedule in day {
+                if let Some((code, name)) = schedule.course.split_once(" - ") {
+                    course_names.insert(code.trim().to_string(), name.trim().to_string());
+                }
+            }
+        }
         
-        Ok(Self { schedules })
+        Ok(Self { schedules, course_names })
     }
     
     fn process_day(
@@ -76,7 +103,7 @@ impl ScheduleOracle {
             let key = (course_code, schedule.parallel.to_lowercase());
             schedules
                 .entry(key)
-                .or_insert_with(Vec::new)
+                .or_default()
                 .push((weekday, start_time));
         }
     }
@@ -118,6 +145,35 @@ impl ScheduleOracle {
         next_meetings.into_iter().next()
     }
     
+    /// Next meeting after `now`: the earliest of today's meetings that hasn't started yet
+    /// (e.g. a 13:00 practicum after the 08:00 lecture), otherwise the first one on a later day
+    pub fn next_meeting_after(
+        &self,
+        course_name: &str,
+        parallel_code: &str,
+        now: NaiveDateTime,
+    ) -> Option<(NaiveDate, String)> {
+        let today = now.date();
+        let parallel_lower = parallel_code.to_lowercase();
+        
+        let later_today = self.schedules
+            .iter()
+            .filter(|((code, parallel), _)| parallel == &parallel_lower && Self::course_matches(code, course_name))
+            .flat_map(|(_, times)| times.iter())
+            .filter(|(weekday, time)| {
+                *weekday == today.weekday()
+                    && NaiveTime::parse_from_str(time, "%H:%M").map(|t| t > now.time()).unwrap_or(false)
+            })
+            .map(|(_, time)| time.clone())
+            .min();
+        
+        match later_today {
+            Some(time) => Some((today, time)),
+            // get_next_meeting_with_time skips from_date itself
+            None => self.get_next_meeting_with_time(course_name, parallel_code, today),
+        }
+    }
+    
     /// Get next meeting for a course and parallel (date only - backward compatible)
     pub fn get_next_meeting(
         &self,
@@ -130,7 +186,7 @@ impl ScheduleOracle {
     }
     
     /// Check if course code matches course name
-    fn course_matches(course_code: &str, course_name: &str) -> bool {
+    pub fn course_matches(course_code: &str, course_name: &str) -> bool {
         let name_lower = course_name.to_lowercase();
         
         // Map course codes to names (based on your data)
@@ -173,6 +229,52 @@ impl ScheduleOracle {
         }
     }
     
+    /// All classes on a given weekday, sorted by start time
+    pub fn classes_on(&self, weekday: Weekday) -> Vec<ClassSlot> {
+        let mut slots: Vec<ClassSlot> = self.schedules
+            .iter()
+            .flat_map(|((code, parallel), times)| {
+                times
+                    .iter()
+                    .filter(move |(day, _)| *day == weekday)
+                    .map(move |(_, start)| ClassSlot {
+                        course_code: code.clone(),
+                        course_name: self.course_name(code).to_string(),
+                        parallel: parallel.clone(),
+                        start_time: start.clone(),
+                    })
+            })
+            .collect();
+        
+        slots.sort_by(|a, b| {
+            a.start_time.cmp(&b.start_time)
+                .then_with(|| a.course_name.cmp(&b.course_name))
+                .then_with(|| a.parallel.cmp(&b.parallel))
+        });
+        slots
+    }
+    
+    /// Parallels (lowercase) that have meetings for this course
+    pub fn parallels_for_course(&self, course_name: &str) -> Vec<String> {
+        let mut parallels: Vec<String> = self.schedules
+            .keys()
+            .filter(|(code, _)| Self::course_matches(code, course_name))
+            .map(|(_, parallel)| parallel.clone())
+            .collect();
+        
+        parallels.sort();
+        parallels.dedup();
+        parallels
+    }
+    
+    /// Course name for a code, falls back to the code itself
+    pub fn course_name<'a>(&'a self, course_code: &'a str) -> &'a str {
+        self.course_names
+            .get(course_code)
+            .map(|name| name.as_str())
+            .unwrap_or(course_code)
+    }
+    
     /// Get all schedule info for debugging
     pub fn get_schedule_for_course(
         &self,
@@ -207,6 +309,68 @@ mod tests {
         assert_eq!(ScheduleOracle::days_until_weekday(Weekday::Mon, Weekday::Mon), 7);
     }
     
+    #[test]
+    fn test_classes_on() {
+        let oracle = ScheduleOracle::from_json(r#"{
+            "Senin": [
+                {"course": "KOM120H - Struktur Data", "parallel": "K1", "schedule": "10:00-11:40"},
+                {"course": "KOM1231 - Rekayasa Perangkat Lunak", "parallel": "R2", "schedule": "08:00-09:40"}
+            ],
+            "Selasa": [{"course": "KOM120H - Struktur Data", "parallel": "P3", "schedule": "13:00-15:00"}],
+            "Rabu": [], "Kamis": [], "Jumat": []
+        }"#).unwrap();
+        
+        let monday = oracle.classes_on(Weekday::Mon);
+        assert_eq!(monday.len(), 2);
+        assert_eq!(monday[0].course_name, "Rekayasa Perangkat Lunak");
+        assert_eq!(monday[1].parallel, "k1");
+        assert!(oracle.classes_on(Weekday::Sat).is_empty());
+        
+        assert_eq!(oracle.parallels_for_course("Strukdat"), vec!["k1", "p3"]);
+    }
+    
+    #[test]
+    fn test_next_meeting_after_includes_today_until_it_starts() {
+        let oracle = ScheduleOracle::from_json(r#"{
+            "Senin": [{"course": "KOM120H - Struktur Data", "parallel": "K1", "schedule": "10:00-11:40"}],
+            "Selasa": [], "Rabu": [],
+            "Kamis": [{"course": "KOM120H - Struktur Data", "parallel": "K1", "schedule": "08:00-09:40"}],
+            "Jumat": []
+        }"#).unwrap();
+        // Senin 19 Okt 2026
+        let monday = NaiveDate::from_ymd_opt(2026, 10, 19).unwrap();
+        
+        let before = monday.and_hms_opt(9, 0, 0).unwrap();
+        assert_eq!(oracle.next_meeting_after("Struktur Data", "k1", before), Some((monday, "10:00".to_string())));
+        
+        let after = monday.and_hms_opt(10, 30, 0).unwrap();
+        let thursday = NaiveDate::from_ymd_opt(2026, 10, 22).unwrap();
+        assert_eq!(oracle.next_meeting_after("Struktur Data", "k1", after), Some((thursday, "08:00".to_string())));
+    }
+    
+    #[test]
+    fn test_next_meeting_after_finds_second_meeting_same_day() {
+        // Lecture 08:00 and practicum 13:00 for the same parallel on Monday
+        let oracle = ScheduleOracle::from_json(r#"{
+            "Senin": [
+                {"course": "KOM120C - Pemrograman", "parallel": "K1", "schedule": "08:00-09:40"},
+                {"course": "KOM120C - Pemrograman", "parallel": "K1", "schedule": "13:00-15:00"}
+            ],
+            "Selasa": [], "Rabu": [], "Kamis": [], "Jumat": []
+        }"#).unwrap();
+        let monday = NaiveDate::from_ymd_opt(2026, 10, 19).unwrap();
+        
+        let between = monday.and_hms_opt(10, 0, 0).unwrap();
+        assert_eq!(oracle.next_meeting_after("Pemrograman", "k1", between), Some((monday, "13:00".to_string())));
+        
+        let before = monday.and_hms_opt(7, 0, 0).unwrap();
+        assert_eq!(oracle.next_meeting_after("Pemrograman", "k1", before), Some((monday, "08:00".to_string())));
+        
+        let evening = monday.and_hms_opt(16, 0, 0).unwrap();
+        let next_monday = NaiveDate::from_ymd_opt(2026, 10, 26).unwrap();
+        assert_eq!(oracle.next_meeting_after("Pemrograman", "k1", evening), Some((next_monday, "08:00".to_string())));
+    }
+    
     #[test]
     fn test_course_matches() {
         assert!(ScheduleOracle::course_matches("KOM120C", "Pemrograman"));
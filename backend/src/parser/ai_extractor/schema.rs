@@ -0,0 +1,243 @@
+// backend/src/parser/ai_extractor/schema.rs
+//
+// Field-level validation of extraction answers. The field table below mirrors
+// `AIClassification` / `AssignmentData` (models.rs) and adds the checks serde
+// can't do: deadline format, course names that exist, parallel code format.
+// Every problem is reported, so one repair prompt can fix them all.
+
+use serde_json::{Map, Value};
+
+use crate::clarification::is_valid_parallel_code;
+use crate::database::crud::parse_deadline;
+use crate::models::AIClassification;
+
+#[derive(Debug, Clone, Copy)]
+enum Field {
+    Text,
+    OptText,
+    Course,
+    OptCourse,
+    OptDeadline,
+    OptParallel,
+    Keywords,
+    Assignments,
+    Confidence,
+}
+
+const ASSIGNMENT_INFO: &[(&str, Field)] = &[
+    ("course_name", Field::OptCourse),
+    ("title", Field::Text),
+    ("deadline", Field::OptDeadline),
+    ("description", Field::OptText),
+    ("parallel_code", Field::OptParallel),
+    ("confidence", Field::Confidence),
+];
+
+const ASSIGNMENT_DATA: &[(&str, Field)] = &[
+    ("course_name", Field::Course),
+    ("title", Field::Text),
+    ("deadline", Field::OptDeadline),
+    ("description", Field::OptText),
+    ("parallel_code", Field::OptParallel),
+    ("confidence", Field::Confidence),
+];
+
+const MULTIPLE_ASSIGNMENTS: &[(&str, Field)] = &[("assignments", Field::Assignments)];
+
+const ASSIGNMENT_UPDATE: &[(&str, Field)] = &[
+    ("reference_keywords", Field::Keywords),
+    ("changes", Field::Text),
+    ("new_title", Field::OptText),
+    ("new_deadline", Field::OptDeadline),
+    ("new_description", Field::OptText),
+    ("parallel_code", Field::OptParallel),
+];
+
+const TYPES: &[(&str, &[(&str, Field)])] = &[
+    ("assignment_info", ASSIGNMENT_INFO),
+    ("multiple_assignments", MULTIPLE_ASSIGNMENTS),
+    ("assignment_update", ASSIGNMENT_UPDATE),
+    ("unrecognized", &[]),
+];
+
+/// Parse and check an extraction answer. `courses` are the exact course names
+/// the answer may use (no check when empty, e.g. the course list failed to load).
+pub(super) fn validate_classification(ai_text: &str, courses: &[&str]) -> Result<AIClassification, Vec<String>> {
+    let cleaned = ai_text
+        .trim()
+        .trim_start_matches("```json")
+        .trim_start_matches("```")
+        .trim_end_matches("```")
+        .trim();
+
+    let object = match serde_json::from_str::<Value>(cleaned) {
+        Ok(Value::Object(object)) => object,
+        Ok(_) => return Err(vec!["answer must be one JSON object".to_string()]),
+        Err(e) => return Err(vec![format!("answer is not valid JSON ({})", e)]),
+    };
+
+    let kind = object.get("type").and_then(Value::as_str).unwrap_or_default();
+    let Some((_, fields)) = TYPES.iter().find(|(name, _)| *name == kind) else {
+        let names: Vec<&str> = TYPES.iter().map(|(name, _)| *name).collect();
+        return Err(vec![format!("\"type\" must be one of {}", names.join(", "))]);
+    };
+
+    let mut errors = Vec::new();
+    check_fields(&object, fields, "", courses, &mut errors);
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    serde_json::from_value(Value::Object(object)).map_err(|e| vec![e.to_string()])
+}
+
+fn check_fields(object: &Map<String, Value>, fields: &[(&str, Field)], prefix: &str, courses: &[&str], errors: &mut Vec<String>) {
+    for (name, field) in fields {
+        let path = format!("{}{}", prefix, name);
+        let value = object.get(*name).unwrap_or(&Value::Null);
+        if let Err(e) = check_field(value, *field, &path, courses, errors) {
+            errors.push(format!("{}: {}", path, e));
+        }
+    }
+}
+
+/// Problems with nested values are pushed to `errors` directly
+fn check_field(value: &Value, field: Field, path: &str, courses: &[&str], errors: &mut Vec<String>) -> Result<(), String> {
+    let optional = matches!(
+        field,
+        Field::OptText | Field::OptCourse | Field::OptDeadline | Field::OptParallel | Field::Confidence
+    );
+    if value.is_null() {
+        return if optional { Ok(()) } else { Err("is required".to_string()) };
+    }
+
+    match field {
+        Field::Text | Field::OptText => match value.as_str() {
+            Some(text) if !text.trim().is_empty() || optional => Ok(()),
+            Some(_) => Err("must not be empty".to_string()),
+            None => Err("must be a string".to_string()),
+        },
+        Field::Course | Field::OptCourse => {
+            let name = value.as_str().ok_or("must be a string")?;
+            if courses.is_empty() || courses.iter().any(|c| c.eq_ignore_ascii_case(name.trim())) {
+                Ok(())
+            } else {
+                Err(format!("\"{}\" is not in the course list, use the exact name from it", name))
+            }
+        }
+        Field::OptDeadline => {
+            let deadline = value.as_str().ok_or("must be a string or null")?;
+            parse_deadline(deadline)
+                .map(|_| ())
+                .map_err(|_| format!("\"{}\" must be \"YYYY-MM-DD HH:MM\" (WIB) or null", deadline))
+        }
+        Field::OptParallel => {
+            let code = value.as_str().ok_or("must be a string or null")?;
+            if is_valid_parallel_code(&code.trim().to_uppercase()) {
+                Ok(())
+            } else {
+                Err(format!("\"{}\" must be k1-k4, p1-p4, r1-r4, \"all\" or null", code))
+            }
+        }
+        Field::Keywords => match value.as_array() {
+            Some(words) if words.iter().all(Value::is_string) => Ok(()),
+            _ => Err("must be an array of strings".to_string()),
+        },
+        Field::Assignments => {
+            let items = value.as_array().ok_or("must be an array")?;
+            if items.is_empty() {
+                return Err("must not be empty".to_string());
+            }
+            for (i, item) in items.iter().enumerate() {
+                let prefix = format!("{}[{}].", path, i);
+                match item.as_object() {
+                    Some(object) => check_fields(object, ASSIGNMENT_DATA, &prefix, courses, errors),
+                    None => errors.push(format!("{}[{}]: must be an object", path, i)),
+                }
+            }
+            Ok(())
+        }
+        Field::Confidence => {
+            let scores = value.as_object().ok_or("must be an object of per-field scores")?;
+            for (name, score) in scores {
+                if !score.is_null() && !score.as_f64().is_some_and(|s| (0.0..=1.0).contains(&s)) {
+                    errors.push(format!("{}.{}: must be a number from 0 to 1", path, name));
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COURSES: &[&str] = &["KOM120C - Pemrograman", "KOM120H - Struktur Data"];
+
+    #[test]
+    fn test_valid_answers_parse() {
+        let info = r#"```json
+        {"type": "assignment_info", "course_name": "kom120c - pemrograman", "title": "LKP 6",
+         "deadline": "2026-10-21 23:59", "description": null, "parallel_code": "K1",
+         "confidence": {"course_name": 0.7, "title": 1}}
+        ```"#;
+        match validate_classification(info, COURSES) {
+            Ok(AIClassification::AssignmentInfo { confidence, .. }) => {
+                assert_eq!(confidence.course_name, Some(0.7));
+                assert_eq!(confidence.deadline, None);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        let update = r#"{"type": "assignment_update", "reference_keywords": ["LKP 5"], "changes": "diundur",
+            "new_deadline": "2026-10-22 23:59", "parallel_code": null}"#;
+        assert!(matches!(validate_classification(update, COURSES), Ok(AIClassification::AssignmentUpdate { .. })));
+
+        assert!(matches!(validate_classification(r#"{"type": "unrecognized"}"#, COURSES), Ok(AIClassification::Unrecognized)));
+        // No course list → names aren't checked
+        let unknown = r#"{"type": "assignment_info", "course_name": "Kalkulus", "title": "PR 1"}"#;
+        assert!(validate_classification(unknown, &[]).is_ok());
+    }
+
+    #[test]
+    fn test_reports_every_field_error() {
+        let answer = r#"{"type": "assignment_info", "course_name": "Pemrog", "title": "",
+            "deadline": "besok jam 8", "parallel_code": "kelas A", "confidence": {"title": 0.9, "deadline": "high"}}"#;
+
+        assert_eq!(
+            validate_classification(answer, COURSES).unwrap_err(),
+            vec![
+                "course_name: \"Pemrog\" is not in the course list, use the exact name from it".to_string(),
+                "title: must not be empty".to_string(),
+                "deadline: \"besok jam 8\" must be \"YYYY-MM-DD HH:MM\" (WIB) or null".to_string(),
+                "parallel_code: \"kelas A\" must be k1-k4, p1-p4, r1-r4, \"all\" or null".to_string(),
+                "confidence.deadline: must be a number from 0 to 1".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nested_assignments_and_shape_errors() {
+        let answer = r#"{"type": "multiple_assignments", "assignments": [
+            {"course_name": "KOM120H - Struktur Data", "title": "Kuis 2", "deadline": "2026-10-22 08:00"},
+            {"title": "Laporan", "deadline": "22/10"},
+            "LKP 7"
+        ]}"#;
+
+        assert_eq!(
+            validate_classification(answer, COURSES).unwrap_err(),
+            vec![
+                "assignments[1].course_name: is required".to_string(),
+                "assignments[1].deadline: \"22/10\" must be \"YYYY-MM-DD HH:MM\" (WIB) or null".to_string(),
+                "assignments[2]: must be an object".to_string(),
+            ]
+        );
+
+        assert_eq!(validate_classification("Sure! Here it is", COURSES).unwrap_err().len(), 1);
+        assert_eq!(
+            validate_classification(r#"{"type": "reminder"}"#, COURSES).unwrap_err(),
+            vec!["\"type\" must be one of assignment_info, multiple_assignments, assignment_update, unrecognized"]
+        );
+    }
+}
@@ -0,0 +1,327 @@
+// backend/src/parser/ai_extractor/templates.rs
+//
+// Prompt wording lives in backend/prompts/*.txt (directory via PROMPTS_DIR),
+// not in Rust: a header with `version:`, a `---` line, then the body with
+// `{{name}}` placeholders. Files are checked on every use and reloaded when
+// they change; a file that fails validation is ignored (the last good
+// version stays in use). The copies compiled into the binary are the
+// fallback when a file is missing, e.g. in tests and the eval tool.
+
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(super) enum PromptKind {
+    Classification,
+    Matching,
+    DuplicateDetection,
+    ContextResolver,
+}
+
+const ALL: [PromptKind; 4] = [
+    PromptKind::Classification,
+    PromptKind::Matching,
+    PromptKind::DuplicateDetection,
+    PromptKind::ContextResolver,
+];
+
+impl PromptKind {
+    fn name(&self) -> &'static str {
+        match self {
+            PromptKind::Classification => "classification",
+            PromptKind::Matching => "matching",
+            PromptKind::DuplicateDetection => "duplicate_detection",
+            PromptKind::ContextResolver => "context_resolver",
+        }
+    }
+
+    /// Placeholders the template may use; `true` = must use
+    fn variables(&self) -> &'static [(&'static str, bool)] {
+        match self {
+            PromptKind::Classification => &[
+                ("current_datetime", false),
+                ("current_date", false),
+                ("tomorrow", false),
+                ("day_after_tomorrow", false),
+                ("next_week", false),
+                ("message", true),
+                ("image_text", false),
+                ("courses", true),
+                ("assignments", false),
+                ("context_hints", false),
+            ],
+            PromptKind::Matching => &[
+                ("current_time", false),
+                ("changes", true),
+                ("keywords", false),
+                ("parallel_info", false),
+                ("assignments", true),
+            ],
+            PromptKind::DuplicateDetection => &[
+                ("course_name", false),
+                ("title", true),
+                ("description", false),
+                ("parallel_info", false),
+                ("candidates", true),
+            ],
+            PromptKind::ContextResolver => &[
+                ("message", true),
+                ("sender_history", false),
+                ("quoted_section", false),
+                ("courses", true),
+            ],
+        }
+    }
+
+    fn built_in(&self) -> &'static str {
+        match self {
+            PromptKind::Classification => include_str!("../../../prompts/classification.txt"),
+            PromptKind::Matching => include_str!("../../../prompts/matching.txt"),
+            PromptKind::DuplicateDetection => include_str!("../../../prompts/duplicate_detection.txt"),
+            PromptKind::ContextResolver => include_str!("../../../prompts/context_resolver.txt"),
+        }
+    }
+}
+
+/// A prompt ready to send, and the template version it came from
+#[derive(Debug, Clone)]
+pub(super) struct RenderedPrompt {
+    pub text: String,
+    /// "classification@2026-10-18.1+9f1c2ab0"
+    pub version: String,
+}
+
+#[derive(Debug)]
+struct Template {
+    /// Declared version + hash of the body, so an edit without a bump still shows
+    version: String,
+    body: String,
+}
+
+impl Template {
+    fn parse(kind: PromptKind, content: &str) -> Result<Self, String> {
+        let (header, body) = content
+            .split_once("\n---\n")
+            .ok_or("missing \"---\" line between header and body")?;
+        let body = body.trim_end_matches('\n');
+
+        let mut declared = None;
+        for line in header.lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with('#')) {
+            match line.split_once(':') {
+                Some(("version", value)) if !value.trim().is_empty() => declared = Some(value.trim()),
+                _ => return Err(format!("unknown header line \"{}\"", line)),
+            }
+        }
+        let declared = declared.ok_or("header has no \"version:\"")?;
+
+        if body.trim().is_empty() {
+            return Err("body is empty".to_string());
+        }
+
+        let used = placeholders(body)?;
+        let allowed = kind.variables();
+        if let Some(unknown) = used.iter().find(|name| !allowed.iter().any(|(v, _)| v == *name)) {
+            let names: Vec<&str> = allowed.iter().map(|(v, _)| *v).collect();
+            return Err(format!("unknown variable {{{{{}}}}} (allowed: {})", unknown, names.join(", ")));
+        }
+        if let Some((missing, _)) = allowed.iter().find(|(v, required)| *required && !used.contains(v)) {
+            return Err(format!("required variable {{{{{}}}}} is not used", missing));
+        }
+
+        Ok(Template { version: format!("{}+{:08x}", declared, fnv1a(body)), body: body.to_string() })
+    }
+
+    fn render(&self, vars: &[(&str, &str)]) -> String {
+        let mut out = String::with_capacity(self.body.len() + vars.iter().map(|(_, v)| v.len()).sum::<usize>());
+        let mut rest = self.body.as_str();
+
+        // Values are inserted verbatim: a "{{" inside a message is never expanded
+        while let Some(start) = rest.find("{{") {
+            out.push_str(&rest[..start]);
+            let end = rest[start..].find("}}").map(|e| start + e).unwrap_or(rest.len());
+            let name = &rest[start + 2..end];
+            match vars.iter().find(|(v, _)| *v == name) {
+                Some((_, value)) => out.push_str(value),
+                None => eprintln!("⚠️  Prompt variable {{{{{}}}}} has no value", name),
+            }
+            rest = rest.get(end + 2..).unwrap_or_default();
+        }
+        out.push_str(rest);
+        out
+    }
+}
+
+/// Names of every `{{name}}` in `body`
+fn placeholders(body: &str) -> Result<Vec<&str>, String> {
+    let mut names = Vec::new();
+    let mut rest = body;
+
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let end = after.find("}}").ok_or("unclosed \"{{\"")?;
+        let name = &after[..end];
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_lowercase() || c == '_') {
+            return Err(format!("invalid placeholder \"{{{{{}}}}}\"", name));
+        }
+        names.push(name);
+        rest = &after[end + 2..];
+    }
+    Ok(names)
+}
+
+/// Stable across builds and platforms, unlike std's hasher
+fn fnv1a(text: &str) -> u32 {
+    text.bytes().fold(0x811c9dc5, |hash, byte| (hash ^ byte as u32).wrapping_mul(0x01000193))
+}
+
+struct Loaded {
+    template: Arc<Template>,
+    /// mtime of the file this came from (None = built-in, no file)
+    modified: Option<SystemTime>,
+}
+
+#[derive(Default)]
+struct TemplateCache {
+    templates: Mutex<HashMap<PromptKind, Loaded>>,
+}
+
+static TEMPLATES: Lazy<TemplateCache> = Lazy::new(TemplateCache::default);
+
+fn path(kind: PromptKind) -> PathBuf {
+    let dir = std::env::var("PROMPTS_DIR").unwrap_or_else(|_| "prompts".to_string());
+    PathBuf::from(dir).join(format!("{}.txt", kind.name()))
+}
+
+fn built_in(kind: PromptKind) -> Arc<Template> {
+    Arc::new(Template::parse(kind, kind.built_in()).expect("built-in prompt template is valid"))
+}
+
+impl TemplateCache {
+    /// Current template for `kind`, re-reading `path` if it changed since last use
+    fn get(&self, kind: PromptKind, path: &Path) -> Arc<Template> {
+        let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+
+        let mut templates = self.templates.lock().unwrap();
+        if let Some(loaded) = templates.get(&kind).filter(|l| l.modified == modified) {
+            return loaded.template.clone();
+        }
+
+        let previous = templates.get(&kind).map(|l| l.template.clone());
+        let template = match modified.map(|_| std::fs::read_to_string(path)) {
+            None => built_in(kind),
+            Some(Ok(content)) => match Template::parse(kind, &content) {
+                Ok(template) => {
+                    println!("📝 Loaded prompt {}@{} from {}", kind.name(), template.version, path.display());
+                    Arc::new(template)
+                }
+                Err(e) => {
+                    let fallback = previous.unwrap_or_else(|| built_in(kind));
+                    eprintln!(
+                        "⚠️  Invalid prompt template {}: {} — keeping {}@{}",
+                        path.display(),
+                        e,
+                        kind.name(),
+                        fallback.version
+                    );
+                    fallback
+                }
+            },
+            Some(Err(e)) => {
+                eprintln!("⚠️  Failed to read prompt template {}: {}", path.display(), e);
+                previous.unwrap_or_else(|| built_in(kind))
+            }
+        };
+
+        // Remember the mtime even for a rejected file so it isn't re-parsed on every call
+        templates.insert(kind, Loaded { template: template.clone(), modified });
+        template
+    }
+}
+
+fn current(kind: PromptKind) -> Arc<Template> {
+    TEMPLATES.get(kind, &path(kind))
+}
+
+pub(super) fn render(kind: PromptKind, vars: &[(&str, &str)]) -> RenderedPrompt {
+    let template = current(kind);
+    RenderedPrompt {
+        text: template.render(vars),
+        version: format!("{}@{}", kind.name(), template.version),
+    }
+}
+
+/// Load and validate every template now (called at startup); returns their versions
+pub fn load_prompt_templates() -> Vec<String> {
+    ALL.iter().map(|kind| format!("{}@{}", kind.name(), current(*kind).version)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_built_in_templates_are_valid() {
+        for kind in ALL {
+            let template = Template::parse(kind, kind.built_in()).unwrap();
+            assert!(template.version.contains('+'), "{}", template.version);
+            assert!(!template.body.ends_with('\n'));
+        }
+    }
+
+    #[test]
+    fn test_render_named_variables() {
+        let content = "# comment\nversion: 3\n---\nMessage: \"{{changes}}\"\nList:\n{{assignments}}\nJSON: {\"a\": {\"b\": 1}}\n";
+        let template = Template::parse(PromptKind::Matching, content).unwrap();
+
+        assert_eq!(
+            template.render(&[("changes", "diundur {{besok}}"), ("assignments", "#1 LKP 5")]),
+            "Message: \"diundur {{besok}}\"\nList:\n#1 LKP 5\nJSON: {\"a\": {\"b\": 1}}"
+        );
+        assert_eq!(template.version, format!("3+{:08x}", fnv1a(&template.body)));
+    }
+
+    #[test]
+    fn test_validation_errors() {
+        let parse = |content: &str| Template::parse(PromptKind::Matching, content).unwrap_err();
+
+        assert_eq!(parse("{{changes}} {{assignments}}"), "missing \"---\" line between header and body");
+        assert_eq!(parse("author: me\n---\n{{changes}}"), "unknown header line \"author: me\"");
+        assert_eq!(parse("# no version\n---\n{{changes}}"), "header has no \"version:\"");
+        assert!(parse("version: 1\n---\n{{changes}} {{assignmnets}}").starts_with("unknown variable {{assignmnets}}"));
+        assert_eq!(parse("version: 1\n---\n{{changes}}"), "required variable {{assignments}} is not used");
+        assert_eq!(parse("version: 1\n---\n{{changes}} {{assignments"), "unclosed \"{{\"");
+        assert_eq!(parse("version: 1\n---\n{{Changes}} {{assignments}}"), "invalid placeholder \"{{Changes}}\"");
+    }
+
+    #[test]
+    fn test_hot_reload_keeps_last_good_version() {
+        let dir = std::env::temp_dir().join(format!("marbot-prompts-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("matching.txt");
+        let cache = TemplateCache::default();
+        let write = |content: &str, age_secs: u64| {
+            std::fs::write(&path, content).unwrap();
+            // Distinct mtimes even on coarse-grained filesystems
+            let mtime = SystemTime::now() - std::time::Duration::from_secs(age_secs);
+            std::fs::File::options().write(true).open(&path).unwrap().set_modified(mtime).unwrap();
+        };
+
+        // No file yet → built-in
+        assert_eq!(cache.get(PromptKind::Matching, &path).version, built_in(PromptKind::Matching).version);
+
+        write("version: 7\n---\n{{changes}} {{assignments}}", 20);
+        let v7 = cache.get(PromptKind::Matching, &path);
+        assert!(v7.version.starts_with("7+"));
+
+        write("version: 8\n---\n{{changes}}", 10);
+        assert_eq!(cache.get(PromptKind::Matching, &path).version, v7.version);
+
+        write("version: 9\n---\nUpdate: {{changes}}\n{{assignments}}", 5);
+        assert!(cache.get(PromptKind::Matching, &path).version.starts_with("9+"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,228 @@
+// backend/src/parser/ai_extractor/usage.rs
+//
+// Token / cost accounting for every provider call, and the budget caps that
+// read it back. Rows go to `llm_usage` in the background so a slow insert
+// never delays a reply. Until `init` is called (eval binary, tests) nothing
+// is recorded and no budget applies.
+
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, TimeZone, Utc};
+use once_cell::sync::OnceCell;
+use serde::Deserialize;
+use sqlx::PgPool;
+use std::time::Duration;
+
+use super::model_chain::{LlmTask, LLM_CONFIG};
+use super::provider::{ModelStep, ProviderKind, TokenUsage};
+use crate::database::crud::{get_llm_usage_totals, insert_llm_usage};
+use crate::models::{LlmUsageTotals, NewLlmUsage};
+
+static USAGE_POOL: OnceCell<PgPool> = OnceCell::new();
+
+/// Start recording usage and enforcing budgets (called once from main)
+pub fn init(pool: PgPool) {
+    if USAGE_POOL.set(pool).is_err() {
+        eprintln!("⚠️  LLM usage tracking already initialized");
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BudgetPeriod {
+    #[default]
+    Daily,
+    Monthly,
+}
+
+impl BudgetPeriod {
+    /// Midnight WIB of today / of the 1st of this month
+    pub fn start(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        let wib = FixedOffset::east_opt(7 * 3600).unwrap();
+        let today = now.with_timezone(&wib).date_naive();
+        let first = match self {
+            BudgetPeriod::Daily => today,
+            BudgetPeriod::Monthly => NaiveDate::from_ymd_opt(today.year(), today.month(), 1).unwrap(),
+        };
+
+        wib.from_local_datetime(&first.and_hms_opt(0, 0, 0).unwrap())
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            BudgetPeriod::Daily => "hari ini",
+            BudgetPeriod::Monthly => "bulan ini",
+        }
+    }
+}
+
+/// Cap on one provider. Once any limit is reached its models are skipped
+/// until the period rolls over and the chain moves on to the next provider.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Budget {
+    pub provider: ProviderKind,
+    #[serde(default)]
+    pub period: BudgetPeriod,
+    #[serde(default)]
+    pub max_requests: Option<i64>,
+    #[serde(default)]
+    pub max_tokens: Option<i64>,
+    #[serde(default)]
+    pub max_cost_usd: Option<f64>,
+}
+
+impl Budget {
+    /// Which limit `totals` has reached, if any
+    pub fn exceeded(&self, totals: &LlmUsageTotals) -> Option<String> {
+        if let Some(max) = self.max_requests.filter(|max| totals.requests >= *max) {
+            return Some(format!("{}/{} requests", totals.requests, max));
+        }
+        if let Some(max) = self.max_tokens.filter(|max| totals.tokens >= *max) {
+            return Some(format!("{}/{} tokens", totals.tokens, max));
+        }
+        if let Some(max) = self.max_cost_usd.filter(|max| totals.cost_usd >= *max) {
+            return Some(format!("${:.4}/${:.2}", totals.cost_usd, max));
+        }
+        None
+    }
+}
+
+/// USD per 1M tokens
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct Price {
+    #[serde(default)]
+    pub input: f64,
+    #[serde(default)]
+    pub output: f64,
+}
+
+impl Price {
+    pub fn cost_usd(&self, usage: TokenUsage) -> f64 {
+        (usage.prompt_tokens as f64 * self.input + usage.completion_tokens as f64 * self.output) / 1_000_000.0
+    }
+}
+
+/// How a call ended, as stored in `llm_usage.outcome`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum Outcome {
+    Ok,
+    /// Answered, but the caller couldn't use the answer
+    Invalid,
+    RateLimited,
+    Error,
+}
+
+impl Outcome {
+    fn label(&self) -> &'static str {
+        match self {
+            Outcome::Ok => "ok",
+            Outcome::Invalid => "invalid",
+            Outcome::RateLimited => "rate_limited",
+            Outcome::Error => "error",
+        }
+    }
+}
+
+/// Store one provider call (fire and forget)
+pub(super) fn record(
+    task: LlmTask,
+    step: &ModelStep,
+    prompt_version: Option<&str>,
+    usage: TokenUsage,
+    latency: Duration,
+    outcome: Outcome,
+) {
+    let Some(pool) = USAGE_POOL.get() else {
+        return;
+    };
+
+    let cost_usd = LLM_CONFIG
+        .prices
+        .get(&step.model)
+        .map(|price| price.cost_usd(usage))
+        .unwrap_or(0.0);
+
+    let row = NewLlmUsage {
+        provider: step.provider.name().to_string(),
+        model: step.model.clone(),
+        task: task.label().to_string(),
+        prompt_version: prompt_version.map(str::to_string),
+        prompt_tokens: usage.prompt_tokens as i32,
+        completion_tokens: usage.completion_tokens as i32,
+        latency_ms: latency.as_millis().min(i32::MAX as u128) as i32,
+        outcome: outcome.label().to_string(),
+        cost_usd,
+    };
+
+    let pool = pool.clone();
+    tokio::spawn(async move {
+        if let Err(e) = insert_llm_usage(&pool, &row).await {
+            eprintln!("⚠️  Failed to record LLM usage: {}", e);
+        }
+    });
+}
+
+/// Why `provider` must be skipped right now, if one of its budgets is used up.
+/// Lookup errors never block a call.
+pub(super) async fn over_budget(provider: ProviderKind) -> Option<String> {
+    let pool = USAGE_POOL.get()?;
+    let name = provider.name();
+
+    for budget in LLM_CONFIG.budgets.iter().filter(|b| b.provider == provider) {
+        match get_llm_usage_totals(pool, name, budget.period.start(Utc::now())).await {
+            Ok(totals) => {
+                if let Some(reason) = budget.exceeded(&totals) {
+                    return Some(format!("{} budget {} ({})", name, budget.period.label(), reason));
+                }
+            }
+            Err(e) => eprintln!("⚠️  Failed to read LLM usage for {}: {}", name, e),
+        }
+    }
+    None
+}
+
+/// Budgets from llm_models.json, for #usage
+pub fn configured_budgets() -> &'static [Budget] {
+    &LLM_CONFIG.budgets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_period_start_is_wib_midnight() {
+        // 2026-10-31 18:30 UTC = 2026-11-01 01:30 WIB
+        let now = Utc.with_ymd_and_hms(2026, 10, 31, 18, 30, 0).unwrap();
+
+        assert_eq!(BudgetPeriod::Daily.start(now), Utc.with_ymd_and_hms(2026, 10, 31, 17, 0, 0).unwrap());
+        assert_eq!(BudgetPeriod::Monthly.start(now), Utc.with_ymd_and_hms(2026, 10, 31, 17, 0, 0).unwrap());
+
+        let mid_month = Utc.with_ymd_and_hms(2026, 10, 18, 3, 0, 0).unwrap();
+        assert_eq!(BudgetPeriod::Monthly.start(mid_month), Utc.with_ymd_and_hms(2026, 9, 30, 17, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn test_budget_exceeded() {
+        let budget = Budget {
+            provider: ProviderKind::Groq,
+            period: BudgetPeriod::Daily,
+            max_requests: Some(1000),
+            max_tokens: None,
+            max_cost_usd: Some(0.5),
+        };
+        let totals = |requests, cost_usd| LlmUsageTotals { requests, tokens: 1_000_000, cost_usd };
+
+        assert_eq!(budget.exceeded(&totals(999, 0.1)), None);
+        assert_eq!(budget.exceeded(&totals(1000, 0.1)).as_deref(), Some("1000/1000 requests"));
+        assert_eq!(budget.exceeded(&totals(10, 0.5)).as_deref(), Some("$0.5000/$0.50"));
+    }
+
+    #[test]
+    fn test_price_per_million_tokens() {
+        let price = Price { input: 0.15, output: 0.60 };
+        let usage = TokenUsage { prompt_tokens: 2_000, completion_tokens: 500 };
+
+        assert!((price.cost_usd(usage) - 0.0006).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,318 @@
+// backend/src/parser/commands/admin.rs - Perintah khusus grup akademik
+
+use super::{format_date_id, humanize_deadline, preview_text, sanitize_wa_md, CommandContext, CommandResponse};
+use crate::database::crud::{delete_assignment, get_active_assignments_sorted, get_deleted_assignments, restore_assignment};
+use crate::conversation::{ChoiceOption, ChoicePurpose, PendingAction, REPLY_TIMEOUT};
+use crate::models::{CommandArgs, TrashedAssignment};
+use crate::parser::registry::{ArgKind, ArgSpec, Command, HelpLine, HelpSection, Scope};
+use async_trait::async_trait;
+use chrono::FixedOffset;
+use uuid::Uuid;
+
+pub struct Delete;
+
+#[async_trait]
+impl Command for Delete {
+    fn name(&self) -> &'static str {
+        "delete"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["hapus"]
+    }
+
+    fn args(&self) -> &'static [ArgSpec] {
+        &[
+            ArgSpec { name: "id", kind: ArgKind::Index, required: true },
+        ]
+    }
+
+    fn help(&self) -> &'static [HelpLine] {
+        &[
+            HelpLine { section: HelpSection::Admin, usage: "#delete <id>", description: "hapus tugas (id dari #tugas)" },
+        ]
+    }
+
+    fn scope(&self) -> Scope {
+        Scope::AcademicChannel
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>, args: &CommandArgs) -> CommandResponse {
+        let index = args.index(0).unwrap_or_default();
+
+        println!("🗑️ Delete command received from {} in chat {}", ctx.user_phone, ctx.chat_id);
+
+        match get_active_assignments_sorted(ctx.pool).await {
+            Ok(assignments) => {
+                let idx = (index as usize).saturating_sub(1);
+
+                if idx >= assignments.len() {
+                    return CommandResponse::Text(format!(
+                        "❌ Tugas nomor *{}* tidak ditemukan.\nCek nomor terbaru dengan *#tugas*",
+                        index
+                    ));
+                }
+
+                let target = &assignments[idx];
+                ctx.expect_reply(PendingAction::ConfirmDelete {
+                    assignment_id: target.id,
+                    title: target.title.clone(),
+                    course_name: target.course_name.clone(),
+                });
+
+                CommandResponse::Text(format!(
+                    "⚠️ *HAPUS TUGAS?*\n\n\
+                    Mata Kuliah: {}\n\
+                    Judul: {}\n\n\
+                    Balas *ya* untuk menghapus atau *tidak* untuk batal.\n\
+                    _Berlaku {} menit._",
+                    sanitize_wa_md(&target.course_name),
+                    sanitize_wa_md(&target.title),
+                    REPLY_TIMEOUT.as_secs() / 60
+                ))
+            }
+            Err(e) => {
+                eprintln!("❌ Error fetching list for delete: {}", e);
+                CommandResponse::Text("❌ Gagal mengambil daftar tugas.".to_string())
+            }
+        }
+    }
+}
+
+/// Second step of `#delete`, after the user answered "ya"
+pub(super) async fn delete_confirmed(
+    ctx: &CommandContext<'_>,
+    assignment_id: Uuid,
+    title: &str,
+    course_name: &str,
+) -> CommandResponse {
+    match delete_assignment(ctx.pool, assignment_id, ctx.user_phone).await {
+        Ok(true) => {
+            CommandResponse::Text(format!(
+                "🗑️ *TUGAS DIHAPUS*\n\n\
+                Mata Kuliah: {}\n\
+                Judul: {}\n\n\
+                _Tugas dipindah ke trash ({} hari)._\n\
+                _Salah hapus? Ketik #restore {}_",
+                sanitize_wa_md(course_name),
+                sanitize_wa_md(title),
+                trash_retention_days(),
+                short_code(assignment_id)
+            ))
+        },
+        Ok(false) => CommandResponse::Text("❌ Gagal menghapus. Tugas mungkin sudah hilang.".to_string()),
+        Err(e) => {
+            eprintln!("❌ DB Error on delete: {}", e);
+            CommandResponse::Text("❌ Terjadi kesalahan sistem.".to_string())
+        }
+    }
+}
+
+pub struct Trash;
+
+#[async_trait]
+impl Command for Trash {
+    fn name(&self) -> &'static str {
+        "trash"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["sampah"]
+    }
+
+    fn help(&self) -> &'static [HelpLine] {
+        &[
+            HelpLine { section: HelpSection::Admin, usage: "#trash", description: "tugas yang baru dihapus" },
+        ]
+    }
+
+    fn scope(&self) -> Scope {
+        Scope::AcademicChannel
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>, _args: &CommandArgs) -> CommandResponse {
+        println!("🗑️ Trash command received from {} in chat {}", ctx.user_phone, ctx.chat_id);
+
+        match get_deleted_assignments(ctx.pool).await {
+            Ok(trashed) if trashed.is_empty() => {
+                CommandResponse::Text("🗑️ *Trash*\n\n📭 Trash kosong.".to_string())
+            }
+            Ok(trashed) => CommandResponse::Text(format_trash(&trashed)),
+            Err(e) => {
+                eprintln!("❌ Error fetching trash: {}", e);
+                CommandResponse::Text("❌ Gagal mengambil isi trash.".to_string())
+            }
+        }
+    }
+}
+
+pub struct Restore;
+
+#[async_trait]
+impl Command for Restore {
+    fn name(&self) -> &'static str {
+        "restore"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["pulihkan"]
+    }
+
+    fn args(&self) -> &'static [ArgSpec] {
+        &[
+            ArgSpec { name: "kode", kind: ArgKind::Text, required: true },
+        ]
+    }
+
+    fn help(&self) -> &'static [HelpLine] {
+        &[
+            HelpLine { section: HelpSection::Admin, usage: "#restore <kode>", description: "kembalikan tugas dari #trash" },
+        ]
+    }
+
+    fn scope(&self) -> Scope {
+        Scope::AcademicChannel
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>, args: &CommandArgs) -> CommandResponse {
+        let code = args.text(0).unwrap_or_default().trim().to_lowercase();
+
+        println!("♻️ Restore command '{}' from {} in chat {}", code, ctx.user_phone, ctx.chat_id);
+
+        let trashed = match get_deleted_assignments(ctx.pool).await {
+            Ok(t) => t,
+            Err(e) => {
+                eprintln!("❌ Error fetching trash: {}", e);
+                return CommandResponse::Text("❌ Gagal mengambil isi trash.".to_string());
+            }
+        };
+
+        let matches: Vec<_> = trashed.iter().filter(|t| code_matches(t.id, &code)).collect();
+        match matches.as_slice() {
+            [target] => restore_chosen(ctx, target.id).await,
+            [] => CommandResponse::Text(format!(
+                "❌ Kode *{}* tidak ada di trash.\nCek kode dengan *#trash*",
+                sanitize_wa_md(&code)
+            )),
+            _ => {
+                let options: Vec<_> = matches
+                    .iter()
+                    .map(|t| ChoiceOption {
+                        id: t.id,
+                        label: format!("{} — {}", sanitize_wa_md(&t.title), sanitize_wa_md(&t.course_name)),
+                    })
+                    .collect();
+
+                let mut response = format!(
+                    "⚠️ Kode *{}* cocok dengan {} tugas. Balas dengan nomornya:\n\n",
+                    sanitize_wa_md(&code),
+                    options.len()
+                );
+                for (i, option) in options.iter().enumerate() {
+                    response.push_str(&format!("*{}.* {}\n", i + 1, option.label));
+                }
+                response.push_str("\n_Balas *tidak* untuk batal._");
+
+                ctx.expect_reply(PendingAction::Choose { purpose: ChoicePurpose::Restore, options });
+                CommandResponse::Text(response)
+            }
+        }
+    }
+}
+
+/// Restore one trashed assignment (also the answer to an ambiguous `#restore`)
+pub(super) async fn restore_chosen(ctx: &CommandContext<'_>, id: Uuid) -> CommandResponse {
+    let trashed = match get_deleted_assignments(ctx.pool).await {
+        Ok(t) => t,
+        Err(e) => {
+            eprintln!("❌ Error fetching trash: {}", e);
+            return CommandResponse::Text("❌ Gagal mengambil isi trash.".to_string());
+        }
+    };
+    let Some(target) = trashed.iter().find(|t| t.id == id) else {
+        return CommandResponse::Text("❌ Tugas sudah tidak ada di trash.".to_string());
+    };
+
+    match restore_assignment(ctx.pool, target.id).await {
+        Ok(true) => CommandResponse::Text(format!(
+            "♻️ *TUGAS DIKEMBALIKAN*\n\n\
+            Mata Kuliah: {}\n\
+            Judul: {}\n\n\
+            _Status selesai mahasiswa tetap tersimpan._",
+            sanitize_wa_md(&target.course_name),
+            sanitize_wa_md(&target.title)
+        )),
+        Ok(false) => CommandResponse::Text("❌ Gagal mengembalikan. Tugas mungkin sudah dipulihkan.".to_string()),
+        Err(e) => {
+            eprintln!("❌ DB Error on restore: {}", e);
+            CommandResponse::Text("❌ Terjadi kesalahan sistem.".to_string())
+        }
+    }
+}
+
+/// Berapa hari tugas disimpan di trash sebelum dihapus permanen (env TRASH_RETENTION_DAYS)
+pub fn trash_retention_days() -> i32 {
+    std::env::var("TRASH_RETENTION_DAYS")
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .filter(|d| *d > 0)
+        .unwrap_or(30)
+}
+
+/// Kode pendek untuk #restore / #approve: 8 karakter pertama UUID
+pub(super) fn short_code(id: Uuid) -> String {
+    id.simple().to_string()[..8].to_string()
+}
+
+/// Kode minimal 4 karakter, dicocokkan sebagai prefix UUID
+pub(super) fn code_matches(id: Uuid, code: &str) -> bool {
+    let code = code.replace('-', "");
+    code.len() >= 4 && id.simple().to_string().starts_with(&code)
+}
+
+fn format_trash(trashed: &[TrashedAssignment]) -> String {
+    let gmt7 = FixedOffset::east_opt(7 * 3600).unwrap();
+    let mut response = format!(
+        "🗑️ *Trash*\n_Dihapus permanen setelah {} hari._\n\n",
+        trash_retention_days()
+    );
+
+    for t in trashed {
+        let deleted_at = t.deleted_at.with_timezone(&gmt7);
+        let deleted_by = t
+            .deleted_by
+            .as_deref()
+            .map(|p| p.split('@').next().unwrap_or(p).to_string())
+            .unwrap_or_else(|| "—".to_string());
+
+        response.push_str(&format!(
+            "🔖 *{}* — {}\n📌 {}\n⏰ {}\n🗑️ {} {} oleh {}\n\n",
+            short_code(t.id),
+            preview_text(&sanitize_wa_md(&t.title), 25),
+            sanitize_wa_md(&t.course_name),
+            humanize_deadline(&t.deadline),
+            format_date_id(deleted_at.date_naive()),
+            deleted_at.format("%H:%M"),
+            deleted_by
+        ));
+    }
+
+    response.push_str("_♻️ Kembalikan: #restore <kode>_");
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restore_code() {
+        let id = Uuid::parse_str("a1b2c3d4-e5f6-4711-8899-aabbccddeeff").unwrap();
+
+        assert_eq!(short_code(id), "a1b2c3d4");
+        assert!(code_matches(id, "a1b2c3d4"));
+        assert!(code_matches(id, "a1b2c3d4-e5f6"));
+        assert!(!code_matches(id, "a1b"));
+        assert!(!code_matches(id, "ffff0000"));
+    }
+}
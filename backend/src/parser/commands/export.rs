@@ -0,0 +1,129 @@
+// backend/src/parser/commands/export.rs - Ekspor deadline ke kalender (.ics & link langganan)
+
+use super::{CommandContext, CommandResponse};
+use crate::calendar::build_calendar;
+use crate::database::crud::{
+    get_active_assignments_for_user, get_or_create_calendar_token, get_reminder_preference, rotate_calendar_token,
+};
+use crate::models::{CommandArgs, ReminderPreference};
+use crate::parser::registry::{ArgKind, ArgSpec, Command, HelpLine, HelpSection, Scope};
+use async_trait::async_trait;
+use chrono::Utc;
+
+pub struct Export;
+
+#[async_trait]
+impl Command for Export {
+    fn name(&self) -> &'static str {
+        "export"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["ekspor", "kalender"]
+    }
+
+    fn args(&self) -> &'static [ArgSpec] {
+        &[
+            ArgSpec { name: "ics | link | reset", kind: ArgKind::Text, required: false },
+        ]
+    }
+
+    fn help(&self) -> &'static [HelpLine] {
+        &[
+            HelpLine { section: HelpSection::Personal, usage: "#export ics", description: "file kalender (.ics) berisi deadline kamu" },
+            HelpLine { section: HelpSection::Personal, usage: "#export link", description: "link langganan kalender (auto-update)" },
+        ]
+    }
+
+    /// Daftar tugas pribadi, jangan di grup akademik; link kalender malah rahasia (lihat execute)
+    fn scope(&self) -> Scope {
+        Scope::NotAcademicChannel
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>, args: &CommandArgs) -> CommandResponse {
+        let sub = args.text(0).unwrap_or("ics").to_lowercase();
+        println!("📤 Export command ({}) from {}\n", sub, ctx.user_phone);
+
+        // Siapa pun yang pegang link bisa baca deadline kamu: jangan sampai terkirim ke grup mana pun
+        if matches!(sub.as_str(), "link" | "url" | "reset") && ctx.is_group() {
+            return CommandResponse::Text(
+                "🔒 _Link kalender hanya dikirim lewat chat pribadi._\nKetik *#export link* langsung ke bot ya.".to_string(),
+            );
+        }
+
+        match sub.as_str() {
+            "ics" | "file" => export_file(ctx).await,
+            "link" | "url" => feed_link(ctx, false).await,
+            "reset" => feed_link(ctx, true).await,
+            _ => CommandResponse::Text(
+                "⚠️ Format salah. Gunakan: *#export ics*, *#export link* atau *#export reset*".to_string(),
+            ),
+        }
+    }
+}
+
+async fn export_file(ctx: &CommandContext<'_>) -> CommandResponse {
+    let assignments = match get_active_assignments_for_user(ctx.pool, ctx.user_phone).await {
+        Ok(a) => a.into_iter().filter(|a| !a.is_completed).collect::<Vec<_>>(),
+        Err(e) => {
+            eprintln!("❌ Error fetching assignments for export: {}", e);
+            return CommandResponse::Text("❌ Gagal mengambil data tugas.".to_string());
+        }
+    };
+
+    let with_deadline = assignments.iter().filter(|a| a.deadline.is_some()).count();
+    if with_deadline == 0 {
+        return CommandResponse::Text("📭 Tidak ada tugas dengan deadline untuk diekspor.".to_string());
+    }
+
+    let lead_minutes = match get_reminder_preference(ctx.pool, ctx.user_phone).await {
+        Ok(Some(pref)) => pref.lead_minutes,
+        _ => ReminderPreference::default_for(ctx.user_phone).lead_minutes,
+    };
+
+    let ics = build_calendar(&assignments, &lead_minutes, Utc::now());
+
+    CommandResponse::File {
+        filename: "tugas-mabot.ics".to_string(),
+        mimetype: "text/calendar".to_string(),
+        data: ics.into_bytes(),
+        caption: format!(
+            "📅 *{} deadline* siap diimpor ke kalender.\n\n\
+            _Buka file ini di HP untuk menambahkan. Mau otomatis ter-update? Ketik #export link_",
+            with_deadline
+        ),
+    }
+}
+
+async fn feed_link(ctx: &CommandContext<'_>, rotate: bool) -> CommandResponse {
+    let Ok(base_url) = std::env::var("PUBLIC_URL") else {
+        return CommandResponse::Text(
+            "⚠️ Link kalender belum diaktifkan oleh admin bot.\n_Pakai #export ics untuk file kalender._".to_string(),
+        );
+    };
+
+    let token = if rotate {
+        rotate_calendar_token(ctx.pool, ctx.user_phone).await
+    } else {
+        get_or_create_calendar_token(ctx.pool, ctx.user_phone).await
+    };
+
+    match token {
+        Ok(token) => {
+            let notice = if rotate { "🔄 Link lama sudah tidak berlaku.\n\n" } else { "" };
+            CommandResponse::Text(format!(
+                "{}📅 *Link Kalender Kamu*\n{}/calendar/{}.ics\n\n\
+                Tambahkan lewat _Google Calendar → Kalender lain → Dari URL_ (atau Apple Calendar → Langganan). \
+                Deadline akan ter-update otomatis.\n\n\
+                🔒 _Jangan bagikan link ini. Bocor? Ketik #export reset_",
+                notice,
+                base_url.trim_end_matches('/'),
+                token
+            ))
+        }
+        Err(e) => {
+            eprintln!("❌ Error creating calendar token: {}", e);
+            CommandResponse::Text("❌ Gagal membuat link kalender.".to_string())
+        }
+    }
+}
@@ -0,0 +1,229 @@
+// backend/src/parser/commands/general.rs - Perintah umum (global list, help, ping)
+
+use super::{format_assignments_list, get_gmt7_now, CommandContext, CommandResponse};
+use crate::database::crud::{get_active_assignments_for_user, get_active_assignments_sorted};
+use crate::models::{AssignmentWithCourse, CommandArgs};
+use crate::parser::registry::{self, ArgKind, ArgSpec, Command, HelpLine, HelpSection};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate};
+use std::time::Instant;
+
+pub struct Ping;
+
+#[async_trait]
+impl Command for Ping {
+    fn name(&self) -> &'static str {
+        "ping"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["test", "tes"]
+    }
+
+    fn help(&self) -> &'static [HelpLine] {
+        &[
+            HelpLine { section: HelpSection::General, usage: "#ping", description: "cek bot hidup & latency" },
+        ]
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>, _args: &CommandArgs) -> CommandResponse {
+        println!("🏓 Ping command received from {}\n", ctx.user_phone);
+        
+        let start_time = Instant::now();
+        let db_start = Instant::now();
+
+        let db_status = sqlx::query("SELECT 1").execute(ctx.pool).await;
+        let db_duration = db_start.elapsed();
+
+        let (db_icon, db_msg) = match db_status {
+            Ok(_) => ("🟢", format!("{:.2?}", db_duration)),
+            Err(_) => ("🔴", "Error / Disconnected".to_string()),
+        };
+
+        let bot_duration = start_time.elapsed();
+
+        let response_text = format!(
+            "🏓 *PONG! - System Diagnostic*\n\n\
+            🖥️ *Server Status:*\n\
+            • Bot Logic: 🟢 Online\n\
+            • Database: {} Connected\n\n\
+            ⏱️ *Real-time Latency:*\n\
+            • 🗄️ Database Query: {}\n\
+            • ⚙️ Bot Processing: {:.2?}\n\n\
+            ",
+            db_icon,
+            db_msg,
+            bot_duration
+        );
+
+        CommandResponse::Text(response_text)
+    }
+}
+
+pub struct Tugas;
+
+#[async_trait]
+impl Command for Tugas {
+    fn name(&self) -> &'static str {
+        "tugas"
+    }
+
+    fn args(&self) -> &'static [ArgSpec] {
+        &[
+            ArgSpec { name: "id", kind: ArgKind::Index, required: false },
+        ]
+    }
+
+    fn help(&self) -> &'static [HelpLine] {
+        &[
+            HelpLine { section: HelpSection::General, usage: "#tugas", description: "lihat semua tugas (global)" },
+        ]
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>, args: &CommandArgs) -> CommandResponse {
+        // "#tugas 3" is an alias of "#3"
+        if args.index(0).is_some() {
+            return super::run(&super::personal::Expand, ctx, args).await;
+        }
+
+        println!("📋 Tugas command received from {}", ctx.user_phone);
+
+        match get_active_assignments_sorted(ctx.pool).await {
+            Ok(assignments) => format_assignments_list(assignments, "*[Daftar Tugas Aktif]*", false, false),
+            Err(e) => {
+                eprintln!("❌ Error fetching assignments: {}", e);
+                CommandResponse::Text(
+                    "❌ Maaf, terjadi kesalahan saat mengambil data tugas.\n_Coba lagi sebentar ya._"
+                        .to_string(),
+                )
+            }
+        }
+    }
+}
+
+pub struct Today;
+
+#[async_trait]
+impl Command for Today {
+    fn name(&self) -> &'static str {
+        "today"
+    }
+
+    fn help(&self) -> &'static [HelpLine] {
+        &[
+            HelpLine { section: HelpSection::General, usage: "#today", description: "tugas deadline hari ini" },
+        ]
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>, _args: &CommandArgs) -> CommandResponse {
+        println!("📅 Today command received from {}", ctx.user_phone);
+
+        match get_active_assignments_for_user(ctx.pool, ctx.user_phone).await {
+            Ok(assignments) => {
+                let today_assignments = due_on(assignments, get_gmt7_now().date_naive());
+
+                format_assignments_list(today_assignments, "*[Tugas Hari Ini]*", false, true)
+            }
+            Err(e) => {
+                eprintln!("❌ Error fetching assignments: {}", e);
+                CommandResponse::Text(
+                    "❌ Maaf, terjadi kesalahan saat mengambil data tugas.\n_Coba lagi sebentar ya._"
+                        .to_string(),
+                )
+            }
+        }
+    }
+}
+
+pub struct Week;
+
+#[async_trait]
+impl Command for Week {
+    fn name(&self) -> &'static str {
+        "week"
+    }
+
+    fn help(&self) -> &'static [HelpLine] {
+        &[
+            HelpLine { section: HelpSection::General, usage: "#week", description: "tugas 7 hari ke depan" },
+        ]
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>, _args: &CommandArgs) -> CommandResponse {
+        println!("📆 Week command received from {}", ctx.user_phone);
+
+        match get_active_assignments_for_user(ctx.pool, ctx.user_phone).await {
+            Ok(assignments) => {
+                let week_assignments = due_within(assignments, get_gmt7_now(), 7);
+
+                format_assignments_list(week_assignments, "📆 *Tugas Minggu Ini (7 Hari)*", false, true)
+            }
+            Err(e) => {
+                eprintln!("❌ Error fetching assignments: {}", e);
+                CommandResponse::Text(
+                    "❌ Maaf, terjadi kesalahan saat mengambil data tugas.\n_Coba lagi sebentar ya._"
+                        .to_string(),
+                )
+            }
+        }
+    }
+}
+
+/// Tugas dengan deadline pada tanggal (WIB) tertentu
+pub(super) fn due_on(assignments: Vec<AssignmentWithCourse>, date: NaiveDate) -> Vec<AssignmentWithCourse> {
+    let gmt7 = FixedOffset::east_opt(7 * 3600).unwrap();
+
+    assignments
+        .into_iter()
+        .filter(|a| {
+            a.deadline
+                .map(|deadline| deadline.with_timezone(&gmt7).date_naive() == date)
+                .unwrap_or(false)
+        })
+        .collect()
+}
+
+/// Tugas dengan deadline antara sekarang dan `days` hari ke depan
+pub(super) fn due_within(
+    assignments: Vec<AssignmentWithCourse>,
+    now: DateTime<FixedOffset>,
+    days: u32,
+) -> Vec<AssignmentWithCourse> {
+    let end = now + Duration::days(days as i64);
+
+    assignments
+        .into_iter()
+        .filter(|a| {
+            a.deadline
+                .map(|deadline| {
+                    let d = deadline.with_timezone(&now.timezone());
+                    d >= now && d <= end
+                })
+                .unwrap_or(false)
+        })
+        .collect()
+}
+
+pub struct Help;
+
+#[async_trait]
+impl Command for Help {
+    fn name(&self) -> &'static str {
+        "help"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["bantuan"]
+    }
+
+    fn help(&self) -> &'static [HelpLine] {
+        &[
+            HelpLine { section: HelpSection::General, usage: "#help", description: "bantuan" },
+        ]
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>, _args: &CommandArgs) -> CommandResponse {
+        println!("❓ Help command received from {}\n", ctx.user_phone);
+        CommandResponse::Text(registry::help_text())
+    }
+}
@@ -0,0 +1,288 @@
+// backend/src/parser/commands/history.rs - Tugas yang sudah lewat deadline (overdue & riwayat)
+
+use super::{get_gmt7_now, humanize_deadline, preview_text, sanitize_wa_md, CommandContext, CommandResponse};
+use crate::database::crud::{
+    get_course_by_name_or_alias, get_overdue_assignments_for_user, get_past_assignments_for_user,
+    mark_assignment_complete,
+};
+use crate::models::{AssignmentWithCourse, CommandArgs};
+use crate::parser::registry::{ArgKind, ArgSpec, Command, HelpLine, HelpSection, Scope};
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, Duration, FixedOffset, TimeZone, Utc};
+
+pub struct Overdue;
+
+#[async_trait]
+impl Command for Overdue {
+    fn name(&self) -> &'static str {
+        "overdue"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["terlewat"]
+    }
+
+    fn args(&self) -> &'static [ArgSpec] {
+        &[
+            ArgSpec { name: "done <no>", kind: ArgKind::Text, required: false },
+        ]
+    }
+
+    fn help(&self) -> &'static [HelpLine] {
+        &[
+            HelpLine { section: HelpSection::Personal, usage: "#overdue", description: "tugas lewat deadline yang belum kamu selesaikan" },
+        ]
+    }
+
+    fn scope(&self) -> Scope {
+        Scope::NotAcademicChannel
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>, args: &CommandArgs) -> CommandResponse {
+        println!("⌛ Overdue command from {}\n", ctx.user_phone);
+
+        let overdue = match get_overdue_assignments_for_user(ctx.pool, ctx.user_phone).await {
+            Ok(a) => a,
+            Err(e) => {
+                eprintln!("❌ Error fetching overdue assignments: {}", e);
+                return CommandResponse::Text(
+                    "❌ Maaf, terjadi kesalahan saat mengambil data tugas.\n_Coba lagi sebentar ya._".to_string(),
+                );
+            }
+        };
+
+        match args.text(0) {
+            None => format_overdue_list(&overdue),
+            Some(sub) => match parse_done_index(sub) {
+                Some(index) => mark_overdue_done(ctx, &overdue, index).await,
+                None => CommandResponse::Text(
+                    "⚠️ Format salah. Gunakan: *#overdue* atau *#overdue done <nomor>*".to_string(),
+                ),
+            },
+        }
+    }
+}
+
+pub struct History;
+
+#[async_trait]
+impl Command for History {
+    fn name(&self) -> &'static str {
+        "riwayat"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["history"]
+    }
+
+    fn args(&self) -> &'static [ArgSpec] {
+        &[
+            ArgSpec { name: "matkul | minggu <n>", kind: ArgKind::Text, required: false },
+        ]
+    }
+
+    fn help(&self) -> &'static [HelpLine] {
+        &[
+            HelpLine { section: HelpSection::Personal, usage: "#riwayat [matkul | minggu <n>]", description: "riwayat tugas yang sudah lewat" },
+        ]
+    }
+
+    fn scope(&self) -> Scope {
+        Scope::NotAcademicChannel
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>, args: &CommandArgs) -> CommandResponse {
+        println!("📜 History command ({:?}) from {}\n", args.text(0), ctx.user_phone);
+
+        let now = get_gmt7_now();
+        let (header, from, to, course_id) = match parse_history_filter(args.text(0)) {
+            HistoryFilter::Recent => (
+                "📜 *Riwayat Tugas (14 hari terakhir)*".to_string(),
+                Some((now - Duration::days(14)).with_timezone(&Utc)),
+                None,
+                None,
+            ),
+            HistoryFilter::Week(weeks_ago) => {
+                let (start, end) = week_range(now, weeks_ago);
+                let label = match weeks_ago {
+                    0 => "minggu ini".to_string(),
+                    1 => "minggu lalu".to_string(),
+                    n => format!("{} minggu lalu", n),
+                };
+                (format!("📜 *Riwayat Tugas — {}*", label), Some(start), Some(end), None)
+            }
+            HistoryFilter::Course(query) => match get_course_by_name_or_alias(ctx.pool, &query).await {
+                Ok(Some(course)) => (
+                    format!("📜 *Riwayat Tugas — {}*", sanitize_wa_md(&course.name)),
+                    None,
+                    None,
+                    Some(course.id),
+                ),
+                Ok(None) => {
+                    return CommandResponse::Text(format!(
+                        "❌ Mata kuliah *{}* tidak ditemukan.\n\n💡 _Atau pakai #riwayat minggu <n> untuk per minggu._",
+                        sanitize_wa_md(&query)
+                    ))
+                }
+                Err(e) => {
+                    eprintln!("❌ Error looking up course: {}", e);
+                    return CommandResponse::Text("❌ Terjadi kesalahan sistem.".to_string());
+                }
+            },
+        };
+
+        match get_past_assignments_for_user(ctx.pool, ctx.user_phone, from, to, course_id).await {
+            Ok(past) if past.is_empty() => {
+                CommandResponse::Text(format!("{}\n\n📭 Tidak ada tugas yang sudah lewat.", header))
+            }
+            Ok(past) => {
+                let done = past.iter().filter(|a| a.is_completed).count();
+                let mut response = format!("{}\n✅ {}/{} selesai\n\n", header, done, past.len());
+
+                for (i, a) in past.iter().enumerate() {
+                    let state = if a.is_completed { "✅" } else { "❌" };
+                    response.push_str(&format!(
+                        "{} *[{}] [{}]*\n📌 {}\n⏰ {}\n\n",
+                        state,
+                        i + 1,
+                        preview_text(&sanitize_wa_md(&a.title), 25),
+                        sanitize_wa_md(&a.course_name),
+                        humanize_deadline(&a.deadline)
+                    ));
+                }
+
+                response.push_str("_✅ selesai • ❌ terlewat — belum selesai? cek #overdue_");
+                CommandResponse::Text(response)
+            }
+            Err(e) => {
+                eprintln!("❌ Error fetching history: {}", e);
+                CommandResponse::Text("❌ Gagal mengambil riwayat tugas.".to_string())
+            }
+        }
+    }
+}
+
+fn format_overdue_list(overdue: &[AssignmentWithCourse]) -> CommandResponse {
+    if overdue.is_empty() {
+        return CommandResponse::Text(
+            "⌛ *Tugas Terlewat*\n\n🎉 Tidak ada tugas yang terlewat. Mantap!".to_string(),
+        );
+    }
+
+    let mut response = String::from("⌛ *Tugas Terlewat*\n_Deadline sudah lewat, tapi belum kamu tandai selesai._\n\n");
+
+    for (i, a) in overdue.iter().enumerate() {
+        response.push_str(&format!(
+            "🔴 *[{}] [{}]*\n📌 {}\n⏰ Deadline: {}\n\n",
+            i + 1,
+            preview_text(&sanitize_wa_md(&a.title), 25),
+            sanitize_wa_md(&a.course_name),
+            humanize_deadline(&a.deadline)
+        ));
+    }
+
+    response.push_str("_✅ Sudah dikumpulkan? #overdue done <nomor>_");
+    CommandResponse::Text(response)
+}
+
+async fn mark_overdue_done(
+    ctx: &CommandContext<'_>,
+    overdue: &[AssignmentWithCourse],
+    index: u32,
+) -> CommandResponse {
+    let Some(assignment) = (index as usize).checked_sub(1).and_then(|idx| overdue.get(idx)) else {
+        return CommandResponse::Text(format!(
+            "❌ Tugas nomor *{}* tidak ada di daftar terlewat.\n\n\
+            💡 _Tip: Ketik #overdue untuk lihat daftarnya._",
+            index
+        ));
+    };
+
+    match mark_assignment_complete(ctx.pool, assignment.id, ctx.user_phone).await {
+        Ok(_) => CommandResponse::Text(format!(
+            "✅ Tugas *{}* ditandai selesai (terlambat).\n\n\
+            _Salah tandai? Ketik #undo_",
+            sanitize_wa_md(&assignment.title)
+        )),
+        Err(e) => CommandResponse::Text(format!("❌ Database error: {}", e)),
+    }
+}
+
+/// "done 2" / "selesai 2" → 2
+fn parse_done_index(text: &str) -> Option<u32> {
+    let mut words = text.split_whitespace();
+    let sub = words.next()?.to_lowercase();
+    if !matches!(sub.as_str(), "done" | "selesai") {
+        return None;
+    }
+    words.next()?.parse().ok()
+}
+
+#[derive(Debug, PartialEq)]
+enum HistoryFilter {
+    Recent,
+    /// Minggu kalender (Senin–Minggu WIB), 0 = minggu ini
+    Week(u32),
+    Course(String),
+}
+
+fn parse_history_filter(text: Option<&str>) -> HistoryFilter {
+    let Some(text) = text.map(str::trim).filter(|t| !t.is_empty()) else {
+        return HistoryFilter::Recent;
+    };
+
+    let lower = text.to_lowercase();
+    let mut words = lower.split_whitespace();
+    if matches!(words.next(), Some("minggu" | "week")) {
+        return match words.next() {
+            None | Some("ini") => HistoryFilter::Week(0),
+            Some("lalu") | Some("kemarin") => HistoryFilter::Week(1),
+            Some(n) => n.parse().map(HistoryFilter::Week).unwrap_or(HistoryFilter::Course(text.to_string())),
+        };
+    }
+
+    HistoryFilter::Course(text.to_string())
+}
+
+/// Rentang [Senin 00:00, Senin berikutnya 00:00) WIB untuk minggu ke-n ke belakang
+fn week_range(now: DateTime<FixedOffset>, weeks_ago: u32) -> (DateTime<Utc>, DateTime<Utc>) {
+    let monday = now.date_naive() - Duration::days(now.weekday().num_days_from_monday() as i64)
+        - Duration::weeks(weeks_ago as i64);
+    let start = now
+        .timezone()
+        .from_local_datetime(&monday.and_hms_opt(0, 0, 0).unwrap())
+        .unwrap()
+        .with_timezone(&Utc);
+
+    (start, start + Duration::weeks(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_history_filter() {
+        assert_eq!(parse_history_filter(None), HistoryFilter::Recent);
+        assert_eq!(parse_history_filter(Some("minggu")), HistoryFilter::Week(0));
+        assert_eq!(parse_history_filter(Some("minggu lalu")), HistoryFilter::Week(1));
+        assert_eq!(parse_history_filter(Some("week 3")), HistoryFilter::Week(3));
+        assert_eq!(parse_history_filter(Some("Strukdat")), HistoryFilter::Course("Strukdat".to_string()));
+        assert_eq!(parse_done_index("done 2"), Some(2));
+        assert_eq!(parse_done_index("selesai"), None);
+    }
+
+    #[test]
+    fn test_week_range() {
+        let gmt7 = FixedOffset::east_opt(7 * 3600).unwrap();
+        // Rabu, 15 Okt 2025 10:00 WIB
+        let now = gmt7.with_ymd_and_hms(2025, 10, 15, 10, 0, 0).unwrap();
+
+        let (start, end) = week_range(now, 0);
+        assert_eq!(start, gmt7.with_ymd_and_hms(2025, 10, 13, 0, 0, 0).unwrap());
+        assert_eq!(end - start, Duration::weeks(1));
+
+        let (start, _) = week_range(now, 2);
+        assert_eq!(start, gmt7.with_ymd_and_hms(2025, 9, 29, 0, 0, 0).unwrap());
+    }
+}
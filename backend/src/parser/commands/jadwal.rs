@@ -0,0 +1,325 @@
+// backend/src/parser/commands/jadwal.rs - Jadwal kuliah dari ScheduleOracle + tugas sebelum kelas
+
+use super::{format_date_id, get_gmt7_now, humanize_deadline, preview_text, sanitize_wa_md, status_dot, CommandContext, CommandResponse};
+use crate::database::crud::{get_active_assignments_for_user, get_course_by_name_or_alias, get_user_parallels, set_user_parallels};
+use crate::models::{AssignmentWithCourse, CommandArgs};
+use crate::parser::ai_extractor::{ScheduleOracle, SCHEDULE_ORACLE};
+use crate::parser::registry::{ArgKind, ArgSpec, Command, HelpLine, HelpSection};
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc, Weekday};
+
+pub struct Jadwal;
+
+#[async_trait]
+impl Command for Jadwal {
+    fn name(&self) -> &'static str {
+        "jadwal"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["schedule"]
+    }
+
+    fn args(&self) -> &'static [ArgSpec] {
+        &[
+            ArgSpec { name: "besok | matkul | kelas <kode>", kind: ArgKind::Text, required: false },
+        ]
+    }
+
+    fn help(&self) -> &'static [HelpLine] {
+        &[
+            HelpLine { section: HelpSection::General, usage: "#jadwal [besok | matkul]", description: "jadwal kelas + tugas sebelum kelas" },
+            HelpLine { section: HelpSection::Personal, usage: "#jadwal kelas <K1 P2 ...>", description: "atur kelas paralel kamu untuk #jadwal" },
+        ]
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>, args: &CommandArgs) -> CommandResponse {
+        println!("🗓️ Jadwal command ({:?}) from {}\n", args.text(0), ctx.user_phone);
+
+        let query = parse_jadwal_query(args.text(0));
+
+        if let JadwalQuery::SetParallels(parallels) = &query {
+            return match set_user_parallels(ctx.pool, ctx.user_phone, parallels).await {
+                Ok(_) if parallels.is_empty() => CommandResponse::Text(
+                    "✅ Kelas paralel kamu dihapus. #jadwal akan menampilkan semua paralel.".to_string(),
+                ),
+                Ok(_) => CommandResponse::Text(format!(
+                    "✅ Kelas paralel kamu disimpan: *{}*\n\n_Ketik #jadwal untuk lihat jadwal hari ini._",
+                    format_parallels(parallels)
+                )),
+                Err(e) => {
+                    eprintln!("❌ Error saving parallels: {}", e);
+                    CommandResponse::Text("❌ Gagal menyimpan kelas paralel.".to_string())
+                }
+            };
+        }
+
+        let parallels = match get_user_parallels(ctx.pool, ctx.user_phone).await {
+            Ok(p) => p,
+            Err(e) => {
+                eprintln!("❌ Error fetching parallels: {}", e);
+                Vec::new()
+            }
+        };
+
+        let tasks: Vec<AssignmentWithCourse> = match get_active_assignments_for_user(ctx.pool, ctx.user_phone).await {
+            Ok(a) => a.into_iter().filter(|a| !a.is_completed).collect(),
+            Err(e) => {
+                eprintln!("❌ Error fetching assignments: {}", e);
+                Vec::new()
+            }
+        };
+
+        let now = get_gmt7_now();
+        let response = match query {
+            JadwalQuery::Today => day_schedule(&SCHEDULE_ORACLE, now.date_naive(), "Hari Ini", &parallels, &tasks),
+            JadwalQuery::Tomorrow => day_schedule(&SCHEDULE_ORACLE, now.date_naive() + Duration::days(1), "Besok", &parallels, &tasks),
+            JadwalQuery::Day(weekday) => {
+                let days_ahead = (7 + weekday.num_days_from_monday() as i64 - now.weekday().num_days_from_monday() as i64) % 7;
+                let date = now.date_naive() + Duration::days(days_ahead);
+                day_schedule(&SCHEDULE_ORACLE, date, day_name_id(weekday), &parallels, &tasks)
+            }
+            JadwalQuery::Course(query) => {
+                // Pakai nama resmi dari DB kalau ada (alias seperti "sd" → "Struktur Data")
+                let course_name = match get_course_by_name_or_alias(ctx.pool, &query).await {
+                    Ok(Some(course)) => course.name,
+                    _ => query,
+                };
+                course_schedule(&SCHEDULE_ORACLE, &course_name, now.naive_local(), &parallels, &tasks)
+            }
+            JadwalQuery::SetParallels(_) => unreachable!(),
+        };
+
+        CommandResponse::Text(response)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum JadwalQuery {
+    Today,
+    Tomorrow,
+    Day(Weekday),
+    Course(String),
+    /// `#jadwal kelas K1 P2`, kosong = hapus
+    SetParallels(Vec<String>),
+}
+
+fn parse_jadwal_query(text: Option<&str>) -> JadwalQuery {
+    let Some(text) = text.map(str::trim).filter(|t| !t.is_empty()) else {
+        return JadwalQuery::Today;
+    };
+
+    let lower = text.to_lowercase();
+    let mut words = lower.split_whitespace();
+    let first = words.next().unwrap_or_default();
+
+    match first {
+        "hari" | "today" | "sekarang" if !lower.contains("besok") => JadwalQuery::Today,
+        "besok" | "tomorrow" => JadwalQuery::Tomorrow,
+        "kelas" | "paralel" | "pararel" => {
+            let codes: Vec<String> = words
+                .filter(|w| !matches!(*w, "hapus" | "off" | "reset"))
+                .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric()).to_string())
+                .filter(|w| is_parallel_code(w))
+                .collect();
+            JadwalQuery::SetParallels(codes)
+        }
+        _ => match parse_weekday_id(first) {
+            Some(weekday) if words.next().is_none() => JadwalQuery::Day(weekday),
+            _ => JadwalQuery::Course(text.to_string()),
+        },
+    }
+}
+
+/// K1 / P2 / R3 style code
+fn is_parallel_code(code: &str) -> bool {
+    let mut chars = code.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic())
+        && code.len() <= 4
+        && code.len() >= 2
+        && chars.all(|c| c.is_ascii_digit())
+}
+
+fn parse_weekday_id(word: &str) -> Option<Weekday> {
+    match word {
+        "senin" => Some(Weekday::Mon),
+        "selasa" => Some(Weekday::Tue),
+        "rabu" => Some(Weekday::Wed),
+        "kamis" => Some(Weekday::Thu),
+        "jumat" | "jum'at" => Some(Weekday::Fri),
+        "sabtu" => Some(Weekday::Sat),
+        "minggu" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+fn day_name_id(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "Senin",
+        Weekday::Tue => "Selasa",
+        Weekday::Wed => "Rabu",
+        Weekday::Thu => "Kamis",
+        Weekday::Fri => "Jumat",
+        Weekday::Sat => "Sabtu",
+        Weekday::Sun => "Minggu",
+    }
+}
+
+fn format_parallels(parallels: &[String]) -> String {
+    parallels.iter().map(|p| p.to_uppercase()).collect::<Vec<_>>().join(", ")
+}
+
+fn day_schedule(
+    oracle: &ScheduleOracle,
+    date: NaiveDate,
+    label: &str,
+    parallels: &[String],
+    tasks: &[AssignmentWithCourse],
+) -> String {
+    let slots: Vec<_> = oracle
+        .classes_on(date.weekday())
+        .into_iter()
+        .filter(|slot| parallels.is_empty() || parallels.contains(&slot.parallel))
+        .collect();
+
+    let mut response = format!(
+        "🗓️ *Jadwal {} — {}, {}*\n",
+        label,
+        day_name_id(date.weekday()),
+        format_date_id(date)
+    );
+    response.push_str(&parallel_note(parallels));
+
+    if slots.is_empty() {
+        response.push_str("\n🎉 Tidak ada kelas.");
+        return response;
+    }
+
+    for slot in &slots {
+        response.push_str(&format!(
+            "\n🕗 {} • *{}* ({})\n",
+            slot.start_time,
+            sanitize_wa_md(&slot.course_name),
+            slot.parallel.to_uppercase()
+        ));
+
+        if let Some(meeting) = meeting_utc(date, &slot.start_time) {
+            response.push_str(&tasks_before(tasks, &slot.course_code, &slot.parallel, meeting));
+        }
+    }
+
+    response
+}
+
+fn course_schedule(
+    oracle: &ScheduleOracle,
+    course_name: &str,
+    now: NaiveDateTime,
+    parallels: &[String],
+    tasks: &[AssignmentWithCourse],
+) -> String {
+    let all_parallels = oracle.parallels_for_course(course_name);
+    if all_parallels.is_empty() {
+        return format!(
+            "❌ Jadwal untuk *{}* tidak ditemukan.\n\n💡 _Coba nama lain, mis. #jadwal strukdat_",
+            sanitize_wa_md(course_name)
+        );
+    }
+
+    // Kalau user sudah atur paralel dan ada yang cocok, tampilkan itu saja
+    let mine: Vec<String> = all_parallels.iter().filter(|p| parallels.contains(p)).cloned().collect();
+    let shown = if mine.is_empty() { all_parallels } else { mine };
+
+    let mut response = format!("🗓️ *Pertemuan Berikutnya — {}*\n", sanitize_wa_md(course_name));
+    response.push_str(&parallel_note(parallels));
+
+    for parallel in &shown {
+        let Some((date, time)) = oracle.next_meeting_after(course_name, parallel, now) else {
+            continue;
+        };
+
+        response.push_str(&format!(
+            "\n🕗 *{}* — {}, {} {}\n",
+            parallel.to_uppercase(),
+            day_name_id(date.weekday()),
+            format_date_id(date),
+            time
+        ));
+
+        if let (Some(meeting), Some(code)) = (meeting_utc(date, &time), course_code_for(oracle, course_name, date.weekday())) {
+            response.push_str(&tasks_before(tasks, &code, parallel, meeting));
+        }
+    }
+
+    response
+}
+
+fn parallel_note(parallels: &[String]) -> String {
+    if parallels.is_empty() {
+        "_Semua paralel • atur kelasmu: #jadwal kelas K1 P2_\n".to_string()
+    } else {
+        format!("_Kelas kamu: {}_\n", format_parallels(parallels))
+    }
+}
+
+fn course_code_for(oracle: &ScheduleOracle, course_name: &str, weekday: Weekday) -> Option<String> {
+    oracle
+        .classes_on(weekday)
+        .into_iter()
+        .find(|slot| ScheduleOracle::course_matches(&slot.course_code, course_name))
+        .map(|slot| slot.course_code)
+}
+
+fn meeting_utc(date: NaiveDate, start_time: &str) -> Option<DateTime<Utc>> {
+    let gmt7 = FixedOffset::east_opt(7 * 3600).unwrap();
+    let time = NaiveTime::parse_from_str(start_time, "%H:%M").ok()?;
+    gmt7.from_local_datetime(&date.and_time(time)).single().map(|d| d.with_timezone(&Utc))
+}
+
+/// Pending tasks for this course/parallel with a deadline before the meeting starts
+fn tasks_before(tasks: &[AssignmentWithCourse], course_code: &str, parallel: &str, meeting: DateTime<Utc>) -> String {
+    let mut lines = String::new();
+
+    for task in tasks {
+        let same_course = ScheduleOracle::course_matches(course_code, &task.course_name);
+        let same_parallel = task
+            .parallel_code
+            .as_deref()
+            .map(|p| p.eq_ignore_ascii_case(parallel) || p == "all")
+            .unwrap_or(true);
+        let due_before = task.deadline.map(|d| d <= meeting).unwrap_or(false);
+
+        if same_course && same_parallel && due_before {
+            lines.push_str(&format!(
+                "   {} {} — {}\n",
+                status_dot(&task.deadline),
+                preview_text(&sanitize_wa_md(&task.title), 25),
+                humanize_deadline(&task.deadline)
+            ));
+        }
+    }
+
+    if lines.is_empty() {
+        lines
+    } else {
+        format!("   📝 _Tugas sebelum kelas:_\n{}", lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_jadwal_query() {
+        assert_eq!(parse_jadwal_query(None), JadwalQuery::Today);
+        assert_eq!(parse_jadwal_query(Some("besok")), JadwalQuery::Tomorrow);
+        assert_eq!(parse_jadwal_query(Some("kamis")), JadwalQuery::Day(Weekday::Thu));
+        assert_eq!(parse_jadwal_query(Some("Strukdat")), JadwalQuery::Course("Strukdat".to_string()));
+        assert_eq!(
+            parse_jadwal_query(Some("kelas K1, P2 lab")),
+            JadwalQuery::SetParallels(vec!["k1".to_string(), "p2".to_string()])
+        );
+        assert_eq!(parse_jadwal_query(Some("kelas hapus")), JadwalQuery::SetParallels(vec![]));
+    }
+}
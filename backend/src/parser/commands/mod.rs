@@ -0,0 +1,421 @@
+// backend/src/parser/commands/mod.rs - Command dispatch + shared formatting helpers
+
+pub mod admin;
+pub mod export;
+pub mod general;
+pub mod history;
+pub mod jadwal;
+pub mod notes;
+pub mod personal;
+pub mod query;
+pub mod remind;
+pub mod review;
+pub mod search;
+pub mod stats;
+pub mod usage;
+
+use crate::conversation::{parse_answer, parse_choice, Answer, ChoicePurpose, PendingAction, CONVERSATIONS};
+use crate::models::{AssignmentWithCourse, BotCommand, CommandArgs};
+use crate::parser::registry::{self, Command, Role, Scope};
+use chrono::{DateTime, FixedOffset, Datelike, NaiveDate, Utc};
+use sqlx::PgPool;
+
+/// Handle bot commands and return response text or forward action
+pub enum CommandResponse {
+    Text(String),
+    ForwardMessage { message_id: String, warning: String },
+    /// File attachment, sent via WAHA /api/sendFile
+    File { filename: String, mimetype: String, data: Vec<u8>, caption: String },
+}
+
+/// Get current time in GMT+7 (Indonesian timezone)
+fn get_gmt7_now() -> DateTime<FixedOffset> {
+    let gmt7 = FixedOffset::east_opt(7 * 3600).unwrap();
+    Utc::now().with_timezone(&gmt7)
+}
+
+/// Everything a command needs to know about where it was sent
+pub struct CommandContext<'a> {
+    pub pool: &'a PgPool,
+    pub user_phone: &'a str,
+    pub user_name: &'a str,
+    pub chat_id: &'a str,
+}
+
+impl CommandContext<'_> {
+    pub fn is_group(&self) -> bool {
+        self.chat_id.ends_with("@g.us")
+    }
+
+    pub fn is_academic_channel(&self) -> bool {
+        let academic_channels = std::env::var("ACADEMIC_CHANNELS").unwrap_or_default();
+        academic_channels
+            .split(',')
+            .map(|s| s.trim())
+            .any(|channel| channel == self.chat_id)
+    }
+
+    /// The DEBUG_GROUP_ID chat, where course reps review extractions
+    pub fn is_debug_group(&self) -> bool {
+        std::env::var("DEBUG_GROUP_ID").is_ok_and(|id| id.trim() == self.chat_id)
+    }
+
+    pub fn is_admin(&self) -> bool {
+        is_admin(self.user_phone)
+    }
+
+    /// Ask a follow-up: the user's next plain message in this chat goes to `handle_reply`
+    pub fn expect_reply(&self, action: PendingAction) {
+        CONVERSATIONS.expect(self.chat_id, self.user_phone, action);
+    }
+}
+
+/// Handle bot commands and return response
+pub async fn handle_command(
+    cmd: BotCommand,
+    user_phone: &str,
+    user_name: &str,
+    chat_id: &str,
+    pool: &PgPool,
+) -> CommandResponse {
+    let ctx = CommandContext { pool, user_phone, user_name, chat_id };
+
+    // A new command abandons any unanswered follow-up question
+    CONVERSATIONS.clear(chat_id, user_phone);
+
+    match cmd {
+        BotCommand::Run { name, args } => {
+            let Some(command) = registry::lookup(name) else {
+                return unknown_command_response(name, user_phone);
+            };
+
+            run(command, &ctx, &args).await
+        }
+
+        BotCommand::InvalidArgs(name) => {
+            println!("⚠️ Invalid arguments for #{} from {}\n", name, user_phone);
+            match registry::lookup(name) {
+                Some(command) => CommandResponse::Text(format!(
+                    "⚠️ Format salah. Gunakan: *{}*\n_{}_",
+                    registry::usage(command),
+                    command.help().first().map(|h| h.description).unwrap_or_default()
+                )),
+                None => unknown_command_response(name, user_phone),
+            }
+        }
+
+        BotCommand::UnknownCommand(cmd) => unknown_command_response(&cmd, user_phone),
+    }
+}
+
+/// Consume a plain reply to a pending follow-up question.
+/// Returns None when nothing was pending (or it expired), so the message is handled normally.
+pub async fn handle_reply(
+    text: &str,
+    user_phone: &str,
+    user_name: &str,
+    chat_id: &str,
+    pool: &PgPool,
+) -> Option<CommandResponse> {
+    let action = CONVERSATIONS.take(chat_id, user_phone)?;
+    let ctx = CommandContext { pool, user_phone, user_name, chat_id };
+
+    println!("💬 Reply from {} to pending {:?}", user_phone, action);
+
+    let response = match action {
+        PendingAction::ConfirmDelete { assignment_id, title, course_name } => match parse_answer(text) {
+            Some(Answer::Yes) => admin::delete_confirmed(&ctx, assignment_id, &title, &course_name).await,
+            Some(Answer::No) => CommandResponse::Text("👌 Oke, tugas tidak jadi dihapus.".to_string()),
+            None => {
+                // Not an answer: forget the question and let the message through
+                println!("↩️ Not a yes/no answer, dropping pending delete");
+                return None;
+            }
+        },
+        PendingAction::Choose { purpose, options } => match parse_choice(text, options.len()) {
+            Some(n) => {
+                let chosen = &options[n - 1];
+                match purpose {
+                    ChoicePurpose::Restore => admin::restore_chosen(&ctx, chosen.id).await,
+                }
+            }
+            None if parse_answer(text) == Some(Answer::No) => {
+                CommandResponse::Text("👌 Oke, dibatalkan.".to_string())
+            }
+            None => {
+                println!("↩️ Not a choice, dropping pending pick");
+                return None;
+            }
+        },
+    };
+
+    Some(response)
+}
+
+/// Check scope & role, then execute
+async fn run(command: &dyn Command, ctx: &CommandContext<'_>, args: &CommandArgs) -> CommandResponse {
+    if let Some(denied) = check_access(command, ctx, args) {
+        println!("⛔ #{} denied for {} in chat {}\n", command.name(), ctx.user_phone, ctx.chat_id);
+        return denied;
+    }
+
+    command.execute(ctx, args).await
+}
+
+/// Scope & role check shared by every registered command
+fn check_access(command: &dyn Command, ctx: &CommandContext<'_>, args: &CommandArgs) -> Option<CommandResponse> {
+    let scope_error = match command.scope() {
+        Scope::Anywhere => None,
+        Scope::DmOnly if ctx.is_group() => Some(
+            "⚠️ _Command ini hanya bisa dipakai di chat pribadi._".to_string(),
+        ),
+        Scope::AcademicChannel if !ctx.is_academic_channel() => Some(
+            "⛔ *AKSES DITOLAK*\n\n\
+            Command ini hanya boleh dilakukan di Grup Official/Academic Channel oleh PJ Matkul.\n\
+            _Jangan iseng ya!_ 👮"
+                .to_string(),
+        ),
+        Scope::NotAcademicChannel if ctx.is_academic_channel() => Some(
+            "⚠️ _Command ini tidak boleh dijalankan di grup akademik._\n\
+            Ketik command ini di chat pribadi ya.\n\n\
+            💡 _Gunakan #todo untuk lihat daftar tugas pribadi kamu._"
+                .to_string(),
+        ),
+        Scope::DebugGroup if !ctx.is_debug_group() => Some(
+            "⚠️ _Command ini hanya bisa dipakai di grup debug (review tugas)._".to_string(),
+        ),
+        _ => None,
+    };
+
+    if let Some(text) = scope_error {
+        return Some(CommandResponse::Text(text));
+    }
+
+    if command.role(args) == Role::Admin && !ctx.is_admin() {
+        return Some(CommandResponse::Text(format!(
+            "⛔ *AKSES DITOLAK*\n\n\
+            Perintah ini hanya untuk admin / PJ Matkul.\n\
+            _Ketik #{} untuk versi pribadinya, atau #help untuk daftar command._",
+            command.name()
+        )));
+    }
+
+    None
+}
+
+fn unknown_command_response(cmd: &str, user_phone: &str) -> CommandResponse {
+    println!("❓ Unknown command '{}' from {}\n", cmd, user_phone);
+
+    match registry::suggest(cmd) {
+        Some(suggestion) => CommandResponse::Text(format!(
+            "❓ Command tidak dikenali: *{}*\n\nMaksud kamu *#{}*?",
+            sanitize_wa_md(cmd),
+            suggestion
+        )),
+        None => CommandResponse::Text(format!(
+            "❓ Command tidak dikenali: *{}*\n\nKetik *#help* untuk melihat daftar command yang tersedia.",
+            sanitize_wa_md(cmd)
+        )),
+    }
+}
+
+/// Admin = nomor yang terdaftar di ADMIN_NUMBERS (boleh dengan atau tanpa @c.us)
+fn is_admin(user_phone: &str) -> bool {
+    let admins = std::env::var("ADMIN_NUMBERS").unwrap_or_default();
+    let phone = user_phone.split('@').next().unwrap_or(user_phone);
+
+    admins
+        .split(',')
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .any(|admin| admin == user_phone || admin.split('@').next() == Some(phone))
+}
+
+fn format_percent(rate: Option<f32>) -> String {
+    rate.map(|r| format!("{:.0}%", r * 100.0))
+        .unwrap_or_else(|| "—".to_string())
+}
+
+/// Pick the n-th (1-based) incomplete assignment, same numbering as `#todo`
+fn nth_incomplete(assignments: &[AssignmentWithCourse], index: u32) -> Option<&AssignmentWithCourse> {
+    let idx = (index as usize).checked_sub(1)?;
+    assignments.iter().filter(|a| !a.is_completed).nth(idx)
+}
+
+fn format_assignments_list(
+    assignments: Vec<crate::models::AssignmentWithCourse>,
+    header: &str,
+    show_legend: bool,
+    user_specific: bool,
+) -> CommandResponse {
+    let filtered_assignments: Vec<_> = if user_specific {
+        assignments.into_iter().filter(|a| !a.is_completed).collect()
+    } else {
+        assignments
+    };
+
+    if filtered_assignments.is_empty() {
+        if user_specific {
+            return CommandResponse::Text(format!(
+                "{}\n\n🎉 *Selamat!* Semua tugas sudah selesai!\n✨ _Kamu keren banget!_",
+                header
+            ));
+        } else if show_legend {
+            return CommandResponse::Text(format!(
+                "{}\n\n📭 Belum ada tugas untuk periode ini.",
+                header
+            ));
+        } else {
+            return CommandResponse::Text(format!(
+                "{}\n\n📭 Belum ada tugas.",
+                header
+            ));
+        }
+    }
+
+    let mut response = String::new();
+    response.push_str(header);
+    response.push('\n');
+
+    if show_legend {
+        response.push_str("\nKeterangan:\n🔴 Deadline 0–2 hari\n🟢 Deadline > 2 hari\n⚪ Belum ada deadline\n\n");
+    } else {
+        response.push('\n');
+    }
+
+    for (i, a) in filtered_assignments.iter().enumerate() {
+        response.push_str(&format_assignment_entry(i + 1, a));
+    }
+
+    if user_specific {
+        response.push_str("\n_🔎 Detail: #<nomor>_\n_✅ Selesai: #done <nomor>_");
+    } else {
+        response.push_str("\n_💡 Gunakan #todo untuk list personal_");
+    }
+    
+    CommandResponse::Text(response)
+}
+
+/// One list entry (status, judul, matkul, deadline, deskripsi, kode), numbered `number`
+fn format_assignment_entry(number: usize, a: &AssignmentWithCourse) -> String {
+    let status_emoji = status_dot(&a.deadline);
+    let title_fmt = preview_text(&sanitize_wa_md(&a.title), 25);
+    let due_text = humanize_deadline(&a.deadline);
+    let course = sanitize_wa_md(&a.course_name);
+
+    let desc_line = a
+        .description
+        .as_ref()
+        .map(|d| sanitize_wa_md(d))
+        .map(|d| d.trim().to_string())
+        .filter(|d| !d.is_empty())
+        .map(|d| format!("📝 {}", preview_text(&d, 25)))
+        .unwrap_or_default();
+
+    let code_line = a
+        .parallel_code
+        .as_ref()
+        .map(|c| format!("🧩 Kode: {}", sanitize_wa_md(c)))
+        .unwrap_or_default();
+
+    let mut entry = format!("{} *[{}] [{}]*\n", status_emoji, number, title_fmt);
+    entry.push_str(&format!("📌 {}\n", course));
+    entry.push_str(&format!("⏰ Deadline: {}\n", due_text));
+
+    if !desc_line.is_empty() {
+        entry.push_str(&format!("{}\n", desc_line));
+    }
+    if !code_line.is_empty() {
+        entry.push_str(&format!("{}\n", code_line));
+    }
+    entry.push('\n');
+    entry
+}
+
+/// Status indicator based on deadline
+#[allow(non_snake_case)]
+fn status_dot(deadline: &Option<DateTime<Utc>>) -> &'static str {
+    match deadline {
+        Some(d) => {
+            let days = days_left(d);
+            if days < 1 {
+                "🔴"
+            } else if days == 1 {
+                "🟠"
+            } else if days == 2 {
+                "🟡"
+            } else {
+                "🟢"
+            }
+        }
+        None => "⚪" // No deadline set
+    }
+}
+
+fn days_left(deadline_utc: &DateTime<Utc>) -> i64 {
+    let gmt7 = FixedOffset::east_opt(7 * 3600).unwrap();
+    let now = get_gmt7_now().date_naive();
+    let due = deadline_utc.with_timezone(&gmt7).date_naive();
+    (due - now).num_days()
+}
+
+#[allow(non_snake_case)]
+fn humanize_deadline(deadline: &Option<DateTime<Utc>>) -> String {
+    match deadline {
+        Some(deadline_utc) => {
+            let gmt7 = FixedOffset::east_opt(7 * 3600).unwrap();
+            let deadline_gmt7 = deadline_utc.with_timezone(&gmt7);
+            let now = get_gmt7_now().date_naive();
+            let due = deadline_gmt7.date_naive();
+            
+            let delta = (due - now).num_days();
+            let date_str = format_date_id(due);
+            let time_str = deadline_gmt7.format("%d-%m %H:%M").to_string();
+
+            match delta {
+                0 => format!("Hari ini ({} {})", date_str, time_str),
+                1 => format!("Besok ({} {})", date_str, time_str),
+                d if d >= 2 => format!("H-{} ({} {})", d, date_str, time_str), 
+                -1 => format!("Kemarin ({} {})", date_str, time_str),
+                d => format!("lewat {} hari ({} {})", d.abs(), date_str, time_str),
+            }
+        }
+        None => "⚠️ Belum ada deadline".to_string()
+    }
+}
+
+fn format_date_id(date: NaiveDate) -> String {
+    let day = date.day();
+    let month = match date.month() {
+        1 => "Jan", 2 => "Feb", 3 => "Mar", 4 => "Apr",
+        5 => "Mei", 6 => "Jun", 7 => "Jul", 8 => "Agu",
+        9 => "Sep", 10 => "Okt", 11 => "Nov", 12 => "Des",
+        _ => "???",
+    };
+    format!("{} {} {}", day, month, date.year())
+}
+
+fn preview_text(s: &str, max_chars: usize) -> String {
+    let one_line = s
+        .replace('\n', " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut out = String::new();
+    for (i, ch) in one_line.chars().enumerate() {
+        if i >= max_chars {
+            out.push('…');
+            return out;
+        }
+        out.push(ch);
+    }
+    out
+}
+
+fn sanitize_wa_md(s: &str) -> String {
+    s.replace('*', "×")
+        .replace('_', " ")
+        .replace('~', "-")
+        .replace('`', "'")
+}
\ No newline at end of file
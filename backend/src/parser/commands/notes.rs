@@ -0,0 +1,200 @@
+// backend/src/parser/commands/notes.rs - Catatan pribadi per tugas (#note, #notes)
+//
+// Notes are private: both commands are DM-only and the #<nomor> card only
+// shows them outside groups. Group lists and broadcasts never read this table.
+
+use super::{humanize_deadline, nth_incomplete, preview_text, sanitize_wa_md, CommandContext, CommandResponse};
+use crate::database::crud::{
+    add_assignment_note, delete_assignment_notes, get_active_assignments_for_user, get_assignment_notes,
+};
+use crate::models::{AssignmentNote, CommandArgs};
+use crate::parser::registry::{ArgKind, ArgSpec, Command, HelpLine, HelpSection, Scope};
+use async_trait::async_trait;
+
+const MAX_NOTE_CHARS: usize = 500;
+
+pub struct Note;
+
+#[async_trait]
+impl Command for Note {
+    fn name(&self) -> &'static str {
+        "note"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["catat", "catatan"]
+    }
+
+    fn args(&self) -> &'static [ArgSpec] {
+        &[
+            ArgSpec { name: "id", kind: ArgKind::Index, required: true },
+            ArgSpec { name: "teks | hapus", kind: ArgKind::Text, required: true },
+        ]
+    }
+
+    fn help(&self) -> &'static [HelpLine] {
+        &[
+            HelpLine { section: HelpSection::Personal, usage: "#note <id> <teks>", description: "catatan pribadi untuk tugas (hapus: #note <id> hapus)" },
+        ]
+    }
+
+    fn scope(&self) -> Scope {
+        Scope::DmOnly
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>, args: &CommandArgs) -> CommandResponse {
+        let index = args.index(0).unwrap_or_default();
+        let text = args.text(1).unwrap_or_default().trim();
+
+        println!("🗒️ Note command for assignment {} from {}\n", index, ctx.user_phone);
+
+        let assignments = match get_active_assignments_for_user(ctx.pool, ctx.user_phone).await {
+            Ok(a) => a,
+            Err(e) => {
+                eprintln!("❌ Error fetching assignments: {}", e);
+                return CommandResponse::Text(
+                    "❌ Maaf, terjadi kesalahan saat mengambil data tugas.\n_Coba lagi sebentar ya._".to_string(),
+                );
+            }
+        };
+
+        let Some(assignment) = nth_incomplete(&assignments, index) else {
+            return CommandResponse::Text(format!(
+                "❌ Tugas nomor *{}* tidak ditemukan di to-do list kamu.\n\n\
+                💡 _Tip: Ketik #todo untuk lihat daftar tugas._",
+                index
+            ));
+        };
+        let title = sanitize_wa_md(&assignment.title);
+
+        if matches!(text.to_lowercase().as_str(), "hapus" | "delete" | "clear") {
+            return match delete_assignment_notes(ctx.pool, ctx.user_phone, assignment.id).await {
+                Ok(0) => CommandResponse::Text(format!("📭 Belum ada catatan untuk *{}*.", title)),
+                Ok(n) => CommandResponse::Text(format!("🗑️ {} catatan untuk *{}* dihapus.", n, title)),
+                Err(e) => CommandResponse::Text(format!("❌ Database error: {}", e)),
+            };
+        }
+
+        if text.chars().count() > MAX_NOTE_CHARS {
+            return CommandResponse::Text(format!(
+                "⚠️ Catatan terlalu panjang (maks {} karakter).",
+                MAX_NOTE_CHARS
+            ));
+        }
+
+        match add_assignment_note(ctx.pool, ctx.user_phone, assignment.id, text).await {
+            Ok(()) => CommandResponse::Text(format!(
+                "🗒️ Catatan disimpan untuk *{}*.\n\n\
+                _Lihat di #{} atau #notes • Hapus: #note {} hapus_",
+                title, index, index
+            )),
+            Err(e) => CommandResponse::Text(format!("❌ Database error: {}", e)),
+        }
+    }
+}
+
+pub struct Notes;
+
+#[async_trait]
+impl Command for Notes {
+    fn name(&self) -> &'static str {
+        "notes"
+    }
+
+    fn help(&self) -> &'static [HelpLine] {
+        &[
+            HelpLine { section: HelpSection::Personal, usage: "#notes", description: "semua catatan pribadi kamu" },
+        ]
+    }
+
+    fn scope(&self) -> Scope {
+        Scope::DmOnly
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>, _args: &CommandArgs) -> CommandResponse {
+        println!("🗒️ Notes command from {}\n", ctx.user_phone);
+
+        match get_assignment_notes(ctx.pool, ctx.user_phone, None).await {
+            Ok(notes) if notes.is_empty() => CommandResponse::Text(
+                "🗒️ *Catatan Kamu*\n\n📭 Belum ada catatan.\n\n💡 _Tambah dengan #note <nomor> <teks>_".to_string(),
+            ),
+            Ok(notes) => CommandResponse::Text(format_notes(&notes)),
+            Err(e) => {
+                eprintln!("❌ Error fetching notes: {}", e);
+                CommandResponse::Text("❌ Gagal mengambil catatan.".to_string())
+            }
+        }
+    }
+}
+
+/// Notes grouped per assignment (query already orders them that way)
+fn format_notes(notes: &[AssignmentNote]) -> String {
+    let mut response = String::from("🗒️ *Catatan Kamu*\n\n");
+
+    for (i, note) in notes.iter().enumerate() {
+        let new_group = i == 0 || notes[i - 1].assignment_id != note.assignment_id;
+        if new_group {
+            if i > 0 {
+                response.push('\n');
+            }
+            response.push_str(&format!(
+                "*{}*\n📌 {}\n⏰ {}\n",
+                preview_text(&sanitize_wa_md(&note.title), 25),
+                sanitize_wa_md(&note.course_name),
+                humanize_deadline(&note.deadline)
+            ));
+        }
+        response.push_str(&format!("• {}\n", sanitize_wa_md(&note.note)));
+    }
+
+    response.push_str("\n_🗒️ Tambah: #note <nomor> <teks>_");
+    response
+}
+
+/// Lines appended to the #<nomor> detail card
+pub(super) fn notes_section(notes: &[AssignmentNote]) -> String {
+    if notes.is_empty() {
+        return String::new();
+    }
+
+    let mut section = String::from("\n\n🗒️ *Catatan kamu:*");
+    for note in notes {
+        section.push_str(&format!("\n• {}", sanitize_wa_md(&note.note)));
+    }
+    section
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn note(assignment: u128, title: &str, text: &str) -> AssignmentNote {
+        AssignmentNote {
+            assignment_id: Uuid::from_u128(assignment),
+            course_name: "Pemrograman".to_string(),
+            title: title.to_string(),
+            deadline: None,
+            note: text.to_string(),
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_format_notes_groups_by_assignment() {
+        let notes = [
+            note(1, "LKP 5", "tanya asdos no. 3"),
+            note(1, "LKP 5", "kumpul via LMS"),
+            note(2, "Kuis 2", "bab 4-6"),
+        ];
+        let text = format_notes(&notes);
+
+        assert_eq!(text.matches("*LKP 5*").count(), 1);
+        assert!(text.contains("• tanya asdos no. 3\n• kumpul via LMS"));
+        assert!(text.contains("*Kuis 2*"));
+
+        assert_eq!(notes_section(&[]), "");
+        assert!(notes_section(&notes[..1]).ends_with("• tanya asdos no. 3"));
+    }
+}
@@ -0,0 +1,495 @@
+// backend/src/parser/commands/personal.rs - To-do list pribadi (todo, detail, done, snooze, hide)
+
+use super::{
+    format_assignments_list, humanize_deadline, nth_incomplete, preview_text, sanitize_wa_md, status_dot,
+    CommandContext, CommandResponse,
+};
+use super::notes::notes_section;
+use crate::database::crud::{
+    get_active_assignments_for_user, get_assignment_notes, get_hidden_assignments_for_user, get_last_completed_assignment,
+    hide_assignment_for_user, mark_assignment_complete, restore_assignment_for_user,
+    snooze_assignment_for_user, unmark_assignment_complete,
+};
+use crate::models::CommandArgs;
+use crate::parser::registry::{ArgKind, ArgSpec, Command, HelpLine, HelpSection, Scope};
+use async_trait::async_trait;
+use chrono::{Duration, FixedOffset, Utc};
+
+pub struct Todo;
+
+#[async_trait]
+impl Command for Todo {
+    fn name(&self) -> &'static str {
+        "todo"
+    }
+
+    fn help(&self) -> &'static [HelpLine] {
+        &[
+            HelpLine { section: HelpSection::Personal, usage: "#todo", description: "lihat tugas pribadi kamu" },
+        ]
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>, _args: &CommandArgs) -> CommandResponse {
+        println!("✅ Todo command received from {}", ctx.user_phone);
+
+        match get_active_assignments_for_user(ctx.pool, ctx.user_phone).await {
+            Ok(assignments) => {
+                let header = format!("*[To-Do] User ID: {}*", ctx.user_name);
+                format_assignments_list(assignments, &header, false, true)
+            }
+            Err(e) => {
+                eprintln!("❌ Error fetching assignments: {}", e);
+                CommandResponse::Text(
+                    "❌ Maaf, terjadi kesalahan saat mengambil data tugas.\n_Coba lagi sebentar ya._"
+                        .to_string(),
+                )
+            }
+        }
+    }
+}
+
+pub struct Expand;
+
+#[async_trait]
+impl Command for Expand {
+    fn name(&self) -> &'static str {
+        "expand"
+    }
+
+    fn args(&self) -> &'static [ArgSpec] {
+        &[
+            ArgSpec { name: "id", kind: ArgKind::Index, required: true },
+        ]
+    }
+
+    fn help(&self) -> &'static [HelpLine] {
+        &[
+            HelpLine { section: HelpSection::Personal, usage: "#<id>", description: "lihat detail tugas dari #todo" },
+        ]
+    }
+
+    fn scope(&self) -> Scope {
+        Scope::NotAcademicChannel
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>, args: &CommandArgs) -> CommandResponse {
+        let index = args.index(0).unwrap_or_default();
+
+        println!(
+            "🔍 Expand command for assignment {} from {} in chat {}\n",
+            index, ctx.user_phone, ctx.chat_id
+        );
+
+        match get_active_assignments_for_user(ctx.pool, ctx.user_phone).await {
+            Ok(assignments) => {
+                let incomplete: Vec<_> = assignments
+                    .into_iter()
+                    .filter(|a| !a.is_completed)
+                    .collect();
+
+                let idx = (index as usize).saturating_sub(1);
+
+                if idx >= incomplete.len() {
+                    CommandResponse::Text(format!(
+                        "❌ Tugas *#{}* tidak ditemukan di to-do list kamu.\n\n\
+                        💡 _Tip: Ketik #todo untuk lihat daftar tugas._",
+                        index
+                    ))
+                } else {
+                    let assignment = &incomplete[idx];
+
+                    let Some(message_id) = assignment.message_ids.last().cloned() else {
+                        return CommandResponse::Text(
+                            "❌ Pesan asli untuk tugas ini belum tersimpan.\n\
+                            Coba cek daftar dengan *#todo*."
+                                .to_string(),
+                        );
+                    };
+
+                    let status = status_dot(&assignment.deadline);
+                    let done_status = if assignment.is_completed { 
+                        "✅ SUDAH SELESAI" 
+                    } else { 
+                        "⬜ BELUM SELESAI" 
+                    };
+                    
+                    let due_text = humanize_deadline(&assignment.deadline);
+
+                    let course = sanitize_wa_md(&assignment.course_name);
+                    let title = sanitize_wa_md(&assignment.title);
+
+                    let desc_full = assignment
+                        .description
+                        .as_ref()
+                        .map(|d| sanitize_wa_md(d))
+                        .map(|d| d.trim().to_string())
+                        .filter(|d| !d.is_empty())
+                        .unwrap_or_else(|| "—".to_string());
+
+                    let code_line = assignment
+                        .parallel_code
+                        .as_ref()
+                        .map(|c| format!("\n🧩 Pararel: {}", sanitize_wa_md(c)))
+                        .unwrap_or_default();
+
+                    // Catatan pribadi hanya tampil di chat pribadi
+                    let notes = if ctx.is_group() {
+                        String::new()
+                    } else {
+                        match get_assignment_notes(ctx.pool, ctx.user_phone, Some(assignment.id)).await {
+                            Ok(notes) => notes_section(&notes),
+                            Err(e) => {
+                                eprintln!("❌ Error fetching notes: {}", e);
+                                String::new()
+                            }
+                        }
+                    };
+
+                    CommandResponse::ForwardMessage {
+                        message_id,
+                        warning: format!(
+                            "🧾 *Detail Tugas #{}*\nStatus: {}\n\n{} *{}*\n📌 {}\n⏰ Deadline: {}\n📝 {}{}{}\n\n\
+                            _Keterangan: 🔴 deadline 0–2 hari lagi • 🟢 deadline > 2 hari_",
+                            index,
+                            done_status,
+                            status,
+                            title,
+                            course,
+                            due_text,
+                            desc_full,
+                            code_line,
+                            notes
+                        ),
+                    }
+                }
+            }
+            Err(e) => {
+                eprintln!("❌ Error fetching assignments: {}", e);
+                CommandResponse::Text(
+                    "❌ Maaf, terjadi kesalahan saat mengambil data tugas.\n_Coba lagi sebentar ya._"
+                        .to_string(),
+                )
+            }
+        }
+    }
+}
+
+pub struct Done;
+
+#[async_trait]
+impl Command for Done {
+    fn name(&self) -> &'static str {
+        "done"
+    }
+
+    fn args(&self) -> &'static [ArgSpec] {
+        &[
+            ArgSpec { name: "id", kind: ArgKind::Index, required: true },
+        ]
+    }
+
+    fn help(&self) -> &'static [HelpLine] {
+        &[
+            HelpLine { section: HelpSection::Personal, usage: "#done <id>", description: "tandai selesai" },
+        ]
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>, args: &CommandArgs) -> CommandResponse {
+        let id = args.index(0).unwrap_or_default();
+
+        println!("✅ Done command for assignment {} from {}\n", id, ctx.user_phone);
+        
+        match get_active_assignments_for_user(ctx.pool, ctx.user_phone).await {
+            Ok(assignments) => {
+                let incomplete: Vec<_> = assignments
+                    .into_iter()
+                    .filter(|a| !a.is_completed)
+                    .collect();
+
+                let idx = (id as usize).saturating_sub(1);
+                
+                if idx >= incomplete.len() {
+                    return CommandResponse::Text(format!(
+                        "❌ Tugas nomor *{}* tidak ditemukan di to-do list kamu.\n\n\
+                        💡 _Tip: Ketik #todo untuk lihat daftar tugas._",
+                        id
+                    ));
+                }
+                
+                let assignment = &incomplete[idx];
+                
+                match mark_assignment_complete(ctx.pool, assignment.id, ctx.user_phone).await {
+                    Ok(_) => CommandResponse::Text(format!(
+                        "✅ Mantap! Tugas *{}* selesai.\n\n\
+                        _Salah tandai? Ketik #undo_",
+                        sanitize_wa_md(&assignment.title)
+                    )),
+                    Err(e) => CommandResponse::Text(format!("❌ Database error: {}", e))
+                }
+            }
+            Err(e) => CommandResponse::Text(format!("❌ Gagal mengambil data: {}", e))
+        }
+    }
+}
+
+pub struct Undo;
+
+#[async_trait]
+impl Command for Undo {
+    fn name(&self) -> &'static str {
+        "undo"
+    }
+
+    fn help(&self) -> &'static [HelpLine] {
+        &[
+            HelpLine { section: HelpSection::Personal, usage: "#undo", description: "batalkan #done terakhir" },
+        ]
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>, _args: &CommandArgs) -> CommandResponse {
+        println!("↩️  Undo command from {}\n", ctx.user_phone);
+        
+        match get_last_completed_assignment(ctx.pool, ctx.user_phone).await {
+            Ok(Some(assignment)) => {
+                match unmark_assignment_complete(ctx.pool, assignment.id, ctx.user_phone).await {
+                    Ok(_) => CommandResponse::Text(format!(
+                        "↩️ Oke! Tugas *{}* ditandai belum selesai.\n\n\
+                        _Ketik #todo untuk lihat daftar terbaru._",
+                        sanitize_wa_md(&assignment.title)
+                    )),
+                    Err(e) => CommandResponse::Text(format!("❌ Database error: {}", e))
+                }
+            }
+            Ok(None) => {
+                CommandResponse::Text(
+                    "❌ Tidak ada tugas yang baru saja kamu selesaikan.\n\n\
+                    💡 _#undo hanya bisa membatalkan tugas terakhir yang kamu tandai selesai._"
+                        .to_string(),
+                )
+            }
+            Err(e) => {
+                eprintln!("❌ Error fetching last completed: {}", e);
+                CommandResponse::Text(
+                    "❌ Gagal mengambil data tugas terakhir."
+                        .to_string(),
+                )
+            }
+        }
+    }
+}
+
+pub struct Snooze;
+
+#[async_trait]
+impl Command for Snooze {
+    fn name(&self) -> &'static str {
+        "snooze"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["tunda"]
+    }
+
+    fn args(&self) -> &'static [ArgSpec] {
+        &[
+            ArgSpec { name: "id", kind: ArgKind::Index, required: true },
+            // Maksimal 30 hari
+            ArgSpec { name: "durasi", kind: ArgKind::Duration { max_minutes: 30 * 1440 }, required: true },
+        ]
+    }
+
+    fn help(&self) -> &'static [HelpLine] {
+        &[
+            HelpLine { section: HelpSection::Personal, usage: "#snooze <id> <durasi>", description: "sembunyikan sementara (mis. 3h, 2d)" },
+        ]
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>, args: &CommandArgs) -> CommandResponse {
+        let id = args.index(0).unwrap_or_default();
+        let minutes = args.minutes(1).unwrap_or_default();
+
+        println!("😴 Snooze command for assignment {} ({} min) from {}\n", id, minutes, ctx.user_phone);
+
+        let assignments = match get_active_assignments_for_user(ctx.pool, ctx.user_phone).await {
+            Ok(a) => a,
+            Err(e) => return CommandResponse::Text(format!("❌ Gagal mengambil data: {}", e)),
+        };
+
+        let Some(assignment) = nth_incomplete(&assignments, id) else {
+            return CommandResponse::Text(format!(
+                "❌ Tugas nomor *{}* tidak ditemukan di to-do list kamu.\n\n\
+                💡 _Tip: Ketik #todo untuk lihat daftar tugas._",
+                id
+            ));
+        };
+
+        let until = Utc::now() + Duration::minutes(minutes as i64);
+        let gmt7 = FixedOffset::east_opt(7 * 3600).unwrap();
+
+        match snooze_assignment_for_user(ctx.pool, assignment.id, ctx.user_phone, until).await {
+            Ok(_) => CommandResponse::Text(format!(
+                "😴 Tugas *{}* disembunyikan sampai {} WIB.\n\n\
+                _Lihat atau kembalikan lewat #hidden_",
+                sanitize_wa_md(&assignment.title),
+                until.with_timezone(&gmt7).format("%d-%m %H:%M")
+            )),
+            Err(e) => CommandResponse::Text(format!("❌ Database error: {}", e)),
+        }
+    }
+}
+
+pub struct Hide;
+
+#[async_trait]
+impl Command for Hide {
+    fn name(&self) -> &'static str {
+        "hide"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["sembunyikan"]
+    }
+
+    fn args(&self) -> &'static [ArgSpec] {
+        &[
+            ArgSpec { name: "id", kind: ArgKind::Index, required: true },
+        ]
+    }
+
+    fn help(&self) -> &'static [HelpLine] {
+        &[
+            HelpLine { section: HelpSection::Personal, usage: "#hide <id>", description: "sembunyikan tugas yang tidak berlaku untukmu" },
+        ]
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>, args: &CommandArgs) -> CommandResponse {
+        let id = args.index(0).unwrap_or_default();
+
+        println!("🙈 Hide command for assignment {} from {}\n", id, ctx.user_phone);
+
+        let assignments = match get_active_assignments_for_user(ctx.pool, ctx.user_phone).await {
+            Ok(a) => a,
+            Err(e) => return CommandResponse::Text(format!("❌ Gagal mengambil data: {}", e)),
+        };
+
+        let Some(assignment) = nth_incomplete(&assignments, id) else {
+            return CommandResponse::Text(format!(
+                "❌ Tugas nomor *{}* tidak ditemukan di to-do list kamu.\n\n\
+                💡 _Tip: Ketik #todo untuk lihat daftar tugas._",
+                id
+            ));
+        };
+
+        match hide_assignment_for_user(ctx.pool, assignment.id, ctx.user_phone).await {
+            Ok(_) => CommandResponse::Text(format!(
+                "🙈 Tugas *{}* disembunyikan dari list & pengingat kamu.\n\n\
+                _Tidak dihitung selesai. Kembalikan lewat #hidden_",
+                sanitize_wa_md(&assignment.title)
+            )),
+            Err(e) => CommandResponse::Text(format!("❌ Database error: {}", e)),
+        }
+    }
+}
+
+pub struct Hidden;
+
+#[async_trait]
+impl Command for Hidden {
+    fn name(&self) -> &'static str {
+        "hidden"
+    }
+
+    fn help(&self) -> &'static [HelpLine] {
+        &[
+            HelpLine { section: HelpSection::Personal, usage: "#hidden", description: "daftar tugas tersembunyi" },
+        ]
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>, _args: &CommandArgs) -> CommandResponse {
+        println!("🙈 Hidden list command from {}\n", ctx.user_phone);
+
+        match get_hidden_assignments_for_user(ctx.pool, ctx.user_phone).await {
+            Ok(hidden) if hidden.is_empty() => CommandResponse::Text(
+                "🙈 *Tugas Tersembunyi*\n\n📭 Tidak ada tugas yang kamu hide atau snooze.".to_string(),
+            ),
+            Ok(hidden) => {
+                let gmt7 = FixedOffset::east_opt(7 * 3600).unwrap();
+                let mut response = String::from("🙈 *Tugas Tersembunyi*\n\n");
+
+                for (i, a) in hidden.iter().enumerate() {
+                    let state = if a.hidden {
+                        "🙈 Disembunyikan".to_string()
+                    } else {
+                        let until = a.snoozed_until.map(|u| u.with_timezone(&gmt7).format("%d-%m %H:%M").to_string());
+                        format!("😴 Snooze sampai {} WIB", until.unwrap_or_default())
+                    };
+
+                    response.push_str(&format!("*[{}] [{}]*\n", i + 1, preview_text(&sanitize_wa_md(&a.title), 25)));
+                    response.push_str(&format!("📌 {}\n", sanitize_wa_md(&a.course_name)));
+                    response.push_str(&format!("⏰ Deadline: {}\n", humanize_deadline(&a.deadline)));
+                    response.push_str(&format!("{}\n\n", state));
+                }
+
+                response.push_str("_↩️ Kembalikan: #unhide <nomor>_");
+                CommandResponse::Text(response)
+            }
+            Err(e) => {
+                eprintln!("❌ Error fetching hidden assignments: {}", e);
+                CommandResponse::Text("❌ Gagal mengambil daftar tugas tersembunyi.".to_string())
+            }
+        }
+    }
+}
+
+pub struct Unhide;
+
+#[async_trait]
+impl Command for Unhide {
+    fn name(&self) -> &'static str {
+        "unhide"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["tampilkan"]
+    }
+
+    fn args(&self) -> &'static [ArgSpec] {
+        &[
+            ArgSpec { name: "no", kind: ArgKind::Index, required: true },
+        ]
+    }
+
+    fn help(&self) -> &'static [HelpLine] {
+        &[
+            HelpLine { section: HelpSection::Personal, usage: "#unhide <no>", description: "kembalikan tugas dari #hidden" },
+        ]
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>, args: &CommandArgs) -> CommandResponse {
+        let id = args.index(0).unwrap_or_default();
+
+        println!("👀 Unhide command for assignment {} from {}\n", id, ctx.user_phone);
+
+        let hidden = match get_hidden_assignments_for_user(ctx.pool, ctx.user_phone).await {
+            Ok(h) => h,
+            Err(e) => return CommandResponse::Text(format!("❌ Gagal mengambil data: {}", e)),
+        };
+
+        let Some(assignment) = (id as usize).checked_sub(1).and_then(|idx| hidden.get(idx)) else {
+            return CommandResponse::Text(format!(
+                "❌ Tugas nomor *{}* tidak ada di daftar tersembunyi.\n\n\
+                💡 _Tip: Ketik #hidden untuk lihat daftarnya._",
+                id
+            ));
+        };
+
+        match restore_assignment_for_user(ctx.pool, assignment.id, ctx.user_phone).await {
+            Ok(_) => CommandResponse::Text(format!(
+                "👀 Tugas *{}* muncul lagi di to-do list kamu.\n\n\
+                _Ketik #todo untuk lihat daftar terbaru._",
+                sanitize_wa_md(&assignment.title)
+            )),
+            Err(e) => CommandResponse::Text(format!("❌ Database error: {}", e)),
+        }
+    }
+}
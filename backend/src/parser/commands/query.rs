@@ -0,0 +1,88 @@
+// backend/src/parser/commands/query.rs - Pertanyaan bebas di chat pribadi ("tugas apa aja besok?")
+//
+// The LLM only picks the intent; every answer is produced by the same
+// database-backed commands and formatting as #today / #week / #cari.
+
+use super::general::{due_on, due_within, Today, Week};
+use super::history::Overdue;
+use super::personal::Todo;
+use super::search::Cari;
+use super::{format_assignments_list, format_date_id, get_gmt7_now, run, CommandContext, CommandResponse};
+use crate::database::crud::get_active_assignments_for_user;
+use crate::models::{ArgValue, CommandArgs};
+use crate::parser::ai_extractor::{extract_query_intent, QueryIntent};
+use chrono::{Duration, NaiveDate};
+use sqlx::PgPool;
+
+/// Answer a natural-language question sent in a private chat
+pub async fn handle_query(
+    text: &str,
+    user_phone: &str,
+    user_name: &str,
+    chat_id: &str,
+    pool: &PgPool,
+) -> CommandResponse {
+    let ctx = CommandContext { pool, user_phone, user_name, chat_id };
+    let today = get_gmt7_now().date_naive();
+
+    println!("💬 Private question from {}: {}", user_phone, text);
+
+    let intent = extract_query_intent(text, today).await;
+    answer(&ctx, intent, today).await
+}
+
+async fn answer(ctx: &CommandContext<'_>, intent: QueryIntent, today: NaiveDate) -> CommandResponse {
+    let no_args = CommandArgs::default();
+
+    match intent {
+        QueryIntent::DueOn(date) if date == today => run(&Today, ctx, &no_args).await,
+        QueryIntent::DueOn(date) => {
+            let header = if date == today + Duration::days(1) {
+                format!("*[Tugas Besok — {}]*", format_date_id(date))
+            } else {
+                format!("*[Tugas {}]*", format_date_id(date))
+            };
+            with_active_assignments(ctx, |assignments| {
+                format_assignments_list(due_on(assignments, date), &header, false, true)
+            })
+            .await
+        }
+        QueryIntent::DueWithin(7) => run(&Week, ctx, &no_args).await,
+        QueryIntent::DueWithin(days) => {
+            let header = format!("📆 *Tugas {} Hari ke Depan*", days);
+            with_active_assignments(ctx, |assignments| {
+                format_assignments_list(due_within(assignments, get_gmt7_now(), days), &header, false, true)
+            })
+            .await
+        }
+        QueryIntent::Search(keywords) => {
+            run(&Cari, ctx, &CommandArgs(vec![Some(ArgValue::Text(keywords))])).await
+        }
+        QueryIntent::Todo => run(&Todo, ctx, &no_args).await,
+        QueryIntent::Overdue => run(&Overdue, ctx, &CommandArgs(vec![None])).await,
+        QueryIntent::Unknown => CommandResponse::Text(
+            "🤔 Maaf, aku belum paham pertanyaannya.\n\n\
+            Coba tanya seperti:\n\
+            • _tugas apa aja besok?_\n\
+            • _deadline minggu ini apa aja?_\n\
+            • _kapan deadline LKP 5 pemrog?_\n\n\
+            💡 _Atau ketik #help untuk daftar command._"
+                .to_string(),
+        ),
+    }
+}
+
+async fn with_active_assignments(
+    ctx: &CommandContext<'_>,
+    format: impl FnOnce(Vec<crate::models::AssignmentWithCourse>) -> CommandResponse,
+) -> CommandResponse {
+    match get_active_assignments_for_user(ctx.pool, ctx.user_phone).await {
+        Ok(assignments) => format(assignments),
+        Err(e) => {
+            eprintln!("❌ Error fetching assignments: {}", e);
+            CommandResponse::Text(
+                "❌ Maaf, terjadi kesalahan saat mengambil data tugas.\n_Coba lagi sebentar ya._".to_string(),
+            )
+        }
+    }
+}
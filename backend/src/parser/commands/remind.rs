@@ -0,0 +1,233 @@
+// backend/src/parser/commands/remind.rs - Pengaturan pengingat pribadi via DM
+
+use super::{CommandContext, CommandResponse};
+use crate::classifier::{parse_clock_time, parse_duration_minutes};
+use crate::database::crud::{get_reminder_preference, upsert_reminder_preference};
+use crate::models::{CommandArgs, RemindAction, ReminderPreference};
+use crate::parser::registry::{ArgKind, ArgSpec, Command, HelpLine, HelpSection};
+use async_trait::async_trait;
+use chrono::NaiveTime;
+
+pub struct Remind;
+
+#[async_trait]
+impl Command for Remind {
+    fn name(&self) -> &'static str {
+        "remind"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["reminder", "pengingat"]
+    }
+
+    fn args(&self) -> &'static [ArgSpec] {
+        &[
+            ArgSpec { name: "pengaturan", kind: ArgKind::Text, required: false },
+        ]
+    }
+
+    fn help(&self) -> &'static [HelpLine] {
+        &[
+            HelpLine { section: HelpSection::Personal, usage: "#remind", description: "atur pengingat pribadi via DM" },
+        ]
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>, args: &CommandArgs) -> CommandResponse {
+        let words: Vec<&str> = args.text(0).unwrap_or_default().split_whitespace().collect();
+        let action = parse_remind_action(&words);
+
+        println!("🔔 Remind command ({:?}) from {}\n", action, ctx.user_phone);
+
+        let mut pref = match get_reminder_preference(ctx.pool, ctx.user_phone).await {
+            Ok(Some(pref)) => pref,
+            Ok(None) => ReminderPreference::default_for(ctx.user_phone),
+            Err(e) => {
+                eprintln!("❌ Error fetching reminder preference: {}", e);
+                return CommandResponse::Text(
+                    "❌ Gagal mengambil pengaturan pengingat.\n_Coba lagi sebentar ya._".to_string(),
+                );
+            }
+        };
+
+        let notice = match action {
+            RemindAction::Status => None,
+            RemindAction::Usage => return CommandResponse::Text(remind_usage_text()),
+            RemindAction::On => {
+                pref.enabled = true;
+                Some("✅ Pengingat pribadi *diaktifkan*.")
+            }
+            RemindAction::Off => {
+                pref.enabled = false;
+                Some("🔕 Pengingat pribadi *dimatikan*.")
+            }
+            RemindAction::Times(times) => {
+                pref.digest_times = times;
+                Some("✅ Jam ringkasan harian disimpan.")
+            }
+            RemindAction::Lead(leads) => {
+                pref.lead_minutes = leads;
+                Some("✅ Waktu pengingat deadline disimpan.")
+            }
+            RemindAction::Quiet(range) => {
+                pref.quiet_start = range.map(|(start, _)| start);
+                pref.quiet_end = range.map(|(_, end)| end);
+                Some("✅ Jam tenang disimpan.")
+            }
+        };
+
+        if notice.is_some() {
+            match upsert_reminder_preference(ctx.pool, &pref).await {
+                Ok(saved) => pref = saved,
+                Err(e) => {
+                    eprintln!("❌ Error saving reminder preference: {}", e);
+                    return CommandResponse::Text("❌ Gagal menyimpan pengaturan pengingat.".to_string());
+                }
+            }
+        }
+
+        let mut response = String::new();
+        if let Some(notice) = notice {
+            response.push_str(notice);
+            response.push_str("\n\n");
+        }
+        response.push_str(&format_reminder_status(&pref));
+
+        CommandResponse::Text(response)
+    }
+}
+
+/// Parse `#remind` arguments. Bad arguments fall back to `Usage` so the user gets a hint instead of "unknown command"
+fn parse_remind_action(args: &[&str]) -> RemindAction {
+    let Some(sub) = args.first().map(|a| a.to_lowercase()) else {
+        return RemindAction::Status;
+    };
+    let rest = &args[1..];
+
+    match sub.as_str() {
+        "on" | "aktif" | "nyala" => RemindAction::On,
+        "off" | "mati" | "nonaktif" => RemindAction::Off,
+        "status" => RemindAction::Status,
+        "jam" | "time" | "times" => {
+            let times: Option<Vec<NaiveTime>> = rest.iter().map(|t| parse_clock_time(t)).collect();
+            match times {
+                Some(mut times) if !times.is_empty() && times.len() <= 4 => {
+                    times.sort();
+                    times.dedup();
+                    RemindAction::Times(times)
+                }
+                _ => RemindAction::Usage,
+            }
+        }
+        "lead" | "sebelum" => {
+            let leads: Option<Vec<i32>> = rest.iter().map(|d| parse_duration_minutes(d)).collect();
+            match leads {
+                // Antara 10 menit dan 14 hari
+                Some(mut leads) if !leads.is_empty()
+                    && leads.len() <= 4
+                    && leads.iter().all(|m| (10..=14 * 1440).contains(m)) =>
+                {
+                    leads.sort_unstable_by(|a, b| b.cmp(a));
+                    leads.dedup();
+                    RemindAction::Lead(leads)
+                }
+                _ => RemindAction::Usage,
+            }
+        }
+        "quiet" | "tenang" => {
+            let joined = rest.join(" ");
+            if matches!(joined.to_lowercase().as_str(), "off" | "mati" | "hapus") {
+                return RemindAction::Quiet(None);
+            }
+            let range = joined
+                .split_once('-')
+                .and_then(|(start, end)| Some((parse_clock_time(start)?, parse_clock_time(end)?)));
+            match range {
+                Some((start, end)) if start != end => RemindAction::Quiet(Some((start, end))),
+                _ => RemindAction::Usage,
+            }
+        }
+        _ => RemindAction::Usage,
+    }
+}
+
+fn format_reminder_status(pref: &ReminderPreference) -> String {
+    let state = if pref.enabled { "🟢 Aktif" } else { "🔴 Nonaktif" };
+
+    let digest = if pref.digest_times.is_empty() {
+        "—".to_string()
+    } else {
+        pref.digest_times
+            .iter()
+            .map(|t| t.format("%H:%M").to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    let leads = if pref.lead_minutes.is_empty() {
+        "—".to_string()
+    } else {
+        pref.lead_minutes
+            .iter()
+            .map(|m| ReminderPreference::describe_lead(*m))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    let quiet = match (pref.quiet_start, pref.quiet_end) {
+        (Some(start), Some(end)) => format!("{}–{}", start.format("%H:%M"), end.format("%H:%M")),
+        _ => "tidak diatur".to_string(),
+    };
+
+    let hint = if pref.enabled {
+        "_Ketik #remind off untuk mematikan._"
+    } else {
+        "_Ketik #remind on untuk mengaktifkan._"
+    };
+
+    format!(
+        "🔔 *Pengingat Pribadi*: {}\n\n\
+        🕖 Ringkasan harian: {} WIB\n\
+        ⏳ Sebelum deadline: {}\n\
+        🌙 Jam tenang: {}\n\n\
+        {}\n\n\
+        💡 _Pengingat hanya berisi tugas yang belum kamu selesaikan. Ketik #remind help untuk cara mengatur._",
+        state, digest, leads, quiet, hint
+    )
+}
+
+fn remind_usage_text() -> String {
+    "🔔 *Atur Pengingat Pribadi*\n\n\
+    • #remind — lihat pengaturan\n\
+    • #remind on / off — nyalakan / matikan\n\
+    • #remind jam 07:00 19:00 — jam ringkasan harian (maks 4)\n\
+    • #remind lead H-1 3h — pengingat sebelum deadline (10 menit – 14 hari)\n\
+    • #remind quiet 22:00-06:00 — jam tenang (atau `#remind quiet off`)\n\n\
+    _Satuan: H-1 / 1d (hari), 3h / 3j (jam), 30m (menit)_"
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_remind_action() {
+        assert!(matches!(parse_remind_action(&[]), RemindAction::Status));
+        assert!(matches!(parse_remind_action(&["on"]), RemindAction::On));
+
+        match parse_remind_action(&["lead", "3h", "H-1"]) {
+            RemindAction::Lead(leads) => assert_eq!(leads, vec![1440, 180]),
+            other => panic!("unexpected: {:?}", other),
+        }
+
+        match parse_remind_action(&["quiet", "22:00-06:00"]) {
+            RemindAction::Quiet(Some((start, end))) => {
+                assert_eq!(start, NaiveTime::from_hms_opt(22, 0, 0).unwrap());
+                assert_eq!(end, NaiveTime::from_hms_opt(6, 0, 0).unwrap());
+            }
+            other => panic!("unexpected: {:?}", other),
+        }
+
+        assert!(matches!(parse_remind_action(&["jam", "pagi"]), RemindAction::Usage));
+    }
+}
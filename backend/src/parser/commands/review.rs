@@ -0,0 +1,482 @@
+// backend/src/parser/commands/review.rs - Antrian review tugas yang kurang yakin (grup debug)
+
+use super::admin::{code_matches, short_code};
+use super::{sanitize_wa_md, CommandContext, CommandResponse};
+use crate::clarification::{generate_clarification_message, identify_missing_fields, parse_clarification_response};
+use crate::confidence::review_threshold;
+use crate::database::crud::{
+    close_review, create_assignment, get_assignment_by_title_and_course, get_assignment_with_course_by_id,
+    get_course_by_name_or_alias, get_pending_reviews, parse_deadline, reopen_review, set_review_assignment,
+    update_assignment_fields,
+};
+use crate::models::{AssignmentReview, CommandArgs, NewAssignment};
+use crate::parser::registry::{ArgKind, ArgSpec, Command, HelpLine, HelpSection, Scope};
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, FixedOffset, Utc};
+use std::collections::HashMap;
+
+pub struct Approve;
+
+#[async_trait]
+impl Command for Approve {
+    fn name(&self) -> &'static str {
+        "approve"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["setuju"]
+    }
+
+    fn args(&self) -> &'static [ArgSpec] {
+        &[
+            ArgSpec { name: "kode", kind: ArgKind::Text, required: false },
+        ]
+    }
+
+    fn help(&self) -> &'static [HelpLine] {
+        &[
+            HelpLine { section: HelpSection::Admin, usage: "#approve", description: "daftar tugas yang menunggu review (grup debug)" },
+            HelpLine { section: HelpSection::Admin, usage: "#approve <kode> [field: nilai; ...]", description: "terbitkan tugas dari antrian review, boleh sambil dikoreksi" },
+        ]
+    }
+
+    fn scope(&self) -> Scope {
+        Scope::DebugGroup
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>, args: &CommandArgs) -> CommandResponse {
+        let input = args.text(0).unwrap_or_default().trim();
+
+        println!("🔍 Approve command '{}' from {}", input, ctx.user_phone);
+
+        let pending = match get_pending_reviews(ctx.pool).await {
+            Ok(p) => p,
+            Err(e) => {
+                eprintln!("❌ Error fetching reviews: {}", e);
+                return CommandResponse::Text("❌ Gagal mengambil antrian review.".to_string());
+            }
+        };
+
+        let (code, fixes) = input.split_once(' ').unwrap_or((input, ""));
+        if code.is_empty() {
+            return CommandResponse::Text(format_queue(&pending));
+        }
+
+        let review = match find_review(&pending, code) {
+            Ok(review) => review,
+            Err(message) => return CommandResponse::Text(message),
+        };
+
+        let mut assignment = NewAssignment {
+            course_id: review.course_id,
+            title: review.title.clone(),
+            description: review.description.clone(),
+            deadline: review.deadline,
+            parallel_code: review.parallel_code.clone(),
+            sender_id: review.sender_id.clone(),
+            message_id: review.message_id.clone(),
+        };
+
+        let fixes = if fixes.trim().is_empty() {
+            HashMap::new()
+        } else {
+            match parse_fixes(fixes, review.deadline) {
+                Ok(updates) => updates,
+                Err(message) => return CommandResponse::Text(message),
+            }
+        };
+        if let Err(message) = apply_fixes(ctx, &mut assignment, &fixes).await {
+            return CommandResponse::Text(message);
+        }
+
+        let course_id = match review.target_id {
+            Some(target_id) => {
+                if fixes.contains_key("course_name") {
+                    return CommandResponse::Text("⚠️ Mata kuliah tugas lama tidak bisa diganti lewat review update.".to_string());
+                }
+                match get_assignment_with_course_by_id(ctx.pool, target_id).await {
+                    Ok(Some(_)) => None,
+                    Ok(None) => return CommandResponse::Text(target_gone(review)),
+                    Err(e) => {
+                        eprintln!("❌ Failed to fetch review target: {}", e);
+                        return CommandResponse::Text("❌ Terjadi kesalahan sistem.".to_string());
+                    }
+                }
+            }
+            None => match assignment.course_id {
+                Some(course_id) => Some(course_id),
+                None => {
+                    return CommandResponse::Text(format!(
+                        "⚠️ Mata kuliah belum diketahui.\nTambahkan: *#approve {} matkul: <nama matkul>*",
+                        short_code(review.id)
+                    ))
+                }
+            },
+        };
+
+        match close_review(ctx.pool, review.id, "approved", ctx.user_phone, None).await {
+            Ok(true) => {}
+            Ok(false) => return CommandResponse::Text("⚠️ Tugas ini sudah diputuskan orang lain.".to_string()),
+            Err(e) => {
+                eprintln!("❌ DB Error on approve: {}", e);
+                return CommandResponse::Text("❌ Terjadi kesalahan sistem.".to_string());
+            }
+        }
+
+        let title = assignment.title.clone();
+        let saved = match review.target_id {
+            // Only what the update changed (or was fixed now): edits made to the target
+            // while the review waited are kept
+            Some(target_id) => {
+                let changed = |field: &str| review.changed_fields.iter().any(|f| f == field) || fixes.contains_key(field);
+                update_assignment_fields(
+                    ctx.pool,
+                    target_id,
+                    assignment.deadline.filter(|_| changed("deadline")),
+                    Some(assignment.title).filter(|_| changed("title")),
+                    Some(assignment.description).filter(|_| changed("description")),
+                    assignment.parallel_code.filter(|_| changed("parallel_code")),
+                    Some(assignment.message_id),
+                )
+                .await
+                .map(|updated| Some(updated.id))
+            }
+            None => match create_assignment(ctx.pool, assignment).await {
+                Ok(_) => Ok(match course_id {
+                    Some(course_id) => get_assignment_by_title_and_course(ctx.pool, &title, course_id).await.ok().flatten().map(|a| a.id),
+                    None => None,
+                }),
+                Err(e) => Err(e),
+            },
+        };
+
+        let saved_id = match saved {
+            Ok(id) => id,
+            Err(e) => {
+                eprintln!("❌ Failed to save approved assignment: {}", e);
+                let _ = reopen_review(ctx.pool, review.id).await;
+                // Trashed between the check above and the write
+                if matches!(e, sqlx::Error::RowNotFound) && review.target_id.is_some() {
+                    return CommandResponse::Text(target_gone(review));
+                }
+                return CommandResponse::Text("❌ Gagal menyimpan tugas. Review dikembalikan ke antrian.".to_string());
+            }
+        };
+
+        println!("✅ Review {} approved by {}: {}", short_code(review.id), ctx.user_phone, title);
+
+        let Some(saved_id) = saved_id else {
+            return CommandResponse::Text(format!("✅ *DISETUJUI*: {}", sanitize_wa_md(&title)));
+        };
+        if let Err(e) = set_review_assignment(ctx.pool, review.id, saved_id).await {
+            eprintln!("⚠️  Failed to link review to assignment: {}", e);
+        }
+
+        match get_assignment_with_course_by_id(ctx.pool, saved_id).await {
+            Ok(Some(full)) => {
+                let mut response = format!(
+                    "✅ *DISETUJUI*: {}\n📚 {}{}",
+                    sanitize_wa_md(&full.title),
+                    sanitize_wa_md(&full.course_name),
+                    format_details(full.deadline, full.parallel_code.as_deref())
+                );
+
+                // Same follow-up as a directly published task: a reply fills the gaps
+                let missing = identify_missing_fields(&full);
+                if !missing.is_empty() {
+                    response.push_str("\n\n");
+                    response.push_str(&generate_clarification_message(&full, &missing));
+                }
+                CommandResponse::Text(response)
+            }
+            _ => CommandResponse::Text(format!("✅ *DISETUJUI*: {}", sanitize_wa_md(&title))),
+        }
+    }
+}
+
+pub struct Reject;
+
+#[async_trait]
+impl Command for Reject {
+    fn name(&self) -> &'static str {
+        "reject"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["tolak"]
+    }
+
+    fn args(&self) -> &'static [ArgSpec] {
+        &[
+            ArgSpec { name: "kode", kind: ArgKind::Text, required: true },
+        ]
+    }
+
+    fn help(&self) -> &'static [HelpLine] {
+        &[
+            HelpLine { section: HelpSection::Admin, usage: "#reject <kode> [alasan]", description: "buang tugas dari antrian review" },
+        ]
+    }
+
+    fn scope(&self) -> Scope {
+        Scope::DebugGroup
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>, args: &CommandArgs) -> CommandResponse {
+        let input = args.text(0).unwrap_or_default().trim();
+        let (code, reason) = input.split_once(' ').unwrap_or((input, ""));
+        let reason = Some(reason.trim()).filter(|r| !r.is_empty());
+
+        println!("🗑️ Reject command '{}' from {}", code, ctx.user_phone);
+
+        let pending = match get_pending_reviews(ctx.pool).await {
+            Ok(p) => p,
+            Err(e) => {
+                eprintln!("❌ Error fetching reviews: {}", e);
+                return CommandResponse::Text("❌ Gagal mengambil antrian review.".to_string());
+            }
+        };
+
+        let review = match find_review(&pending, code) {
+            Ok(review) => review,
+            Err(message) => return CommandResponse::Text(message),
+        };
+
+        match close_review(ctx.pool, review.id, "rejected", ctx.user_phone, reason).await {
+            Ok(true) => CommandResponse::Text(format!(
+                "🗑️ *DITOLAK*: {}\n_{} tidak akan disimpan._",
+                sanitize_wa_md(&review.title),
+                if review.target_id.is_some() { "Perubahan" } else { "Tugas" }
+            )),
+            Ok(false) => CommandResponse::Text("⚠️ Tugas ini sudah diputuskan orang lain.".to_string()),
+            Err(e) => {
+                eprintln!("❌ DB Error on reject: {}", e);
+                CommandResponse::Text("❌ Terjadi kesalahan sistem.".to_string())
+            }
+        }
+    }
+}
+
+/// Sent to the debug group when an extraction is held for review
+pub fn format_review_card(review: &AssignmentReview) -> String {
+    let code = short_code(review.id);
+    let weak = review.confidence.below(review_threshold());
+    let weak_list = weak.iter().map(|f| field_label(f)).collect::<Vec<_>>().join(", ");
+
+    format!(
+        "🔍 *PERLU REVIEW* (yakin {:.0}%){}\n\
+        \n\
+        📝 *{}*\n\
+        📚 {}{}\n\
+        📄 {}\n\
+        \n\
+        ❓ Kurang yakin: {}\n\
+        \n\
+        ✅ *#approve {}*\n\
+        ✏️ *#approve {} deadline: 25 10 23:59; paralel: K2*\n\
+        🗑️ *#reject {}*",
+        review.score * 100.0,
+        if review.target_id.is_some() {
+            let changed = review.changed_fields.iter().map(|f| field_label(f)).collect::<Vec<_>>().join(", ");
+            format!("\n🔄 _Update untuk tugas yang sudah ada_ (diubah: {})", if changed.is_empty() { "-".to_string() } else { changed })
+        } else {
+            String::new()
+        },
+        sanitize_wa_md(&review.title),
+        review.course_name.as_deref().map(sanitize_wa_md).unwrap_or_else(|| "(mata kuliah tidak dikenal)".to_string()),
+        format_details(review.deadline, review.parallel_code.as_deref()),
+        sanitize_wa_md(&review.description),
+        if weak_list.is_empty() { "-".to_string() } else { weak_list },
+        code,
+        code,
+        code
+    )
+}
+
+fn format_queue(pending: &[AssignmentReview]) -> String {
+    if pending.is_empty() {
+        return "📭 Tidak ada tugas yang menunggu review.".to_string();
+    }
+
+    let mut response = format!("🔍 *Antrian review* ({})\n\n", pending.len());
+    for review in pending {
+        response.push_str(&format!(
+            "`{}` {} — {} ({:.0}%)\n",
+            short_code(review.id),
+            sanitize_wa_md(&review.title),
+            review.course_name.as_deref().map(sanitize_wa_md).unwrap_or_else(|| "?".to_string()),
+            review.score * 100.0
+        ));
+    }
+    response.push_str("\n_Ketik *#approve <kode>* atau *#reject <kode>*_");
+    response
+}
+
+fn find_review<'a>(pending: &'a [AssignmentReview], code: &str) -> Result<&'a AssignmentReview, String> {
+    let code = code.trim().to_lowercase();
+    let matches: Vec<_> = pending.iter().filter(|r| code_matches(r.id, &code)).collect();
+
+    match matches.as_slice() {
+        [review] => Ok(review),
+        [] => Err(format!(
+            "❌ Kode *{}* tidak ada di antrian review.\nCek kode dengan *#approve*",
+            sanitize_wa_md(&code)
+        )),
+        _ => Err(format!("⚠️ Kode *{}* cocok dengan {} tugas. Pakai kode yang lebih panjang.", sanitize_wa_md(&code), matches.len())),
+    }
+}
+
+fn target_gone(review: &AssignmentReview) -> String {
+    format!(
+        "⚠️ Tugas yang mau di-update sudah dihapus.\nBuang review ini dengan *#reject {}*",
+        short_code(review.id)
+    )
+}
+
+/// "deadline: 25 10 23:59; paralel: K2" → same keys as a clarification reply
+fn parse_fixes(fixes: &str, current_deadline: Option<DateTime<Utc>>) -> Result<HashMap<String, String>, String> {
+    let wib = FixedOffset::east_opt(7 * 3600).unwrap();
+    let lines = fixes.split(';').map(str::trim).collect::<Vec<_>>().join("\n");
+    // Deadlines are stored in UTC; the parser works in WIB
+    let current = current_deadline.map(|d| d.with_timezone(&wib).naive_local());
+
+    let invalid = || {
+        "⚠️ Koreksi tidak dikenali. Contoh:\n\
+        *#approve <kode> deadline: 25 10 23:59; paralel: K2; judul: LKP 6; matkul: Pemrograman*"
+            .to_string()
+    };
+
+    if !lines.contains(':') {
+        return Err(invalid());
+    }
+
+    match parse_clarification_response(&lines, Utc::now().with_timezone(&wib).year(), current) {
+        Ok(updates) => Ok(updates),
+        Err(e) if e == "no_date" => Err("⚠️ Tugas ini belum punya tanggal deadline. Kirim lengkap, mis. *deadline: 25 10 23:59*".to_string()),
+        Err(_) => Err(invalid()),
+    }
+}
+
+async fn apply_fixes(ctx: &CommandContext<'_>, assignment: &mut NewAssignment, updates: &HashMap<String, String>) -> Result<(), String> {
+    if let Some(name) = updates.get("course_name") {
+        match get_course_by_name_or_alias(ctx.pool, name).await {
+            Ok(Some(course)) => assignment.course_id = Some(course.id),
+            Ok(None) => return Err(format!("❌ Mata kuliah '{}' tidak ditemukan.", sanitize_wa_md(name))),
+            Err(e) => {
+                eprintln!("❌ Failed to lookup course: {}", e);
+                return Err("❌ Terjadi kesalahan sistem.".to_string());
+            }
+        }
+    }
+    if let Some(deadline) = updates.get("deadline") {
+        assignment.deadline = Some(parse_deadline(deadline).map_err(|_| "❌ Deadline tidak valid.".to_string())?);
+    }
+    if let Some(title) = updates.get("title") {
+        assignment.title = title.clone();
+    }
+    if let Some(description) = updates.get("description") {
+        assignment.description = description.clone();
+    }
+    if let Some(parallel) = updates.get("parallel_code") {
+        assignment.parallel_code = Some(parallel.to_lowercase());
+    }
+    Ok(())
+}
+
+fn field_label(field: &str) -> &'static str {
+    match field {
+        "course_name" => "📚 mata kuliah",
+        "title" => "📝 judul",
+        "deadline" => "⏰ deadline",
+        "parallel_code" => "🧩 paralel",
+        "description" => "📄 deskripsi",
+        _ => "❓ lainnya",
+    }
+}
+
+fn format_details(deadline: Option<DateTime<Utc>>, parallel_code: Option<&str>) -> String {
+    let wib = FixedOffset::east_opt(7 * 3600).unwrap();
+    let deadline = deadline
+        .map(|d| format!("\n⏰ {}", d.with_timezone(&wib).format("%Y-%m-%d %H:%M WIB")))
+        .unwrap_or_default();
+    let parallel = parallel_code
+        .map(|p| format!("\n🧩 Parallel: {}", p.to_uppercase()))
+        .unwrap_or_default();
+
+    format!("{}{}", deadline, parallel)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ConfidenceReport;
+    use chrono::TimeZone;
+    use sqlx::types::Json;
+    use uuid::Uuid;
+
+    fn review(id: u128, title: &str) -> AssignmentReview {
+        let confidence = ConfidenceReport { course_name: 0.5, title: 1.0, deadline: 0.4, parallel_code: 1.0 };
+        AssignmentReview {
+            id: Uuid::from_u128(id),
+            course_id: Some(Uuid::from_u128(1)),
+            course_name: Some("KOM120C - Pemrograman".to_string()),
+            title: title.to_string(),
+            description: "Stack & queue".to_string(),
+            deadline: Some(Utc.with_ymd_and_hms(2026, 10, 21, 16, 59, 0).unwrap()),
+            parallel_code: Some("k1".to_string()),
+            sender_id: None,
+            message_id: "msg".to_string(),
+            confidence: Json(confidence),
+            score: 0.4,
+            status: "pending".to_string(),
+            target_id: None,
+            changed_fields: Vec::new(),
+            created_at: Utc.with_ymd_and_hms(2026, 10, 18, 3, 0, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_find_review_by_code() {
+        let pending = vec![
+            review(0xabcd1234_0000_0000_0000_000000000001, "LKP 6"),
+            review(0xabcd9999_0000_0000_0000_000000000002, "Kuis 2"),
+        ];
+
+        assert_eq!(find_review(&pending, "ABCD1234").unwrap().title, "LKP 6");
+        assert!(find_review(&pending, "abcd").unwrap_err().contains("cocok dengan 2 tugas"));
+        assert!(find_review(&pending, "ffff").unwrap_err().contains("tidak ada di antrian"));
+    }
+
+    #[test]
+    fn test_parse_fixes() {
+        let updates = parse_fixes("deadline: 25 10 23:59; paralel: K2; judul: LKP 7", None).unwrap();
+        assert_eq!(updates.get("parallel_code").map(String::as_str), Some("k2"));
+        assert_eq!(updates.get("title").map(String::as_str), Some("LKP 7"));
+        assert!(updates.get("deadline").unwrap().ends_with("10-25 23:59"));
+
+        // Time only keeps the stored date (16:59 UTC = 23:59 WIB on the 21st)
+        let current = Some(Utc.with_ymd_and_hms(2026, 10, 21, 16, 59, 0).unwrap());
+        assert_eq!(parse_fixes("08:00", current).unwrap().get("deadline").map(String::as_str), Some("2026-10-21 08:00"));
+
+        assert!(parse_fixes("besok aja", None).is_err());
+    }
+
+    #[test]
+    fn test_review_card() {
+        let card = format_review_card(&review(0xabcd1234_0000_0000_0000_000000000001, "LKP 6"));
+
+        assert!(card.contains("🔍 *PERLU REVIEW* (yakin 40%)"));
+        assert!(card.contains("⏰ 2026-10-21 23:59 WIB\n🧩 Parallel: K1"));
+        assert!(card.contains("❓ Kurang yakin: ⏰ deadline, 📚 mata kuliah"));
+        assert!(card.contains("✅ *#approve abcd1234*"));
+        // Must not look like a clarification template to the reply handler
+        assert!(!card.contains("ID:"));
+        assert!(!card.contains("🔄"));
+
+        let mut update = review(0xabcd1234_0000_0000_0000_000000000001, "LKP 6");
+        update.target_id = Some(Uuid::from_u128(0xa1));
+        update.changed_fields = vec!["deadline".to_string(), "description".to_string()];
+        assert!(format_review_card(&update)
+            .starts_with("🔍 *PERLU REVIEW* (yakin 40%)\n🔄 _Update untuk tugas yang sudah ada_ (diubah: ⏰ deadline, 📄 deskripsi)"));
+    }
+}
@@ -0,0 +1,155 @@
+// backend/src/parser/commands/search.rs - Cari tugas berdasarkan kata kunci (#cari)
+
+use super::{format_assignment_entry, sanitize_wa_md, CommandContext, CommandResponse};
+use crate::database::crud::{get_active_assignments_for_user, get_course_by_name_or_alias};
+use crate::models::{AssignmentWithCourse, CommandArgs};
+use crate::parser::registry::{ArgKind, ArgSpec, Command, HelpLine, HelpSection};
+use async_trait::async_trait;
+
+pub struct Cari;
+
+#[async_trait]
+impl Command for Cari {
+    fn name(&self) -> &'static str {
+        "cari"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["search", "find"]
+    }
+
+    fn args(&self) -> &'static [ArgSpec] {
+        &[
+            ArgSpec { name: "kata kunci", kind: ArgKind::Text, required: true },
+        ]
+    }
+
+    fn help(&self) -> &'static [HelpLine] {
+        &[
+            HelpLine { section: HelpSection::Personal, usage: "#cari <kata kunci>", description: "cari tugas dari judul / matkul" },
+        ]
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>, args: &CommandArgs) -> CommandResponse {
+        let query = args.text(0).unwrap_or_default().trim();
+        println!("🔎 Search command '{}' from {}\n", query, ctx.user_phone);
+
+        let assignments = match get_active_assignments_for_user(ctx.pool, ctx.user_phone).await {
+            Ok(a) => a,
+            Err(e) => {
+                eprintln!("❌ Error fetching assignments: {}", e);
+                return CommandResponse::Text(
+                    "❌ Maaf, terjadi kesalahan saat mengambil data tugas.\n_Coba lagi sebentar ya._".to_string(),
+                );
+            }
+        };
+
+        // Kata kunci yang merupakan nama/alias matkul ("pemrog", "sd") dicocokkan ke matkulnya
+        let mut keywords = Vec::new();
+        for word in query.split_whitespace() {
+            let course = get_course_by_name_or_alias(ctx.pool, word).await.ok().flatten();
+            keywords.push(Keyword {
+                text: word.to_lowercase(),
+                course_name: course.map(|c| c.name),
+            });
+        }
+
+        // Numbering follows #todo so #<nomor> / #done <nomor> keep working
+        let matches: Vec<_> = assignments
+            .iter()
+            .filter(|a| !a.is_completed)
+            .enumerate()
+            .filter(|(_, a)| matches_keywords(a, &keywords))
+            .collect();
+
+        if matches.is_empty() {
+            return CommandResponse::Text(format!(
+                "🔎 Tidak ada tugas aktif yang cocok dengan *{}*.\n\n\
+                💡 _Coba kata kunci lain, atau ketik #todo untuk semua tugas._",
+                sanitize_wa_md(query)
+            ));
+        }
+
+        let mut response = format!("🔎 *Hasil Pencarian: {}*\n\n", sanitize_wa_md(query));
+        for (i, a) in matches {
+            response.push_str(&format_assignment_entry(i + 1, a));
+        }
+        response.push_str("\n_🔎 Detail: #<nomor>_\n_✅ Selesai: #done <nomor>_");
+
+        CommandResponse::Text(response)
+    }
+}
+
+struct Keyword {
+    text: String,
+    /// Nama matkul kalau kata ini nama/alias matkul
+    course_name: Option<String>,
+}
+
+/// Every keyword must hit: its course, or the title/description/course/parallel text.
+/// Numbers must match a whole token so "5" doesn't find "LKP 15".
+fn matches_keywords(a: &AssignmentWithCourse, keywords: &[Keyword]) -> bool {
+    let haystack = format!(
+        "{} {} {} {}",
+        a.title,
+        a.course_name,
+        a.description.as_deref().unwrap_or_default(),
+        a.parallel_code.as_deref().unwrap_or_default()
+    )
+    .to_lowercase();
+    let tokens: Vec<&str> = haystack
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .collect();
+
+    keywords.iter().all(|k| {
+        if k.course_name.as_deref() == Some(a.course_name.as_str()) {
+            return true;
+        }
+        if k.text.chars().all(|c| c.is_ascii_digit()) {
+            tokens.contains(&k.text.as_str())
+        } else {
+            haystack.contains(&k.text)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assignment(title: &str, course: &str) -> AssignmentWithCourse {
+        AssignmentWithCourse {
+            id: uuid::Uuid::nil(),
+            course_name: course.to_string(),
+            parallel_code: Some("k1".to_string()),
+            title: title.to_string(),
+            description: None,
+            deadline: None,
+            message_ids: vec![],
+            sender_id: None,
+            is_completed: false,
+        }
+    }
+
+    fn keyword(text: &str, course_name: Option<&str>) -> Keyword {
+        Keyword { text: text.to_string(), course_name: course_name.map(str::to_string) }
+    }
+
+    #[test]
+    fn test_matches_keywords() {
+        let lkp5 = assignment("LKP 5", "Pemrograman");
+        let lkp15 = assignment("LKP 15", "Pemrograman");
+        let sd = assignment("LKP 5", "Struktur Data");
+
+        let query = [keyword("lkp", None), keyword("5", None), keyword("pemrog", Some("Pemrograman"))];
+        assert!(matches_keywords(&lkp5, &query));
+        assert!(!matches_keywords(&lkp15, &query));
+        assert!(!matches_keywords(&sd, &query));
+
+        // Alias yang tidak muncul di teks tetap cocok lewat matkulnya
+        assert!(matches_keywords(&sd, &[keyword("sd", Some("Struktur Data"))]));
+        assert!(matches_keywords(&lkp15, &[keyword("pemrograman", None)]));
+        assert!(!matches_keywords(&lkp5, &[keyword("kuis", None)]));
+    }
+}
@@ -0,0 +1,160 @@
+// backend/src/parser/commands/stats.rs - Statistik penyelesaian tugas
+
+use super::{format_date_id, format_percent, preview_text, sanitize_wa_md, CommandContext, CommandResponse};
+use crate::database::crud::{get_completion_records_for_user, get_course_by_name_or_alias, get_course_completion_counts};
+use crate::models::CommandArgs;
+use crate::parser::registry::{ArgKind, ArgSpec, Command, HelpLine, HelpSection, Role};
+use crate::stats::compute_personal_stats;
+use async_trait::async_trait;
+use chrono::{FixedOffset, Utc};
+
+pub struct Stats;
+
+#[async_trait]
+impl Command for Stats {
+    fn name(&self) -> &'static str {
+        "stats"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["statistik"]
+    }
+
+    fn args(&self) -> &'static [ArgSpec] {
+        &[
+            ArgSpec { name: "matkul", kind: ArgKind::Text, required: false },
+        ]
+    }
+
+    fn help(&self) -> &'static [HelpLine] {
+        &[
+            HelpLine { section: HelpSection::Personal, usage: "#stats", description: "statistik penyelesaian tugas kamu" },
+            HelpLine { section: HelpSection::Admin, usage: "#stats <matkul>", description: "statistik kelas anonim (khusus admin)" },
+        ]
+    }
+
+    /// Statistik per mata kuliah hanya untuk admin / PJ Matkul
+    fn role(&self, args: &CommandArgs) -> Role {
+        if args.text(0).is_some() {
+            Role::Admin
+        } else {
+            Role::Member
+        }
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>, args: &CommandArgs) -> CommandResponse {
+        match args.text(0) {
+            None => personal_stats(ctx).await,
+            Some(course_query) => course_stats(ctx, course_query).await,
+        }
+    }
+}
+
+async fn personal_stats(ctx: &CommandContext<'_>) -> CommandResponse {
+    println!("📊 Stats command from {}\n", ctx.user_phone);
+
+    match get_completion_records_for_user(ctx.pool, ctx.user_phone).await {
+        Ok(records) => {
+            let stats = compute_personal_stats(&records, Utc::now());
+
+            if stats.total == 0 {
+                return CommandResponse::Text(
+                    "📊 *Statistik Kamu*\n\n📭 Belum ada data. Tandai tugas dengan *#done* dulu ya!".to_string(),
+                );
+            }
+
+            let mut response = format!(
+                "📊 *Statistik Kamu*\n\n\
+                ✅ Selesai: {}/{} ({})\n\
+                ⏱️ Tepat waktu: {}/{} ({})\n\
+                🔥 Streak tepat waktu: {} (terbaik: {})\n\n\
+                *Per Mata Kuliah:*\n",
+                stats.completed,
+                stats.total,
+                format_percent(stats.completion_rate()),
+                stats.on_time,
+                stats.completed_with_deadline,
+                format_percent(stats.on_time_rate()),
+                stats.current_streak,
+                stats.best_streak
+            );
+
+            for course in &stats.per_course {
+                let rate = (course.total > 0).then(|| course.completed as f32 / course.total as f32);
+                response.push_str(&format!(
+                    "• {} — {}/{} ({})\n",
+                    sanitize_wa_md(&course.course_name),
+                    course.completed,
+                    course.total,
+                    format_percent(rate)
+                ));
+            }
+
+            response.push_str("\n_Dihitung dari tugas yang sudah lewat deadline atau sudah kamu selesaikan (tugas #hide tidak dihitung)._");
+            CommandResponse::Text(response)
+        }
+        Err(e) => {
+            eprintln!("❌ Error fetching stats: {}", e);
+            CommandResponse::Text("❌ Gagal menghitung statistik.".to_string())
+        }
+    }
+}
+
+async fn course_stats(ctx: &CommandContext<'_>, course_query: &str) -> CommandResponse {
+    println!("📊 Course stats command '{}' from {}\n", course_query, ctx.user_phone);
+
+    let course = match get_course_by_name_or_alias(ctx.pool, course_query).await {
+        Ok(Some(course)) => course,
+        Ok(None) => {
+            return CommandResponse::Text(format!(
+                "❌ Mata kuliah *{}* tidak ditemukan.",
+                sanitize_wa_md(course_query)
+            ))
+        }
+        Err(e) => {
+            eprintln!("❌ Error looking up course: {}", e);
+            return CommandResponse::Text("❌ Terjadi kesalahan sistem.".to_string());
+        }
+    };
+
+    match get_course_completion_counts(ctx.pool, course.id).await {
+        Ok(counts) if counts.is_empty() => CommandResponse::Text(format!(
+            "📊 *Statistik Kelas — {}*\n\n📭 Belum ada tugas.",
+            sanitize_wa_md(&course.name)
+        )),
+        Ok(counts) => {
+            let mut response = format!(
+                "📊 *Statistik Kelas — {}*\n_(anonim: jumlah mahasiswa yang menandai selesai)_\n\n",
+                sanitize_wa_md(&course.name)
+            );
+
+            for c in &counts {
+                let parallel = c.parallel_code
+                    .as_ref()
+                    .map(|p| format!(" [{}]", p.to_uppercase()))
+                    .unwrap_or_default();
+                let deadline = c.deadline
+                    .map(|d| {
+                        let gmt7 = FixedOffset::east_opt(7 * 3600).unwrap();
+                        format_date_id(d.with_timezone(&gmt7).date_naive())
+                    })
+                    .unwrap_or_else(|| "tanpa deadline".to_string());
+
+                response.push_str(&format!(
+                    "• *{}*{} — ⏰ {}\n   ✅ {} selesai • ⏱️ {} tepat waktu\n",
+                    preview_text(&sanitize_wa_md(&c.title), 25),
+                    parallel,
+                    deadline,
+                    c.completed,
+                    c.on_time
+                ));
+            }
+
+            CommandResponse::Text(response)
+        }
+        Err(e) => {
+            eprintln!("❌ Error fetching course stats: {}", e);
+            CommandResponse::Text("❌ Gagal menghitung statistik kelas.".to_string())
+        }
+    }
+}
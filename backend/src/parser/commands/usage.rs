@@ -0,0 +1,186 @@
+// backend/src/parser/commands/usage.rs - Pemakaian & biaya LLM (khusus admin)
+
+use super::{CommandContext, CommandResponse};
+use crate::database::crud::get_llm_usage_summary;
+use crate::models::{CommandArgs, LlmUsageSummary, LlmUsageTotals};
+use crate::parser::ai_extractor::{configured_budgets, Budget, BudgetPeriod};
+use crate::parser::registry::{ArgKind, ArgSpec, Command, HelpLine, HelpSection, Role};
+use async_trait::async_trait;
+use chrono::Utc;
+
+pub struct Usage;
+
+#[async_trait]
+impl Command for Usage {
+    fn name(&self) -> &'static str {
+        "usage"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["pemakaian"]
+    }
+
+    fn args(&self) -> &'static [ArgSpec] {
+        &[
+            ArgSpec { name: "periode", kind: ArgKind::Text, required: false },
+        ]
+    }
+
+    fn help(&self) -> &'static [HelpLine] {
+        &[
+            HelpLine { section: HelpSection::Admin, usage: "#usage [bulan]", description: "pemakaian token & biaya LLM hari ini / bulan ini" },
+        ]
+    }
+
+    fn role(&self, _args: &CommandArgs) -> Role {
+        Role::Admin
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>, args: &CommandArgs) -> CommandResponse {
+        let Some(period) = parse_period(args.text(0)) else {
+            return CommandResponse::Text("❌ Periode tidak dikenal. Pakai *#usage* atau *#usage bulan*".to_string());
+        };
+
+        println!("💸 Usage command ({:?}) from {}\n", period, ctx.user_phone);
+
+        match get_llm_usage_summary(ctx.pool, period.start(Utc::now())).await {
+            Ok(rows) => CommandResponse::Text(format_usage(&rows, configured_budgets(), period)),
+            Err(e) => {
+                eprintln!("❌ Error fetching LLM usage: {}", e);
+                CommandResponse::Text("❌ Gagal mengambil data pemakaian LLM.".to_string())
+            }
+        }
+    }
+}
+
+fn parse_period(arg: Option<&str>) -> Option<BudgetPeriod> {
+    match arg.map(|a| a.trim().to_lowercase()).as_deref() {
+        None | Some("hari") | Some("harian") | Some("today") => Some(BudgetPeriod::Daily),
+        Some("bulan") | Some("bulanan") | Some("month") => Some(BudgetPeriod::Monthly),
+        Some(_) => None,
+    }
+}
+
+/// 950 → "950", 12_345 → "12.3K", 2_500_000 → "2.5M"
+fn format_tokens(tokens: i64) -> String {
+    match tokens {
+        t if t >= 1_000_000 => format!("{:.1}M", t as f64 / 1_000_000.0),
+        t if t >= 1_000 => format!("{:.1}K", t as f64 / 1_000.0),
+        t => t.to_string(),
+    }
+}
+
+fn format_usage(rows: &[LlmUsageSummary], budgets: &[Budget], period: BudgetPeriod) -> String {
+    let mut response = format!("💸 *Pemakaian LLM — {}*\n", period.label());
+
+    if rows.is_empty() {
+        response.push_str("\n📭 Belum ada panggilan ke LLM.");
+    }
+
+    let mut providers: Vec<&str> = rows.iter().map(|r| r.provider.as_str()).collect();
+    providers.dedup();
+
+    for provider in providers {
+        let models: Vec<&LlmUsageSummary> = rows.iter().filter(|r| r.provider == provider).collect();
+        let totals = LlmUsageTotals {
+            requests: models.iter().map(|r| r.requests).sum(),
+            tokens: models.iter().map(|r| r.prompt_tokens + r.completion_tokens).sum(),
+            cost_usd: models.iter().map(|r| r.cost_usd).sum(),
+        };
+        let failures: i64 = models.iter().map(|r| r.failures).sum();
+
+        response.push_str(&format!(
+            "\n*{}* — {} request ({} gagal), {} token, ${:.4}\n",
+            provider,
+            totals.requests,
+            failures,
+            format_tokens(totals.tokens),
+            totals.cost_usd
+        ));
+
+        for row in models {
+            response.push_str(&format!(
+                "• {}: {} req, {} in / {} out, {:.1}s\n",
+                row.model,
+                row.requests,
+                format_tokens(row.prompt_tokens),
+                format_tokens(row.completion_tokens),
+                row.avg_latency_ms / 1000.0
+            ));
+        }
+
+        for budget in budgets.iter().filter(|b| b.period == period && b.provider.name() == provider) {
+            let status = match budget.exceeded(&totals) {
+                Some(reason) => format!("⛔ habis ({})", reason),
+                None => "✅ aman".to_string(),
+            };
+            response.push_str(&format!("💰 Budget: {} — {}\n", describe_budget(budget), status));
+        }
+    }
+
+    response
+}
+
+fn describe_budget(budget: &Budget) -> String {
+    let mut limits = Vec::new();
+    if let Some(max) = budget.max_requests {
+        limits.push(format!("{} request", max));
+    }
+    if let Some(max) = budget.max_tokens {
+        limits.push(format!("{} token", format_tokens(max)));
+    }
+    if let Some(max) = budget.max_cost_usd {
+        limits.push(format!("${:.2}", max));
+    }
+    limits.join(" / ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::ai_extractor::ProviderKind;
+
+    fn row(provider: &str, model: &str, requests: i64, failures: i64, tokens: i64, cost_usd: f64) -> LlmUsageSummary {
+        LlmUsageSummary {
+            provider: provider.to_string(),
+            model: model.to_string(),
+            requests,
+            failures,
+            prompt_tokens: tokens,
+            completion_tokens: 0,
+            cost_usd,
+            avg_latency_ms: 1500.0,
+        }
+    }
+
+    #[test]
+    fn test_parse_period() {
+        assert_eq!(parse_period(None), Some(BudgetPeriod::Daily));
+        assert_eq!(parse_period(Some("Bulan")), Some(BudgetPeriod::Monthly));
+        assert_eq!(parse_period(Some("tahun")), None);
+    }
+
+    #[test]
+    fn test_format_usage() {
+        let rows = vec![
+            row("gemini", "gemini-2.5-flash", 3, 0, 4_200, 0.0021),
+            row("groq", "openai/gpt-oss-120b", 40, 5, 120_000, 0.0),
+            row("groq", "llama-3.1-8b-instant", 10, 0, 9_000, 0.0),
+        ];
+        let budgets = vec![
+            Budget { provider: ProviderKind::Groq, period: BudgetPeriod::Daily, max_requests: Some(50), max_tokens: None, max_cost_usd: None },
+            Budget { provider: ProviderKind::Gemini, period: BudgetPeriod::Monthly, max_requests: None, max_tokens: None, max_cost_usd: Some(5.0) },
+        ];
+
+        let text = format_usage(&rows, &budgets, BudgetPeriod::Daily);
+
+        assert!(text.contains("*gemini* — 3 request (0 gagal), 4.2K token, $0.0021"));
+        assert!(text.contains("*groq* — 50 request (5 gagal), 129.0K token"));
+        assert!(text.contains("• openai/gpt-oss-120b: 40 req, 120.0K in / 0 out, 1.5s"));
+        assert!(text.contains("💰 Budget: 50 request — ⛔ habis (50/50 requests)"));
+        // Monthly gemini budget isn't shown for the daily view
+        assert!(!text.contains("$5.00"));
+
+        assert!(format_usage(&[], &budgets, BudgetPeriod::Monthly).contains("Belum ada panggilan"));
+    }
+}
@@ -1,3 +1,4 @@
 pub mod commands;
 pub mod ai_extractor;
+pub mod registry;
 
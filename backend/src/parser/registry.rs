@@ -0,0 +1,316 @@
+// backend/src/parser/registry.rs
+//
+// Single source of truth for bot commands. Every command implements `Command`
+// and is listed in `COMMANDS`; the classifier, `#help` and permission checks all
+// read from this table, so adding a command only means adding one impl here.
+
+use crate::classifier::parse_duration_minutes;
+use crate::models::{ArgValue, CommandArgs};
+use crate::parser::commands::{admin, export, general, history, jadwal, notes, personal, remind, review, search, stats, usage, CommandContext, CommandResponse};
+use async_trait::async_trait;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HelpSection {
+    General,
+    Personal,
+    Admin,
+}
+
+/// Where a command may be used
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    Anywhere,
+    DmOnly,
+    /// Only in chats listed in ACADEMIC_CHANNELS
+    AcademicChannel,
+    /// Anywhere except the academic channels (personal data)
+    NotAcademicChannel,
+    /// Only in the DEBUG_GROUP_ID chat (review queue)
+    DebugGroup,
+}
+
+/// Who may run a command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Member,
+    /// Numbers listed in ADMIN_NUMBERS
+    Admin,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum ArgKind {
+    /// Positive number, e.g. nomor dari #todo
+    Index,
+    /// Duration like 3h / 2d / H-1, stored as minutes
+    Duration { max_minutes: i32 },
+    /// Everything that's left, joined with spaces
+    Text,
+}
+
+#[derive(Debug)]
+pub struct ArgSpec {
+    pub name: &'static str,
+    pub kind: ArgKind,
+    pub required: bool,
+}
+
+#[derive(Debug)]
+pub struct HelpLine {
+    pub section: HelpSection,
+    pub usage: &'static str,
+    pub description: &'static str,
+}
+
+#[async_trait]
+pub trait Command: Send + Sync {
+    /// Canonical name (without #)
+    fn name(&self) -> &'static str;
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn args(&self) -> &'static [ArgSpec] {
+        &[]
+    }
+
+    /// Lines shown in `#help`
+    fn help(&self) -> &'static [HelpLine];
+
+    fn scope(&self) -> Scope {
+        Scope::Anywhere
+    }
+
+    /// Role needed to run the command with these arguments
+    fn role(&self, _args: &CommandArgs) -> Role {
+        Role::Member
+    }
+
+    async fn execute(&self, ctx: &CommandContext<'_>, args: &CommandArgs) -> CommandResponse;
+}
+
+pub static COMMANDS: &[&dyn Command] = &[
+    &general::Ping,
+    &general::Tugas,
+    &general::Today,
+    &general::Week,
+    &jadwal::Jadwal,
+    &general::Help,
+    &personal::Todo,
+    &search::Cari,
+    &personal::Expand,
+    &personal::Done,
+    &personal::Undo,
+    &personal::Snooze,
+    &personal::Hide,
+    &personal::Hidden,
+    &personal::Unhide,
+    &notes::Note,
+    &notes::Notes,
+    &history::Overdue,
+    &history::History,
+    &remind::Remind,
+    &stats::Stats,
+    &export::Export,
+    &admin::Delete,
+    &admin::Trash,
+    &admin::Restore,
+    &review::Approve,
+    &review::Reject,
+    &usage::Usage,
+];
+
+/// Resolve a command word (lowercase, without #) to its command
+pub fn lookup(word: &str) -> Option<&'static dyn Command> {
+    COMMANDS
+        .iter()
+        .copied()
+        .find(|cmd| cmd.name() == word || cmd.aliases().contains(&word))
+}
+
+/// Parse raw words against a command's argument schema. Extra words are ignored
+pub fn parse_args(schema: &[ArgSpec], words: &[&str]) -> Option<CommandArgs> {
+    let mut values = Vec::with_capacity(schema.len());
+    let mut rest = words;
+
+    for spec in schema {
+        let value = match spec.kind {
+            ArgKind::Index => rest
+                .first()
+                .and_then(|w| w.parse::<u32>().ok())
+                .map(ArgValue::Index),
+            ArgKind::Duration { max_minutes } => rest
+                .first()
+                .and_then(|w| parse_duration_minutes(w))
+                .filter(|m| *m <= max_minutes)
+                .map(ArgValue::Minutes),
+            ArgKind::Text => Some(rest.join(" "))
+                .filter(|t| !t.is_empty())
+                .map(ArgValue::Text),
+        };
+
+        match value {
+            Some(ArgValue::Text(_)) => rest = &[],
+            Some(_) => rest = &rest[1..],
+            None if spec.required => return None,
+            None => {}
+        }
+        values.push(value);
+    }
+
+    Some(CommandArgs(values))
+}
+
+/// Usage line built from the argument schema, e.g. "#snooze <id> <durasi>"
+pub fn usage(cmd: &dyn Command) -> String {
+    let mut out = format!("#{}", cmd.name());
+    for arg in cmd.args() {
+        if arg.required {
+            out.push_str(&format!(" <{}>", arg.name));
+        } else {
+            out.push_str(&format!(" [{}]", arg.name));
+        }
+    }
+    out
+}
+
+/// Suggest the closest known command word for a typo, e.g. "tood" -> "todo"
+pub fn suggest(word: &str) -> Option<&'static str> {
+    let word = word.trim_start_matches('#').to_lowercase();
+    if word.is_empty() {
+        return None;
+    }
+
+    // Short words tolerate 1 edit, longer ones 2
+    let max_distance = if word.chars().count() <= 4 { 1 } else { 2 };
+
+    COMMANDS
+        .iter()
+        .flat_map(|cmd| std::iter::once(cmd.name()).chain(cmd.aliases().iter().copied()))
+        .map(|candidate| (candidate, edit_distance(&word, candidate)))
+        .filter(|(_, distance)| *distance <= max_distance)
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(candidate, _)| candidate)
+}
+
+/// Render the `#help` text from the registry
+pub fn help_text() -> String {
+    let section_lines = |section: HelpSection| {
+        COMMANDS
+            .iter()
+            .flat_map(|cmd| cmd.help().iter())
+            .filter(|line| line.section == section)
+            .map(|line| format!("• {} — {}", line.usage, line.description))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    format!(
+        "*[MABOT — Academic Bot]*\n\n\
+        *Perintah Umum:*\n{}\n\n\
+        *Perintah Personal:*\n{}\n\n\
+        *Perintah Admin (Grup Akademik):*\n{}\n\n\
+        *Penting:* #<id>, #done, #snooze dan #hide selalu pakai nomor dari *#todo*. \
+        _Info tugas akan otomatis tersimpan via grup info akademik, tidak dari chat lain._\n\n\
+        *Want to Contribute?*\n\
+        github.com/gimigkk/marbot-academic-bot",
+        section_lines(HelpSection::General),
+        section_lines(HelpSection::Personal),
+        section_lines(HelpSection::Admin)
+    )
+}
+
+/// Damerau-Levenshtein (optimal string alignment) distance, so swapped letters count as one typo
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut d = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+
+    d[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("todo", "todo"), 0);
+        assert_eq!(edit_distance("tood", "todo"), 1);
+        assert_eq!(edit_distance("tugs", "tugas"), 1);
+        assert_eq!(edit_distance("ping", "week"), 4);
+    }
+
+    #[test]
+    fn test_suggest() {
+        assert_eq!(suggest("#tood"), Some("todo"));
+        assert_eq!(suggest("tugass"), Some("tugas"));
+        assert_eq!(suggest("hapsu"), Some("hapus"));
+        assert_eq!(suggest("remnid"), Some("remind"));
+        assert_eq!(suggest("xyzzy"), None);
+    }
+
+    #[test]
+    fn test_help_lists_every_command() {
+        let help = help_text();
+        for cmd in COMMANDS {
+            assert!(!cmd.help().is_empty(), "#{} has no help line", cmd.name());
+            for line in cmd.help() {
+                assert!(help.contains(line.usage), "{} missing from help", line.usage);
+            }
+        }
+    }
+
+    #[test]
+    fn test_names_and_aliases_are_unique() {
+        let mut words: Vec<&str> = COMMANDS
+            .iter()
+            .flat_map(|cmd| std::iter::once(cmd.name()).chain(cmd.aliases().iter().copied()))
+            .collect();
+        let total = words.len();
+        words.sort();
+        words.dedup();
+        assert_eq!(words.len(), total);
+    }
+
+    #[test]
+    fn test_parse_args() {
+        const SCHEMA: &[ArgSpec] = &[
+            ArgSpec { name: "id", kind: ArgKind::Index, required: true },
+            ArgSpec { name: "durasi", kind: ArgKind::Duration { max_minutes: 1440 }, required: false },
+            ArgSpec { name: "catatan", kind: ArgKind::Text, required: false },
+        ];
+
+        let args = parse_args(SCHEMA, &["3", "2h", "lab", "lantai", "2"]).unwrap();
+        assert_eq!(args.index(0), Some(3));
+        assert_eq!(args.minutes(1), Some(120));
+        assert_eq!(args.text(2), Some("lab lantai 2"));
+
+        let args = parse_args(SCHEMA, &["3", "catatan"]).unwrap();
+        assert_eq!(args.minutes(1), None);
+        assert_eq!(args.text(2), Some("catatan"));
+
+        assert!(parse_args(SCHEMA, &["3", "2d"]).unwrap().minutes(1).is_none());
+        assert!(parse_args(SCHEMA, &[]).is_none());
+    }
+}
@@ -2,9 +2,9 @@
 use tokio_cron_scheduler::{Job, JobScheduler, JobSchedulerError};
 use sqlx::PgPool;
 use crate::database::crud;
-use crate::models::SendTextRequest;
+use crate::models::{AssignmentWithCourse, ReminderPreference, SendTextRequest};
 
-use chrono::{DateTime, Datelike, Local, NaiveDate, Utc};
+use chrono::{DateTime, Datelike, Duration, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc};
 
 pub async fn start_scheduler(pool: PgPool) -> Result<(), JobSchedulerError> {
     let sched = JobScheduler::new().await?;
@@ -33,6 +33,31 @@ pub async fn start_scheduler(pool: PgPool) -> Result<(), JobSchedulerError> {
         })
     })?).await?;
 
+    // Pengingat pribadi (DM) - cek tiap menit
+    let pool_personal = pool.clone();
+    sched.add(Job::new_async("0 * * * * *", move |_uuid, _l| {
+        let pool = pool_personal.clone();
+        Box::pin(async move {
+            if let Err(e) = run_personal_reminders(pool).await {
+                eprintln!("❌ Error reminder pribadi: {}", e);
+            }
+        })
+    })?).await?;
+
+    // 03:00 WIB (20:00 UTC) - hapus permanen isi trash yang sudah kedaluwarsa
+    let pool_trash = pool.clone();
+    sched.add(Job::new_async("0 0 20 * * *", move |_uuid, _l| {
+        let pool = pool_trash.clone();
+        Box::pin(async move {
+            let days = crate::parser::commands::admin::trash_retention_days();
+            match crud::purge_deleted_assignments(&pool, days).await {
+                Ok(0) => {}
+                Ok(n) => println!("🗑️ Purged {} assignments from trash (> {} hari)", n, days),
+                Err(e) => eprintln!("❌ Error purge trash: {}", e),
+            }
+        })
+    })?).await?;
+
     sched.start().await?;
     Ok(())
 }
@@ -90,24 +115,189 @@ async fn run_reminder_task(pool: PgPool, greeting: &str) -> Result<(), Box<dyn s
     }
 
     let client = reqwest::Client::new();
-    let waha_url = std::env::var("WAHA_URL").unwrap_or_else(|_| "http://localhost:3001".to_string());
-    let api_key = std::env::var("WAHA_API_KEY").unwrap_or_else(|_| "devkey123".to_string());
 
     for chat_id in target_channels {
-        let payload = SendTextRequest {
-            chat_id: chat_id.to_string(),
-            text: message.clone(),
-            session: "default".to_string(),
+        println!("📤 Mengirim reminder ke {}", chat_id);
+        let _ = send_text(&client, chat_id, &message).await;
+    }
+
+    Ok(())
+}
+
+/// Kirim pengingat pribadi via DM sesuai preferensi tiap user
+async fn run_personal_reminders(pool: PgPool) -> Result<(), Box<dyn std::error::Error>> {
+    let wib = FixedOffset::east_opt(7 * 3600).unwrap();
+    let now = Utc::now();
+    let now_wib = now.with_timezone(&wib);
+    let time_now = NaiveTime::from_hms_opt(now_wib.hour(), now_wib.minute(), 0).unwrap();
+
+    // Bersihkan log lama sekali sehari
+    if time_now == NaiveTime::from_hms_opt(3, 0, 0).unwrap() {
+        let _ = crud::purge_reminder_log(&pool, 30).await;
+    }
+
+    let prefs = crud::get_enabled_reminder_preferences(&pool).await?;
+    if prefs.is_empty() {
+        return Ok(());
+    }
+
+    let client = reqwest::Client::new();
+
+    for pref in prefs {
+        // Jam tenang: tunda, pengingat yang tertunda akan terkirim setelah jam tenang selesai
+        if pref.is_quiet_at(time_now) {
+            continue;
+        }
+
+        let assignments = match crud::get_pending_assignments_for_reminder(&pool, &pref.user_id).await {
+            Ok(a) => a,
+            Err(e) => {
+                eprintln!("❌ Gagal ambil tugas untuk {}: {}", pref.user_id, e);
+                continue;
+            }
         };
 
-        println!("📤 Mengirim reminder ke {}", chat_id);
-        let _ = client
-            .post(format!("{}/api/sendText", waha_url))
-            .header("X-Api-Key", &api_key)
-            .json(&payload)
-            .send()
-            .await;
+        // 1. Ringkasan harian
+        if let Some(digest_at) = due_digest(&pref, now_wib.naive_local()) {
+            let key = format!("digest:{}", digest_at.format("%Y-%m-%d %H:%M"));
+
+            match crud::mark_reminder_sent(&pool, &pref.user_id, &key).await {
+                Ok(true) => {
+                    println!("📤 Ringkasan pribadi {} ke {}", digest_at.format("%H:%M"), pref.user_id);
+                    let message = build_personal_digest(&assignments, digest_at.time());
+                    send_or_unmark(&pool, &client, &pref.user_id, &message, &[key]).await;
+                }
+                Ok(false) => {}
+                Err(e) => {
+                    eprintln!("❌ Gagal catat ringkasan untuk {}: {}", pref.user_id, e);
+                    continue;
+                }
+            }
+        }
+
+        // 2. Pengingat sebelum deadline
+        let mut due_soon: Vec<(&AssignmentWithCourse, i32)> = Vec::new();
+        let mut keys = Vec::new();
+
+        for a in &assignments {
+            let Some(deadline) = a.deadline else { continue };
+
+            // Ambil lead terkecil yang sudah lewat -> satu pengingat per ambang
+            let passed_lead = pref.lead_minutes
+                .iter()
+                .copied()
+                .filter(|lead| now >= deadline - Duration::minutes(*lead as i64))
+                .min();
+
+            if let Some(lead) = passed_lead {
+                let key = format!("lead:{}:{}", a.id, lead);
+                match crud::mark_reminder_sent(&pool, &pref.user_id, &key).await {
+                    Ok(true) => {
+                        due_soon.push((a, lead));
+                        keys.push(key);
+                    }
+                    Ok(false) => {}
+                    Err(e) => eprintln!("❌ Gagal catat pengingat {} untuk {}: {}", key, pref.user_id, e),
+                }
+            }
+        }
+
+        if !due_soon.is_empty() {
+            println!("📤 {} pengingat deadline ke {}", due_soon.len(), pref.user_id);
+            let message = build_deadline_alert(&due_soon);
+            send_or_unmark(&pool, &client, &pref.user_id, &message, &keys).await;
+        }
+    }
+
+    Ok(())
+}
+
+/// Kirim DM yang sudah ditandai di reminder_log; kalau gagal tandanya dihapus supaya dicoba lagi
+async fn send_or_unmark(pool: &PgPool, client: &reqwest::Client, user_id: &str, message: &str, keys: &[String]) {
+    let Err(e) = send_text(client, user_id, message).await else { return };
+
+    eprintln!("❌ Gagal kirim pengingat ke {}: {} (dicoba lagi nanti)", user_id, e);
+    for key in keys {
+        if let Err(e) = crud::unmark_reminder_sent(pool, user_id, key).await {
+            eprintln!("❌ Gagal hapus log {} untuk {}: {}", key, user_id, e);
+        }
     }
+}
+
+/// Latest digest (today's, or yesterday's for overnight quiet hours) that should go out now.
+/// Up to 60 minutes late after a restart; a digest that fell in quiet hours goes out once they end.
+fn due_digest(pref: &ReminderPreference, now: NaiveDateTime) -> Option<NaiveDateTime> {
+    let today = now.date();
+    let latest = pref.digest_times
+        .iter()
+        .flat_map(|t| [today.and_time(*t), (today - Duration::days(1)).and_time(*t)])
+        .filter(|at| *at <= now)
+        .max()?;
+
+    let postponed = pref.is_quiet_at(latest.time());
+    (now - latest < Duration::minutes(60) || postponed).then_some(latest)
+}
+
+fn build_personal_digest(assignments: &[AssignmentWithCourse], digest_time: NaiveTime) -> String {
+    let greeting = match digest_time.hour() {
+        4..=10 => "☀️ Selamat pagi!",
+        11..=14 => "🌤️ Selamat siang!",
+        15..=17 => "🌇 Selamat sore!",
+        _ => "🌙 Selamat malam!",
+    };
+
+    let mut message = String::new();
+    message.push_str(greeting);
+    message.push_str("\n*Tugas Kamu yang Belum Selesai*\n\n");
+
+    if assignments.is_empty() {
+        message.push_str("🎉 Semua tugas sudah selesai. Mantap!\n\n");
+    }
+
+    for (i, a) in assignments.iter().enumerate() {
+        message.push_str(&format!("{} *[{}] [{}]*\n", status_dot(&a.deadline), i + 1, sanitize_wa_md(&a.title)));
+        message.push_str(&format!("📌 {}\n", sanitize_wa_md(&a.course_name)));
+        message.push_str(&format!("⏰ {}\n\n", humanize_deadline(&a.deadline)));
+    }
+
+    message.push_str("_✅ Selesai: #done <nomor> • ⚙️ Atur: #remind_");
+    message
+}
+
+fn build_deadline_alert(due_soon: &[(&AssignmentWithCourse, i32)]) -> String {
+    let mut message = String::from("⏳ *Deadline Sebentar Lagi!*\n\n");
+
+    for (a, lead) in due_soon {
+        message.push_str(&format!("{} *{}*\n", status_dot(&a.deadline), sanitize_wa_md(&a.title)));
+        message.push_str(&format!("📌 {}\n", sanitize_wa_md(&a.course_name)));
+        message.push_str(&format!(
+            "⏰ {} _(pengingat {})_\n\n",
+            humanize_deadline(&a.deadline),
+            ReminderPreference::describe_lead(*lead)
+        ));
+    }
+
+    message.push_str("_Ketik #todo untuk lihat daftar lengkap._");
+    message
+}
+
+async fn send_text(client: &reqwest::Client, chat_id: &str, text: &str) -> Result<(), reqwest::Error> {
+    let waha_url = std::env::var("WAHA_URL").unwrap_or_else(|_| "http://localhost:3001".to_string());
+    let api_key = std::env::var("WAHA_API_KEY").unwrap_or_else(|_| "devkey123".to_string());
+
+    let payload = SendTextRequest {
+        chat_id: chat_id.to_string(),
+        text: text.to_string(),
+        session: "default".to_string(),
+    };
+
+    client
+        .post(format!("{}/api/sendText", waha_url))
+        .header("X-Api-Key", &api_key)
+        .json(&payload)
+        .send()
+        .await?
+        .error_for_status()?;
 
     Ok(())
 }
@@ -192,4 +382,50 @@ fn sanitize_wa_md(s: &str) -> String {
         .replace('_', " ")
         .replace('~', "-")
         .replace('`', "'")
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    fn on(day: u32, h: u32, m: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap().and_time(at(h, m))
+    }
+
+    fn pref(digest_times: Vec<NaiveTime>, quiet: Option<(NaiveTime, NaiveTime)>) -> ReminderPreference {
+        ReminderPreference {
+            digest_times,
+            quiet_start: quiet.map(|q| q.0),
+            quiet_end: quiet.map(|q| q.1),
+            ..ReminderPreference::default_for("628111@c.us")
+        }
+    }
+
+    #[test]
+    fn test_due_digest_on_time_and_after_restart() {
+        let p = pref(vec![at(7, 0), at(19, 0)], None);
+
+        assert_eq!(due_digest(&p, on(20, 7, 0)), Some(on(20, 7, 0)));
+        assert_eq!(due_digest(&p, on(20, 7, 59)), Some(on(20, 7, 0)));
+        assert_eq!(due_digest(&p, on(20, 8, 0)), None);
+        assert_eq!(due_digest(&p, on(20, 6, 59)), None);
+    }
+
+    #[test]
+    fn test_due_digest_postponed_by_long_quiet_hours() {
+        // Digest 21:00 inside quiet 21:00–06:00 → goes out when quiet hours end the next morning
+        let p = pref(vec![at(21, 0)], Some((at(21, 0), at(6, 0))));
+
+        assert_eq!(due_digest(&p, on(21, 6, 0)), Some(on(20, 21, 0)));
+        assert_eq!(due_digest(&p, on(21, 12, 0)), Some(on(20, 21, 0)));
+
+        // A later digest supersedes the postponed one
+        let p = pref(vec![at(7, 0), at(23, 0)], Some((at(22, 0), at(6, 0))));
+        assert_eq!(due_digest(&p, on(21, 6, 30)), Some(on(20, 23, 0)));
+        assert_eq!(due_digest(&p, on(21, 7, 10)), Some(on(21, 7, 0)));
+        assert_eq!(due_digest(&p, on(21, 9, 0)), None);
+    }
+}
@@ -0,0 +1,239 @@
+// backend/src/similarity.rs
+//
+// Local duplicate detection: every assignment gets a term vector (title,
+// description, numbers, assignment type) stored in `assignment_vectors`, and
+// a new announcement is compared against the course's recent assignments by
+// TF-IDF cosine. Clear matches and clear misses are decided here; only the
+// scores in between are sent to the LLM.
+
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+use crate::parser::ai_extractor::{extract_assignment_type, extract_numbers};
+
+/// Stored next to every vector; vectors from another model are recomputed
+pub const MODEL: &str = "tfidf-v1";
+
+/// At or above this (and clearly ahead of the runner-up) → same assignment, no LLM
+const DUPLICATE_SCORE: f32 = 0.75;
+/// Below this for every candidate → new assignment, no LLM
+const DISTINCT_SCORE: f32 = 0.3;
+/// A duplicate must beat the second-best candidate by this much
+const MARGIN: f32 = 0.15;
+/// How many ambiguous candidates the LLM gets to choose from
+const MAX_ESCALATED: usize = 3;
+
+const TITLE_WEIGHT: f32 = 2.0;
+const DESCRIPTION_WEIGHT: f32 = 1.0;
+const NUMBER_WEIGHT: f32 = 3.0;
+const TYPE_WEIGHT: f32 = 1.0;
+
+const STOPWORDS: &[&str] = &[
+    "yang", "dan", "di", "ke", "dari", "untuk", "dengan", "pada", "ini", "itu", "atau", "ada",
+    "akan", "sudah", "udah", "bisa", "juga", "jangan", "lupa", "ya", "yaa", "yg", "dgn", "utk",
+    "teman", "teman2", "temen", "guys", "kak", "mohon", "tolong", "silakan", "silahkan", "info",
+    "the", "a", "an", "of", "and", "to", "in", "on", "for", "is", "are", "be", "with", "by",
+    "tentang", "dalam", "sebelum", "paling", "lambat", "jam", "pukul", "tanggal", "tgl",
+];
+
+/// Term → weight. Title numbers are "#15", the assignment type is "type:lab"
+pub type TermVector = HashMap<String, f32>;
+
+pub fn term_vector(title: &str, description: &str) -> TermVector {
+    let mut vector = TermVector::new();
+    let mut add = |term: String, weight: f32| {
+        let entry = vector.entry(term).or_insert(0.0);
+        // Presence, not counts: a long description shouldn't drown the title
+        *entry = entry.max(weight);
+    };
+
+    for word in words(title) {
+        add(word, TITLE_WEIGHT);
+    }
+    for word in words(description) {
+        add(word, DESCRIPTION_WEIGHT);
+    }
+    // Only the title's numbers: the description is full of dates
+    for number in extract_numbers(title) {
+        add(format!("#{}", number), NUMBER_WEIGHT);
+    }
+    if let Some(kind) = extract_assignment_type(title) {
+        add(format!("type:{}", kind), TYPE_WEIGHT);
+    }
+    vector
+}
+
+fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .filter(|w| w.chars().count() >= 2)
+        .filter(|w| !w.chars().all(|c| c.is_ascii_digit()))
+        .filter(|w| !STOPWORDS.contains(&w.as_str()))
+}
+
+/// Document frequencies the IDF weights come from
+#[derive(Debug, Default)]
+pub struct Corpus {
+    documents: usize,
+    frequencies: HashMap<String, usize>,
+}
+
+impl Corpus {
+    pub fn new(documents: usize, frequencies: HashMap<String, usize>) -> Self {
+        Corpus { documents, frequencies }
+    }
+
+    /// When the stored statistics are unavailable: just the vectors at hand
+    pub fn from_vectors<'a>(vectors: impl IntoIterator<Item = &'a TermVector>) -> Self {
+        let mut corpus = Corpus::default();
+        for vector in vectors {
+            corpus.documents += 1;
+            for term in vector.keys() {
+                *corpus.frequencies.entry(term.clone()).or_insert(0) += 1;
+            }
+        }
+        corpus
+    }
+
+    fn idf(&self, term: &str) -> f32 {
+        let frequency = self.frequencies.get(term).copied().unwrap_or(0);
+        ((1 + self.documents) as f32 / (1 + frequency) as f32).ln() + 1.0
+    }
+}
+
+fn features<'a>(vector: &'a TermVector, prefix: &str) -> HashSet<&'a str> {
+    vector.keys().map(String::as_str).filter(|t| t.starts_with(prefix)).collect()
+}
+
+/// TF-IDF cosine in 0..=1; `None` when the numbers or types contradict ("LKP 5" vs "LKP 6")
+pub fn score(a: &TermVector, b: &TermVector, corpus: &Corpus) -> Option<f32> {
+    for prefix in ["#", "type:"] {
+        let (fa, fb) = (features(a, prefix), features(b, prefix));
+        if !fa.is_empty() && !fb.is_empty() && fa != fb {
+            return None;
+        }
+    }
+
+    let norm = |v: &TermVector| v.iter().map(|(t, w)| (w * corpus.idf(t)).powi(2)).sum::<f32>().sqrt();
+    let (norm_a, norm_b) = (norm(a), norm(b));
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return Some(0.0);
+    }
+
+    let dot: f32 = a
+        .iter()
+        .filter_map(|(t, wa)| b.get(t).map(|wb| wa * wb * corpus.idf(t).powi(2)))
+        .sum();
+    Some((dot / (norm_a * norm_b)).min(1.0))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    Duplicate { id: Uuid, score: f32 },
+    /// `best` is the highest score seen (0 when every candidate contradicted)
+    Distinct { best: f32 },
+    /// Best first, at most MAX_ESCALATED
+    Ambiguous(Vec<(Uuid, f32)>),
+}
+
+pub fn judge(new: &TermVector, candidates: &[(Uuid, TermVector)], corpus: &Corpus) -> Verdict {
+    let mut scored: Vec<(Uuid, f32)> = candidates
+        .iter()
+        .filter_map(|(id, vector)| score(new, vector, corpus).map(|s| (*id, s)))
+        .collect();
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    let best = scored.first().map(|(_, s)| *s).unwrap_or(0.0);
+    let runner_up = scored.get(1).map(|(_, s)| *s).unwrap_or(0.0);
+
+    if best < DISTINCT_SCORE {
+        return Verdict::Distinct { best };
+    }
+    if best >= DUPLICATE_SCORE && best - runner_up >= MARGIN {
+        return Verdict::Duplicate { id: scored[0].0, score: best };
+    }
+    scored.retain(|(_, s)| *s >= DISTINCT_SCORE);
+    scored.truncate(MAX_ESCALATED);
+    Verdict::Ambiguous(scored)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(n: u128) -> Uuid {
+        Uuid::from_u128(n)
+    }
+
+    /// A course's worth of assignments, so the IDF looks like production
+    fn corpus_with(extra: &[&TermVector]) -> Corpus {
+        let background = [
+            term_vector("LKP 3", "Kerjakan LKP 3 tentang array, kumpul di elena"),
+            term_vector("LKP 4", "LKP 4 stack dan queue, kumpul di elena"),
+            term_vector("Quiz 1", "Quiz 1 materi pertemuan 1-4"),
+            term_vector("Tugas Makalah", "Makalah kelompok tentang etika profesi"),
+            term_vector("Laporan Praktikum 2", "Laporan praktikum modul 2"),
+        ];
+        Corpus::from_vectors(background.iter().chain(extra.iter().copied()))
+    }
+
+    #[test]
+    fn test_term_vector_features() {
+        let vector = term_vector("LKP 15", "Dikumpulkan di elena paling lambat 12 Oktober");
+
+        assert_eq!(vector.get("lkp"), Some(&TITLE_WEIGHT));
+        assert_eq!(vector.get("#15"), Some(&NUMBER_WEIGHT));
+        assert_eq!(vector.get("type:lab"), Some(&TYPE_WEIGHT));
+        assert_eq!(vector.get("elena"), Some(&DESCRIPTION_WEIGHT));
+        // Description numbers and stopwords are not terms
+        assert!(!vector.contains_key("#12"));
+        assert!(!vector.contains_key("12"));
+        assert!(!vector.contains_key("paling"));
+    }
+
+    #[test]
+    fn test_contradicting_numbers_or_types_never_match() {
+        let corpus = Corpus::default();
+        let lkp5 = term_vector("LKP 5", "linked list");
+
+        assert_eq!(score(&lkp5, &term_vector("LKP 6", "linked list"), &corpus), None);
+        assert_eq!(score(&term_vector("Quiz 2", ""), &term_vector("UTS 2", ""), &corpus), None);
+        // Missing on one side is not a contradiction
+        assert!(score(&lkp5, &term_vector("LKP", "linked list"), &corpus).is_some());
+    }
+
+    #[test]
+    fn test_reannouncement_is_duplicate_without_llm() {
+        let existing = term_vector("LKP 5", "Kerjakan LKP 5 linked list, kumpul di elena");
+        let new = term_vector("LKP 5", "Reminder LKP 5 linked list dikumpul di elena ya");
+        let corpus = corpus_with(&[&existing]);
+
+        let verdict = judge(&new, &[(id(1), existing), (id(2), term_vector("LKP 4", "stack dan queue"))], &corpus);
+        assert!(matches!(verdict, Verdict::Duplicate { id: found, .. } if found == id(1)), "{:?}", verdict);
+    }
+
+    #[test]
+    fn test_unrelated_assignment_is_distinct_without_llm() {
+        let existing = term_vector("Quiz 1", "Quiz 1 materi pertemuan 1-4");
+        let new = term_vector("Tugas Makalah", "Makalah kelompok tentang etika profesi");
+        let corpus = corpus_with(&[]);
+
+        assert!(matches!(judge(&new, &[(id(1), existing)], &corpus), Verdict::Distinct { .. }));
+        assert_eq!(judge(&new, &[], &corpus), Verdict::Distinct { best: 0.0 });
+    }
+
+    #[test]
+    fn test_close_candidates_are_escalated() {
+        let new = term_vector("Tugas Makalah", "Makalah individu tentang etika profesi");
+        let candidates: Vec<(Uuid, TermVector)> = (1..=5)
+            .map(|n| (id(n), term_vector("Tugas Makalah", "Makalah tentang etika profesi")))
+            .collect();
+        let corpus = corpus_with(&[]);
+
+        // Five equally good matches: no clear winner, the LLM picks from the top three
+        match judge(&new, &candidates, &corpus) {
+            Verdict::Ambiguous(top) => assert_eq!(top.len(), MAX_ESCALATED),
+            other => panic!("expected Ambiguous, got {:?}", other),
+        }
+    }
+}
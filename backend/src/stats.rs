@@ -0,0 +1,184 @@
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+
+/// One assignment from a user's point of view (input for personal stats)
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct CompletionRecord {
+    pub course_name: String,
+    pub deadline: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// Class-wide completion counts for one assignment (no user ids, only counts)
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct AssignmentCompletionCount {
+    pub title: String,
+    pub parallel_code: Option<String>,
+    pub deadline: Option<DateTime<Utc>>,
+    pub completed: i64,
+    pub on_time: i64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CourseBreakdown {
+    pub course_name: String,
+    pub total: usize,
+    pub completed: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PersonalStats {
+    pub total: usize,
+    pub completed: usize,
+    /// Completed assignments that had a deadline (denominator for on-time rate)
+    pub completed_with_deadline: usize,
+    pub on_time: usize,
+    pub current_streak: usize,
+    pub best_streak: usize,
+    pub per_course: Vec<CourseBreakdown>,
+}
+
+impl PersonalStats {
+    pub fn completion_rate(&self) -> Option<f32> {
+        (self.total > 0).then(|| self.completed as f32 / self.total as f32)
+    }
+
+    pub fn on_time_rate(&self) -> Option<f32> {
+        (self.completed_with_deadline > 0).then(|| self.on_time as f32 / self.completed_with_deadline as f32)
+    }
+}
+
+/// Compute personal stats.
+///
+/// `records` should contain every assignment that is either already past its
+/// deadline or completed by the user (hidden ones excluded by the caller).
+/// Streaks count consecutive assignments — ordered by deadline — that were
+/// finished on time; a missed or late one breaks the streak.
+pub fn compute_personal_stats(records: &[CompletionRecord], now: DateTime<Utc>) -> PersonalStats {
+    let total = records.len();
+    let completed = records.iter().filter(|r| r.completed_at.is_some()).count();
+
+    let completed_with_deadline = records
+        .iter()
+        .filter(|r| r.completed_at.is_some() && r.deadline.is_some())
+        .count();
+    let on_time = records.iter().filter(|r| is_on_time(r)).count();
+
+    // Streaks only look at assignments whose deadline has passed (or that are already done)
+    let mut due: Vec<&CompletionRecord> = records
+        .iter()
+        .filter(|r| matches!(r.deadline, Some(d) if d <= now || r.completed_at.is_some()))
+        .collect();
+    due.sort_by_key(|r| r.deadline);
+
+    let mut best_streak = 0;
+    let mut run = 0;
+    for r in &due {
+        if is_on_time(r) {
+            run += 1;
+            best_streak = best_streak.max(run);
+        } else {
+            run = 0;
+        }
+    }
+    let current_streak = run;
+
+    let mut courses: BTreeMap<&str, (usize, usize)> = BTreeMap::new();
+    for r in records {
+        let entry = courses.entry(r.course_name.as_str()).or_default();
+        entry.0 += 1;
+        if r.completed_at.is_some() {
+            entry.1 += 1;
+        }
+    }
+
+    let per_course = courses
+        .into_iter()
+        .map(|(name, (total, completed))| CourseBreakdown {
+            course_name: name.to_string(),
+            total,
+            completed,
+        })
+        .collect();
+
+    PersonalStats {
+        total,
+        completed,
+        completed_with_deadline,
+        on_time,
+        current_streak,
+        best_streak,
+        per_course,
+    }
+}
+
+fn is_on_time(record: &CompletionRecord) -> bool {
+    match (record.completed_at, record.deadline) {
+        (Some(done), Some(deadline)) => done <= deadline,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn record(course: &str, deadline_day: Option<u32>, done_day: Option<u32>) -> CompletionRecord {
+        let at = |d: u32| Utc.with_ymd_and_hms(2026, 3, d, 12, 0, 0).unwrap();
+        CompletionRecord {
+            course_name: course.to_string(),
+            deadline: deadline_day.map(at),
+            completed_at: done_day.map(at),
+        }
+    }
+
+    #[test]
+    fn test_rates_and_breakdown() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 20, 0, 0, 0).unwrap();
+        let records = vec![
+            record("Pemrograman", Some(1), Some(1)),
+            record("Pemrograman", Some(5), Some(6)),   // late
+            record("Struktur Data", Some(10), None),   // missed
+            record("Struktur Data", None, Some(12)),   // no deadline
+        ];
+
+        let stats = compute_personal_stats(&records, now);
+        assert_eq!(stats.total, 4);
+        assert_eq!(stats.completed, 3);
+        assert_eq!(stats.completed_with_deadline, 2);
+        assert_eq!(stats.on_time, 1);
+        assert_eq!(stats.on_time_rate(), Some(0.5));
+        assert_eq!(stats.per_course.len(), 2);
+        assert_eq!(stats.per_course[1], CourseBreakdown {
+            course_name: "Struktur Data".to_string(),
+            total: 2,
+            completed: 1,
+        });
+    }
+
+    #[test]
+    fn test_streaks() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 20, 0, 0, 0).unwrap();
+        let records = vec![
+            record("A", Some(1), Some(1)),
+            record("A", Some(2), Some(2)),
+            record("A", Some(3), Some(3)),
+            record("A", Some(4), None),        // missed -> breaks
+            record("A", Some(8), Some(7)),
+            record("A", Some(9), Some(9)),
+        ];
+
+        let stats = compute_personal_stats(&records, now);
+        assert_eq!(stats.best_streak, 3);
+        assert_eq!(stats.current_streak, 2);
+    }
+
+    #[test]
+    fn test_empty_stats() {
+        let stats = compute_personal_stats(&[], Utc::now() + Duration::days(1));
+        assert_eq!(stats.completion_rate(), None);
+        assert_eq!(stats.on_time_rate(), None);
+        assert_eq!(stats.current_streak, 0);
+    }
+}
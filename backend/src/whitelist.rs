@@ -36,6 +36,14 @@ impl Whitelist {
         self.academic_channels.contains(chat_id)
     }
     
+    /// Private chat with the bot (not a group, channel, status / broadcast list or academic DM)
+    pub fn is_private_chat(&self, chat_id: &str) -> bool {
+        !chat_id.ends_with("@g.us")
+            && !chat_id.ends_with("@newsletter")
+            && !chat_id.ends_with("@broadcast")
+            && !self.is_academic_channel(chat_id)
+    }
+    
     /// Check if we should process this message
     /// Returns (should_process, reason)
     pub fn should_process(&self, chat_id: &str, is_command: bool) -> (bool, &'static str) {
@@ -63,4 +71,22 @@ impl Default for Whitelist {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_private_chat() {
+        let mut whitelist = Whitelist { academic_channels: HashSet::new() };
+        whitelist.add_channel("628999@c.us".to_string());
+
+        assert!(whitelist.is_private_chat("628111@c.us"));
+        assert!(!whitelist.is_private_chat("120363xxx@g.us"));
+        assert!(!whitelist.is_private_chat("120363xxx@newsletter"));
+        assert!(!whitelist.is_private_chat("628999@c.us"));
+        // Status updates and broadcast lists are not someone asking the bot
+        assert!(!whitelist.is_private_chat("status@broadcast"));
+        assert!(!whitelist.is_private_chat("1700000000@broadcast"));
+    }
+}
@@ -0,0 +1,53 @@
+// backend/tests/eval_cassette.rs
+//
+// Runs the eval binary over the committed golden set and cassette, so a
+// prompt or pipeline change that makes the recordings stale (or breaks a
+// case) fails here instead of on the next manual eval run. The cassette
+// holds full request bodies, so any prompt wording change counts as stale.
+
+use serde_json::Value;
+use std::process::Command;
+
+#[test]
+fn golden_set_replays_cleanly() {
+    let report_path = std::env::temp_dir().join(format!("eval-report-{}.md", std::process::id()));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_eval"))
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        // Default model chains, the ones the cassette was recorded with
+        .env("LLM_MODELS_FILE", "tests/fixtures/no_such_models_file.json")
+        .args(["--corpus", "eval/golden.json", "--cassette", "eval/cassette.json", "--report"])
+        .arg(&report_path)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let report = std::fs::read_to_string(&report_path).unwrap();
+    std::fs::remove_file(&report_path).ok();
+
+    assert!(!report.contains("## Unrecorded requests"), "{}", report);
+    assert!(report.contains("Extraction failures: 0"), "{}", report);
+    assert!(report.contains("Duplicate / update matching: 4/4 correct"), "{}", report);
+    assert!(report.contains("## Diffs\n\n_none_"), "{}", report);
+    for field in ["course", "deadline", "parallel", "type"] {
+        assert!(report.contains(&format!("| {} | 1.00 | 1.00 |", field)), "{}", report);
+    }
+}
+
+/// Replay only goes stale if the prompt is part of what is matched: a
+/// `{"model": ...}`-only request would keep answering after any prompt edit
+#[test]
+fn cassette_requests_carry_the_prompt() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/eval/cassette.json");
+    let interactions: Vec<Value> = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+
+    for (i, interaction) in interactions.iter().enumerate() {
+        let request = &interaction["request"];
+        assert!(
+            request.get("messages").or_else(|| request.get("contents")).is_some(),
+            "interaction {} has no prompt in its request: {}",
+            i,
+            request
+        );
+    }
+}
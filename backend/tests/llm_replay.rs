@@ -0,0 +1,181 @@
+// backend/tests/llm_replay.rs
+//
+// Extraction / matching paths against recorded LLM traffic (tests/fixtures).
+// No network and no API keys: every provider call is served by a scoped
+// cassette, and a request without a recording fails the call.
+
+use chrono::{TimeZone, Utc};
+use sqlx::postgres::PgPoolOptions;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+use whatsapp_backend::models::{AIClassification, Assignment};
+use whatsapp_backend::parser::ai_extractor::{extract_with_ai, match_update_to_assignment, Cassette};
+
+const PEMROG: Uuid = Uuid::from_u128(1);
+const LKP_5: Uuid = Uuid::from_u128(0xa1);
+
+fn cassette(fixture: &str) -> Arc<Cassette> {
+    // Default model chains, whatever llm_models.json a developer has locally
+    std::env::set_var("LLM_MODELS_FILE", "tests/fixtures/no_such_models_file.json");
+    Arc::new(Cassette::replay(&format!("tests/fixtures/{}.json", fixture)).unwrap())
+}
+
+/// Never connects: context builder lookups fail fast and degrade
+fn offline_pool() -> PgPool {
+    PgPoolOptions::new()
+        .acquire_timeout(Duration::from_millis(100))
+        .connect_lazy("postgres://test@127.0.0.1:1/test")
+        .unwrap()
+}
+
+fn course_map() -> HashMap<Uuid, String> {
+    HashMap::from([(PEMROG, "KOM120C - Pemrograman".to_string())])
+}
+
+fn existing() -> Vec<Assignment> {
+    vec![Assignment {
+        id: LKP_5,
+        created_at: Utc.with_ymd_and_hms(2026, 10, 15, 2, 0, 0).unwrap(),
+        course_id: Some(PEMROG),
+        title: "LKP 5".to_string(),
+        description: "Implementasi linked list".to_string(),
+        deadline: None,
+        parallel_code: Some("k1".to_string()),
+        sender_id: None,
+        message_ids: Vec::new(),
+    }]
+}
+
+async fn extract(text: &str, image_base64: Option<&str>) -> Result<AIClassification, String> {
+    extract_with_image_text(text, image_base64, None).await
+}
+
+async fn extract_with_image_text(
+    text: &str,
+    image_base64: Option<&str>,
+    image_text: Option<&str>,
+) -> Result<AIClassification, String> {
+    extract_with_ai(
+        text,
+        "- KOM120C - Pemrograman (aliases: pemrog)",
+        &existing(),
+        &course_map(),
+        image_base64,
+        image_text,
+        "628111@c.us",
+        &offline_pool(),
+        None,
+    )
+    .await
+}
+
+fn assert_lkp_6(result: Result<AIClassification, String>) {
+    match result {
+        Ok(AIClassification::AssignmentInfo { course_name, title, parallel_code, low_confidence, .. }) => {
+            assert_eq!(course_name.as_deref(), Some("KOM120C - Pemrograman"));
+            assert_eq!(title, "LKP 6");
+            assert_eq!(parallel_code.as_deref(), Some("k1"));
+            assert!(!low_confidence);
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+fn assert_fully_replayed(cassette: &Cassette) {
+    assert_eq!(cassette.unmatched(), Vec::<String>::new());
+    assert_eq!(cassette.unused(), 0);
+}
+
+#[tokio::test]
+async fn extraction_falls_back_past_rate_limit_and_garbage() {
+    // gpt-oss-120b → 429, deepseek → prose instead of JSON (twice, with repair), gpt-oss-20b → answer
+    let cassette = cassette("extraction_fallback");
+
+    let result = cassette
+        .clone()
+        .scope(extract("LKP 6 pemrog K1 stack & queue, deadline Rabu 23:59", None))
+        .await;
+
+    assert_lkp_6(result);
+    assert_fully_replayed(&cassette);
+}
+
+#[tokio::test]
+async fn invalid_fields_are_repaired_by_same_model() {
+    // gpt-oss-120b answers "Pemrog" / "Rabu 23:59" → repair prompt → fixed answer, no fallback
+    let cassette = cassette("extraction_repair");
+
+    let result = cassette
+        .clone()
+        .scope(extract("LKP 6 pemrog K1 stack & queue, deadline Rabu 23:59", None))
+        .await;
+
+    assert_lkp_6(result);
+    assert_fully_replayed(&cassette);
+}
+
+#[tokio::test]
+async fn vision_unrecognized_retries_text_only() {
+    // Vision model says "unrecognized" (meme) → text chain still extracts the task
+    let cassette = cassette("vision_retry");
+
+    let result = cassette
+        .clone()
+        .scope(extract("LKP 6 pemrog K1 stack & queue, deadline Rabu 23:59", Some("AAAA")))
+        .await;
+
+    assert_lkp_6(result);
+    assert_fully_replayed(&cassette);
+}
+
+#[tokio::test]
+async fn vision_outage_falls_back_to_ocr_text() {
+    // Both vision models 503 → the text chain gets the screenshot's OCR text instead of nothing
+    let cassette = cassette("vision_down_ocr");
+    let ocr = "Pemrograman K1\nLKP 6: Stack & Queue\nDue: Wednesday, 21 October 2026, 11:59 PM";
+
+    let result = cassette
+        .clone()
+        .scope(extract_with_image_text("", Some("AAAA"), Some(ocr)))
+        .await;
+
+    assert_lkp_6(result);
+    assert_fully_replayed(&cassette);
+}
+
+#[tokio::test]
+async fn update_matching_skips_overloaded_model() {
+    let cassette = cassette("update_matching");
+
+    let result = cassette
+        .clone()
+        .scope(match_update_to_assignment(
+            "Deadline diundur jadi Kamis 23:59",
+            &["Pemrograman".to_string(), "LKP 5".to_string()],
+            &existing(),
+            &course_map(),
+            Some("k1"),
+        ))
+        .await;
+
+    assert_eq!(result, Ok(Some(LKP_5)));
+    assert_fully_replayed(&cassette);
+}
+
+#[tokio::test]
+async fn unrecorded_requests_fail() {
+    let cassette = cassette("empty");
+
+    let result = cassette
+        .clone()
+        .scope(match_update_to_assignment("Deadline diundur", &[], &existing(), &course_map(), None))
+        .await;
+
+    assert!(result.is_err());
+    assert!(!cassette.unmatched().is_empty());
+    assert!(cassette.unmatched().iter().all(|u| u.contains("generativelanguage.googleapis.com")));
+}